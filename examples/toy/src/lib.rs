@@ -7,7 +7,8 @@ use {
     async_trait::async_trait,
     serde_json::Value,
     solidmcp::{
-        framework::{PromptProvider, ResourceProvider},
+        content_types::Role,
+        framework::{PromptProvider, ResourceProvider, ResourceRoute},
         handler::{
             PromptArgument, PromptContent, PromptInfo, PromptMessage, ResourceContent, ResourceInfo,
         },
@@ -93,12 +94,17 @@ impl ResourceProvider<NotesContext> for NotesResourceProvider {
                 name: note_name.clone(),
                 description: Some(format!("Markdown note: {}", note_name)),
                 mime_type: Some("text/markdown".to_string()),
+                ..Default::default()
             });
         }
 
         Ok(resources)
     }
 
+    fn routes(&self) -> Vec<ResourceRoute> {
+        vec![ResourceRoute::scheme("note")]
+    }
+
     async fn read_resource(
         &self,
         uri: &str,
@@ -109,7 +115,7 @@ impl ResourceProvider<NotesContext> for NotesResourceProvider {
                 return Ok(ResourceContent {
                     uri: uri.to_string(),
                     mime_type: Some("text/markdown".to_string()),
-                    content,
+                    content: content.into(),
                 });
             }
         }
@@ -173,6 +179,12 @@ impl PromptProvider<NotesContext> for NotesPromptProvider {
         ])
     }
 
+    fn prompt_names(&self) -> Vec<String> {
+        ["meeting_notes", "task_note", "daily_journal"]
+            .map(String::from)
+            .to_vec()
+    }
+
     async fn get_prompt(
         &self,
         name: &str,
@@ -188,12 +200,13 @@ impl PromptProvider<NotesContext> for NotesPromptProvider {
 
                 Ok(PromptContent {
                     messages: vec![PromptMessage {
-                        role: "user".to_string(),
+                        role: Role::User,
                         content: format!(
                             "# {}\n\n## Attendees\n{}\n\n## Agenda\n- \n\n## Discussion\n\n\n## Action Items\n- [ ] \n\n## Next Steps\n\n",
                             meeting_title, attendees
-                        ),
+                        ).into(),
                     }],
+                    ..Default::default()
                 })
             }
             "task_note" => {
@@ -203,12 +216,13 @@ impl PromptProvider<NotesContext> for NotesPromptProvider {
 
                 Ok(PromptContent {
                     messages: vec![PromptMessage {
-                        role: "user".to_string(),
+                        role: Role::User,
                         content: format!(
                             "# Task: {}\n\n**Priority**: {}\n**Due Date**: {}\n\n## Description\n\n\n## Requirements\n- \n\n## Progress\n- [ ] \n\n## Notes\n\n",
                             task_name, priority, due_date
-                        ),
+                        ).into(),
                     }],
+                    ..Default::default()
                 })
             }
             "daily_journal" => {
@@ -217,12 +231,13 @@ impl PromptProvider<NotesContext> for NotesPromptProvider {
 
                 Ok(PromptContent {
                     messages: vec![PromptMessage {
-                        role: "user".to_string(),
+                        role: Role::User,
                         content: format!(
                             "# Daily Journal - {}\n\n## How I'm Feeling\n\n\n## What Happened Today\n\n\n## Accomplishments\n- \n\n## Challenges\n\n\n## Tomorrow's Goals\n- \n\n## Gratitude\n- \n\n",
                             date
-                        ),
+                        ).into(),
                     }],
+                    ..Default::default()
                 })
             }
             _ => Err(anyhow::anyhow!("Prompt not found: {}", name)),
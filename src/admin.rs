@@ -0,0 +1,217 @@
+//! Admin API
+//!
+//! An optional HTTP API for operators to inspect and act on a running server.
+//! Every request must authenticate with the API's own [`Authenticator`],
+//! separate from the one guarding `/mcp`, and optionally hold a scope.
+//!
+//! | Request | Action |
+//! |---------|--------|
+//! | `GET {path}/sessions` | List initialized sessions (`SessionInfo`) |
+//! | `DELETE {path}/sessions/{id}` | Terminate a session, closing its WebSocket |
+//! | `GET {path}/requests` | List in-flight requests (`InFlightRequest`) |
+//! | `DELETE {path}/requests/{id}` | Cancel an in-flight request |
+//!
+//! `DELETE` answers `204 No Content`, or `404 Not Found` if there was no such
+//! session or request. Requests that fail authentication get `401`, principals
+//! missing the required scope get `403`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use solidmcp::admin::AdminApi;
+//! use solidmcp::auth::{ApiKeyAuthenticator, Principal};
+//!
+//! let admin = AdminApi::new(
+//!     ApiKeyAuthenticator::new().with_key("sk-ops", Principal::new("oncall").with_scopes(["admin"])),
+//! )
+//! .require_scope("admin");
+//!
+//! let server = McpServerBuilder::new(context, "my-server", "1.0.0")
+//!     .with_admin_api(admin)
+//!     .build()
+//!     .await?;
+//! ```
+//!
+//! ```text
+//! curl -H "Authorization: Bearer sk-ops" http://localhost:3000/admin/requests
+//! curl -X DELETE -H "Authorization: Bearer sk-ops" http://localhost:3000/admin/requests/42
+//! ```
+
+use {
+    crate::{
+        auth::{self, Authenticator},
+        handler::ConnectionInfo,
+        shared::McpProtocolEngine,
+        tls::{self, ClientCertificate},
+    },
+    serde_json::json,
+    std::{net::SocketAddr, sync::Arc},
+    tracing::info,
+    warp::{
+        filters::path::FullPath,
+        http::{HeaderMap, Method, StatusCode},
+        reply::Response,
+        Filter, Rejection, Reply,
+    },
+};
+
+/// Path the admin API is served under unless configured otherwise.
+pub const DEFAULT_ADMIN_PATH: &str = "/admin";
+
+/// Configuration of the admin API: who may use it and where it is served.
+#[derive(Clone)]
+pub struct AdminApi {
+    authenticator: Arc<dyn Authenticator>,
+    path: String,
+    scope: Option<String>,
+}
+
+impl AdminApi {
+    /// Serve the admin API at `/admin` to every principal `authenticator` accepts.
+    ///
+    /// # Parameters
+    /// - `authenticator`: Decides who each admin request comes from
+    pub fn new(authenticator: impl Authenticator + 'static) -> Self {
+        Self {
+            authenticator: Arc::new(authenticator),
+            path: DEFAULT_ADMIN_PATH.to_string(),
+            scope: None,
+        }
+    }
+
+    /// Serve the admin API under `path` instead of `/admin`.
+    ///
+    /// # Parameters
+    /// - `path`: The HTTP path prefix, e.g. `/internal/admin`
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Only accept principals holding `scope`.
+    ///
+    /// # Parameters
+    /// - `scope`: Scope the principal must have been granted
+    pub fn require_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    /// Get the path prefix the API is served under.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Match a request against the API's endpoints.
+    fn route(&self, method: &Method, path: &str) -> Option<Route> {
+        let rest = path
+            .trim_matches('/')
+            .strip_prefix(self.path.trim_matches('/'))?;
+        let segments: Vec<&str> = rest.split('/').filter(|s| !s.is_empty()).collect();
+        // The prefix must end at a segment boundary
+        if !rest.is_empty() && !rest.starts_with('/') {
+            return None;
+        }
+        match (method, segments.as_slice()) {
+            (&Method::GET, ["sessions"]) => Some(Route::Sessions),
+            (&Method::DELETE, ["sessions", id]) => Some(Route::TerminateSession(id.to_string())),
+            (&Method::GET, ["requests"]) => Some(Route::Requests),
+            (&Method::DELETE, ["requests", id]) => id.parse().ok().map(Route::CancelRequest),
+            _ => None,
+        }
+    }
+}
+
+/// An admin API endpoint.
+enum Route {
+    Sessions,
+    TerminateSession(String),
+    Requests,
+    CancelRequest(u64),
+}
+
+impl Route {
+    async fn respond(self, engine: &McpProtocolEngine, principal: &str) -> Response {
+        match self {
+            Route::Sessions => {
+                warp::reply::json(&json!({ "sessions": engine.sessions() })).into_response()
+            }
+            Route::Requests => warp::reply::json(&json!({
+                "requests": engine.in_flight_requests()
+            }))
+            .into_response(),
+            Route::TerminateSession(session_id) => {
+                let found = engine.terminate_session(&session_id).await;
+                if found {
+                    info!("Admin {} terminated session {}", principal, session_id);
+                }
+                deleted(found)
+            }
+            Route::CancelRequest(id) => {
+                let found = engine.cancel_request(id);
+                if found {
+                    info!("Admin {} cancelled request {}", principal, id);
+                }
+                deleted(found)
+            }
+        }
+    }
+}
+
+fn deleted(found: bool) -> Response {
+    let status = if found {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    };
+    warp::reply::with_status(warp::reply::reply(), status).into_response()
+}
+
+/// Create the filter serving the engine's admin API, if it has one.
+pub(crate) fn routes(
+    engine: Arc<McpProtocolEngine>,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::method()
+        .and(warp::path::full())
+        .and(tls::peer())
+        .and(warp::header::headers_cloned())
+        .and_then(
+            move |method: Method,
+                  full: FullPath,
+                  remote_addr: Option<SocketAddr>,
+                  client_certificate: Option<ClientCertificate>,
+                  headers: HeaderMap| {
+                let engine = engine.clone();
+                async move {
+                    let Some(admin) = engine.admin_api() else {
+                        return Err(warp::reject::not_found());
+                    };
+                    let Some(route) = admin.route(&method, full.as_str()) else {
+                        return Err(warp::reject::not_found());
+                    };
+                    let connection = ConnectionInfo {
+                        remote_addr,
+                        headers,
+                        principal: None,
+                        client_certificate,
+                    };
+                    let connection =
+                        auth::authenticate(admin.authenticator.as_ref(), connection).await?;
+                    let principal = connection.principal.unwrap_or_default();
+                    if let Some(scope) = &admin.scope {
+                        if !principal.has_scope(scope) {
+                            let body =
+                                json!({ "error": format!("admin API requires scope {scope}") });
+                            return Ok(warp::reply::with_status(
+                                warp::reply::json(&body),
+                                StatusCode::FORBIDDEN,
+                            )
+                            .into_response());
+                        }
+                    }
+                    Ok(route.respond(&engine, &principal.id).await)
+                }
+            },
+        )
+        .recover(auth::handle_rejection)
+}
@@ -0,0 +1,408 @@
+//! Audit Logging
+//!
+//! This module records every tool call a `FrameworkHandler` dispatches: who
+//! made it (session, principal, client and remote address), which tool with
+//! which arguments, when it started, how long it took and whether it failed.
+//!
+//! Records go to an [`AuditSink`]. The built-in [`JsonLinesAuditSink`] appends
+//! one JSON object per line to a file from a background thread, so requests
+//! never wait for the disk. A call whose request is dropped before the tool
+//! returns (cancelled, its session terminated or its client disconnected) is
+//! recorded as [`AuditOutcome::Cancelled`]. Arguments are redacted before they
+//! reach the sink:
+//!
+//! - `redact_field` hides a field by name wherever it appears in any tool's arguments
+//! - `redact_path` hides one field of one tool's arguments by its dotted path
+//!
+//! # Example
+//!
+//! ```rust
+//! use solidmcp::audit::{AuditLog, JsonLinesAuditSink};
+//!
+//! let audit = AuditLog::new(JsonLinesAuditSink::open("/var/log/mcp/audit.jsonl")?)
+//!     .redact_field("password")
+//!     .redact_path("deploy", "credentials.token");
+//!
+//! let handler = McpServerBuilder::new(context, "my-server", "1.0.0")
+//!     .with_audit_log(audit)
+//!     .build()
+//!     .await?;
+//! ```
+
+use {
+    crate::handler::McpContext,
+    anyhow::Result,
+    chrono::{DateTime, SecondsFormat, Utc},
+    serde::{Deserialize, Serialize},
+    serde_json::Value,
+    std::{
+        collections::{HashMap, HashSet},
+        fs::OpenOptions,
+        io::{self, BufWriter, Write},
+        path::Path,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            mpsc::{self, TrySendError},
+            Arc,
+        },
+        thread,
+        time::{Instant, SystemTime},
+    },
+    tracing::warn,
+};
+
+/// Value that replaces redacted arguments.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// One tool call, as written to the audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// When the call started, in RFC 3339 format (UTC)
+    pub timestamp: String,
+    /// Name of the called tool
+    pub tool: String,
+    /// Session that made the call
+    pub session_id: Option<String>,
+    /// JSON-RPC ID of the `tools/call` request
+    pub request_id: Option<Value>,
+    /// ID of the authenticated principal
+    pub principal: Option<String>,
+    /// `clientInfo` the session sent in `initialize`
+    pub client_info: Option<Value>,
+    /// Remote socket address of the connection
+    pub remote_addr: Option<String>,
+    /// Trace the request belongs to
+    pub trace_id: Option<String>,
+    /// Arguments of the call, after redaction
+    pub arguments: Value,
+    /// How long the call took, in milliseconds
+    pub duration_ms: f64,
+    /// How the call ended
+    pub outcome: AuditOutcome,
+    /// Error message of a failed call
+    pub error: Option<String>,
+}
+
+/// How an audited tool call ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    /// The tool returned a result
+    Success,
+    /// The call failed or returned a result with `isError`
+    Error,
+    /// The request was dropped before the tool returned: cancelled, its
+    /// session terminated or its client disconnected
+    Cancelled,
+}
+
+/// Destination of audit records.
+///
+/// `record` runs on the request path once the tool has returned, or while a
+/// cancelled request is dropped, so implementations should hand the record off
+/// rather than wait for I/O.
+/// Closures taking an `AuditRecord` are sinks too.
+pub trait AuditSink: Send + Sync {
+    /// Record one tool call.
+    fn record(&self, record: AuditRecord);
+}
+
+impl<F> AuditSink for F
+where
+    F: Fn(AuditRecord) + Send + Sync,
+{
+    fn record(&self, record: AuditRecord) {
+        self(record)
+    }
+}
+
+/// Audit sink appending records to a file in JSON Lines format.
+///
+/// Records are written by a background thread, which exits once every clone
+/// of the sink is dropped and the records sent before have been written.
+/// Records wait for the thread in a queue of bounded size; when the writer
+/// falls behind and the queue is full, new records are dropped and counted
+/// rather than held in memory or left to block requests.
+#[derive(Clone)]
+pub struct JsonLinesAuditSink {
+    sender: mpsc::SyncSender<Command>,
+    dropped: Arc<AtomicU64>,
+}
+
+enum Command {
+    Record(Box<AuditRecord>),
+    Flush(mpsc::SyncSender<io::Result<()>>),
+}
+
+impl JsonLinesAuditSink {
+    /// Records the queue of a sink opened with [`open`](Self::open) holds.
+    pub const DEFAULT_CAPACITY: usize = 10_000;
+
+    /// Open `path` for appending, creating it if needed.
+    ///
+    /// # Parameters
+    /// - `path`: The audit log file
+    ///
+    /// # Returns
+    /// The sink, or the error opening the file
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Self::from_writer(file, Self::DEFAULT_CAPACITY)
+    }
+
+    /// Write records to `writer`, queueing at most `capacity` of them.
+    ///
+    /// # Parameters
+    /// - `writer`: Where the lines go, e.g. a file or standard output
+    /// - `capacity`: Records that may wait for the writer before new ones are dropped
+    ///
+    /// # Returns
+    /// The sink, or the error starting its thread
+    pub fn from_writer(writer: impl Write + Send + 'static, capacity: usize) -> io::Result<Self> {
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        thread::Builder::new()
+            .name("solidmcp-audit".to_string())
+            .spawn(move || write_records(writer, receiver))?;
+        Ok(Self {
+            sender,
+            dropped: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    /// Wait until every record sent so far is written.
+    ///
+    /// # Returns
+    /// The error of the last write, if it failed
+    pub fn flush(&self) -> io::Result<()> {
+        let (reply, done) = mpsc::sync_channel(1);
+        self.sender
+            .send(Command::Flush(reply))
+            .map_err(|_| io::Error::other("audit writer stopped"))?;
+        done.recv()
+            .map_err(|_| io::Error::other("audit writer stopped"))?
+    }
+
+    /// Get the number of records dropped because the queue was full.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl AuditSink for JsonLinesAuditSink {
+    fn record(&self, record: AuditRecord) {
+        match self.sender.try_send(Command::Record(Box::new(record))) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+                // Warn on the 1st, 2nd, 4th, 8th... drop so a stalled disk doesn't flood the log
+                if dropped.is_power_of_two() {
+                    warn!("Audit queue full, {} audit records dropped so far", dropped);
+                }
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                warn!("Audit writer stopped, dropping audit record");
+            }
+        }
+    }
+}
+
+/// Write records until every sender is gone, flushing whenever the queue runs empty.
+fn write_records<W: Write>(writer: W, receiver: mpsc::Receiver<Command>) {
+    let mut writer = BufWriter::new(writer);
+    let write = |writer: &mut BufWriter<W>, command| match command {
+        Command::Record(record) => {
+            let written = serde_json::to_writer(&mut *writer, &record)
+                .map_err(io::Error::from)
+                .and_then(|_| writer.write_all(b"\n"));
+            if let Err(e) = written {
+                warn!("Failed to write audit record: {}", e);
+            }
+        }
+        Command::Flush(reply) => {
+            let _ = reply.send(writer.flush());
+        }
+    };
+    while let Ok(command) = receiver.recv() {
+        write(&mut writer, command);
+        while let Ok(command) = receiver.try_recv() {
+            write(&mut writer, command);
+        }
+        if let Err(e) = writer.flush() {
+            warn!("Failed to flush audit log: {}", e);
+        }
+    }
+}
+
+/// Audit configuration of a `FrameworkHandler`: the sink and the redaction rules.
+#[derive(Clone)]
+pub struct AuditLog {
+    sink: Arc<dyn AuditSink>,
+    redacted_fields: HashSet<String>,
+    redacted_paths: HashMap<String, Vec<Vec<String>>>,
+}
+
+impl AuditLog {
+    /// Create an audit log writing to `sink`, with no redaction rules.
+    ///
+    /// # Parameters
+    /// - `sink`: Where records go
+    pub fn new(sink: impl AuditSink + 'static) -> Self {
+        Self {
+            sink: Arc::new(sink),
+            redacted_fields: HashSet::new(),
+            redacted_paths: HashMap::new(),
+        }
+    }
+
+    /// Redact every field called `name`, at any depth of any tool's arguments.
+    ///
+    /// # Parameters
+    /// - `name`: Field name, e.g. `password`
+    pub fn redact_field(mut self, name: impl Into<String>) -> Self {
+        self.redacted_fields.insert(name.into());
+        self
+    }
+
+    /// Redact one field of a tool's arguments.
+    ///
+    /// Segments of the path are separated by dots. A segment reaching an array
+    /// applies to each of its elements.
+    ///
+    /// # Parameters
+    /// - `tool`: Name of the tool
+    /// - `path`: Path of the field, e.g. `credentials.token`
+    pub fn redact_path(mut self, tool: &str, path: &str) -> Self {
+        self.redacted_paths
+            .entry(tool.to_string())
+            .or_default()
+            .push(path.split('.').map(str::to_string).collect());
+        self
+    }
+
+    /// Apply the redaction rules to the arguments of a call.
+    ///
+    /// # Parameters
+    /// - `tool`: Name of the called tool
+    /// - `arguments`: Arguments as the client sent them
+    ///
+    /// # Returns
+    /// A copy of `arguments` with every redacted field replaced by [`REDACTED`]
+    pub fn redact(&self, tool: &str, arguments: &Value) -> Value {
+        let mut redacted = arguments.clone();
+        if !self.redacted_fields.is_empty() {
+            redact_fields(&mut redacted, &self.redacted_fields);
+        }
+        for path in self.redacted_paths.get(tool).into_iter().flatten() {
+            redact_path(&mut redacted, path);
+        }
+        redacted
+    }
+
+    /// Start auditing a call, redacting its arguments before the tool consumes them.
+    ///
+    /// The call is recorded by [`AuditedCall::finish`], or as cancelled if the
+    /// returned guard is dropped first.
+    pub(crate) fn start(&self, tool: &str, arguments: &Value, context: &McpContext) -> AuditedCall {
+        AuditedCall {
+            sink: self.sink.clone(),
+            record: Some(AuditRecord {
+                timestamp: format_timestamp(SystemTime::now()),
+                tool: tool.to_string(),
+                session_id: context.session_id.clone(),
+                request_id: context.request_id.clone(),
+                principal: context.principal.as_ref().map(|p| p.id.clone()),
+                client_info: context.client_info.clone(),
+                remote_addr: context
+                    .connection
+                    .as_ref()
+                    .and_then(|c| c.remote_addr)
+                    .map(|addr| addr.to_string()),
+                trace_id: context.trace.as_ref().map(|t| t.trace_id.clone()),
+                arguments: self.redact(tool, arguments),
+                duration_ms: 0.0,
+                outcome: AuditOutcome::Cancelled,
+                error: None,
+            }),
+            timer: Instant::now(),
+        }
+    }
+}
+
+/// A tool call in progress, as started by [`AuditLog::start`].
+///
+/// Dropping it before [`finish`](Self::finish) records the call as cancelled.
+pub(crate) struct AuditedCall {
+    sink: Arc<dyn AuditSink>,
+    record: Option<AuditRecord>,
+    timer: Instant,
+}
+
+impl AuditedCall {
+    /// Send the record of the finished call to the sink.
+    pub(crate) fn finish(mut self, result: &Result<Value>) {
+        match result {
+            Ok(value) if value.get("isError").and_then(Value::as_bool) == Some(true) => {
+                self.send(AuditOutcome::Error, None)
+            }
+            Ok(_) => self.send(AuditOutcome::Success, None),
+            Err(e) => self.send(AuditOutcome::Error, Some(e.to_string())),
+        }
+    }
+
+    fn send(&mut self, outcome: AuditOutcome, error: Option<String>) {
+        if let Some(mut record) = self.record.take() {
+            record.duration_ms = self.timer.elapsed().as_secs_f64() * 1000.0;
+            record.outcome = outcome;
+            record.error = error;
+            self.sink.record(record);
+        }
+    }
+}
+
+impl Drop for AuditedCall {
+    fn drop(&mut self) {
+        self.send(AuditOutcome::Cancelled, None);
+    }
+}
+
+fn redact_fields(value: &mut Value, fields: &HashSet<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if fields.contains(key) {
+                    *value = Value::from(REDACTED);
+                } else {
+                    redact_fields(value, fields);
+                }
+            }
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| redact_fields(item, fields)),
+        _ => {}
+    }
+}
+
+fn redact_path(value: &mut Value, path: &[String]) {
+    let Some((first, rest)) = path.split_first() else {
+        return;
+    };
+    match value {
+        Value::Object(map) => {
+            if let Some(value) = map.get_mut(first) {
+                if rest.is_empty() {
+                    *value = Value::from(REDACTED);
+                } else {
+                    redact_path(value, rest);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| redact_path(item, path)),
+        _ => {}
+    }
+}
+
+/// Format a time as RFC 3339 in UTC with millisecond precision.
+fn format_timestamp(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::Millis, true)
+}
@@ -0,0 +1,312 @@
+//! MCP Authentication
+//!
+//! This module provides pluggable authentication for the HTTP and WebSocket
+//! transports. An `Authenticator` inspects the headers of each HTTP request (or
+//! of the WebSocket upgrade request) and either yields the authenticated
+//! `Principal` or rejects the request with `401 Unauthorized` and a
+//! `WWW-Authenticate` challenge.
+//!
+//! The principal is carried into `McpContext::principal` for every message, so
+//! tools can make authorization decisions.
+//!
+//! # Example
+//!
+//! ```rust
+//! use solidmcp::auth::{ApiKeyAuthenticator, Principal};
+//!
+//! let auth = ApiKeyAuthenticator::new()
+//!     .with_key("sk-reporting", Principal::new("reporting-bot"))
+//!     .with_key("sk-admin", Principal::new("alice").with_scopes(["admin"]));
+//! ```
+
+use {
+    super::handler::ConnectionInfo,
+    async_trait::async_trait,
+    ring::digest::{digest, SHA256},
+    serde_json::{json, Value},
+    std::collections::HashMap,
+    tracing::debug,
+    warp::{
+        http::{header, HeaderMap, StatusCode},
+        reject::Reject,
+        Rejection, Reply,
+    },
+};
+
+/// The identity a request was authenticated as.
+///
+/// # Fields
+///
+/// - `id`: Stable identifier of the caller (user, service account, key owner)
+/// - `scopes`: Permissions granted to the caller
+/// - `claims`: Any further attributes, e.g. token claims, as a JSON object
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Principal {
+    /// Identifier of the authenticated caller
+    pub id: String,
+    /// Permissions granted to the caller
+    pub scopes: Vec<String>,
+    /// Additional attributes of the caller
+    pub claims: Value,
+}
+
+impl Principal {
+    /// Create a principal with no scopes or claims.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Grant scopes to the principal.
+    pub fn with_scopes<I, S>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.scopes.extend(scopes.into_iter().map(Into::into));
+        self
+    }
+
+    /// Attach additional attributes to the principal.
+    pub fn with_claims(mut self, claims: Value) -> Self {
+        self.claims = claims;
+        self
+    }
+
+    /// Check whether the principal was granted a scope.
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+}
+
+/// Why a request could not be authenticated.
+#[derive(Debug, thiserror::Error)]
+pub enum AuthError {
+    #[error("Missing credentials")]
+    MissingCredentials,
+    #[error("Invalid credentials: {0}")]
+    InvalidCredentials(String),
+}
+
+/// Why an authenticated request was refused.
+///
+/// The HTTP transport answers these with `403 Forbidden`; other transports
+/// with a JSON-RPC error (code -32003).
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum AccessDenied {
+    /// The request named a session that another principal initialized
+    #[error("Forbidden: session {0} belongs to another principal")]
+    ForeignSession(String),
+    /// The principal lacks scopes a tool requires
+    #[error("Forbidden: tool '{tool}' requires scope {scope}")]
+    InsufficientScope {
+        /// Name of the tool
+        tool: String,
+        /// The missing scopes, space-separated
+        scope: String,
+    },
+}
+
+impl AccessDenied {
+    /// The JSON-RPC error code for this error
+    pub fn code(&self) -> i32 {
+        -32003
+    }
+}
+
+/// Decides who a request comes from.
+///
+/// Implementations are called once per HTTP request and once per WebSocket
+/// upgrade. Rejected requests receive `401 Unauthorized` with the header
+/// returned by [`challenge`](Self::challenge).
+///
+/// # Example
+///
+/// ```rust
+/// use solidmcp::auth::{AuthError, Authenticator, Principal};
+/// use solidmcp::handler::ConnectionInfo;
+/// use async_trait::async_trait;
+///
+/// struct TenantHeader;
+///
+/// #[async_trait]
+/// impl Authenticator for TenantHeader {
+///     async fn authenticate(&self, connection: &ConnectionInfo) -> Result<Principal, AuthError> {
+///         let tenant = connection
+///             .headers
+///             .get("x-tenant")
+///             .and_then(|v| v.to_str().ok())
+///             .ok_or(AuthError::MissingCredentials)?;
+///         Ok(Principal::new(tenant))
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait Authenticator: Send + Sync {
+    /// Authenticate a request from its headers and remote address.
+    ///
+    /// # Returns
+    ///
+    /// The authenticated principal, or why the request was rejected
+    async fn authenticate(&self, connection: &ConnectionInfo) -> Result<Principal, AuthError>;
+
+    /// The `WWW-Authenticate` header value sent with a 401 response.
+    ///
+    /// # Default Implementation
+    ///
+    /// A `Bearer` challenge, with `error="invalid_token"` for invalid credentials
+    fn challenge(&self, error: &AuthError) -> String {
+        match error {
+            AuthError::MissingCredentials => r#"Bearer realm="mcp""#.to_string(),
+            AuthError::InvalidCredentials(_) => {
+                r#"Bearer realm="mcp", error="invalid_token""#.to_string()
+            }
+        }
+    }
+}
+
+/// Authenticates requests against a fixed set of API keys.
+///
+/// The key is read from `Authorization: Bearer <key>` or, failing that, from
+/// the `X-API-Key` header. Keys are compared in constant time.
+#[derive(Debug, Clone, Default)]
+pub struct ApiKeyAuthenticator {
+    keys: HashMap<String, Principal>,
+}
+
+impl ApiKeyAuthenticator {
+    /// Create an authenticator that accepts no keys yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept a key, authenticating its bearer as `principal`.
+    pub fn with_key(mut self, key: impl Into<String>, principal: Principal) -> Self {
+        self.keys.insert(key.into(), principal);
+        self
+    }
+}
+
+#[async_trait]
+impl Authenticator for ApiKeyAuthenticator {
+    async fn authenticate(&self, connection: &ConnectionInfo) -> Result<Principal, AuthError> {
+        let presented = bearer_token(&connection.headers)
+            .or_else(|| connection.headers.get("x-api-key")?.to_str().ok())
+            .ok_or(AuthError::MissingCredentials)?;
+
+        // Check every key so timing does not reveal which one nearly matched
+        let mut matched = None;
+        for (key, principal) in &self.keys {
+            if constant_time_eq(key.as_bytes(), presented.as_bytes()) {
+                matched = Some(principal);
+            }
+        }
+        matched
+            .cloned()
+            .ok_or_else(|| AuthError::InvalidCredentials("unknown API key".to_string()))
+    }
+}
+
+/// Extract the token from an `Authorization: Bearer <token>` header.
+pub fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    let value = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, token) = value.split_once(' ')?;
+    scheme
+        .eq_ignore_ascii_case("bearer")
+        .then(|| token.trim())
+        .filter(|token| !token.is_empty())
+}
+
+/// Compare secrets by their SHA-256 digests, so that neither where they differ
+/// nor how long they are shows in the time taken.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    let (a, b) = (digest(&SHA256, a), digest(&SHA256, b));
+    a.as_ref()
+        .iter()
+        .zip(b.as_ref())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+        == 0
+}
+
+/// Rejection carrying the 401 response for a request that failed authentication.
+#[derive(Debug)]
+pub(crate) struct Unauthorized {
+    challenge: String,
+    message: String,
+}
+
+impl Reject for Unauthorized {}
+
+/// Authenticate a connection, attaching the principal on success.
+pub(crate) async fn authenticate(
+    authenticator: &dyn Authenticator,
+    mut connection: ConnectionInfo,
+) -> Result<ConnectionInfo, Rejection> {
+    match authenticator.authenticate(&connection).await {
+        Ok(principal) => {
+            debug!("Authenticated request as {}", principal.id);
+            connection.principal = Some(principal);
+            Ok(connection)
+        }
+        Err(error) => {
+            debug!("Rejected unauthenticated request: {}", error);
+            Err(warp::reject::custom(Unauthorized {
+                challenge: authenticator.challenge(&error),
+                message: error.to_string(),
+            }))
+        }
+    }
+}
+
+/// Answer a request refused with [`AccessDenied`] with `403 Forbidden`.
+///
+/// Missing scopes are also reported in a `WWW-Authenticate` challenge
+/// (RFC 6750 section 3.1), so OAuth clients can request a broader token.
+pub(crate) fn forbidden(id: &Value, denied: &AccessDenied) -> warp::reply::Response {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {
+            "code": denied.code(),
+            "message": denied.to_string()
+        }
+    });
+    let mut response =
+        warp::reply::with_status(warp::reply::json(&body), StatusCode::FORBIDDEN).into_response();
+    if let AccessDenied::InsufficientScope { scope, .. } = denied {
+        let challenge =
+            format!(r#"Bearer realm="mcp", error="insufficient_scope", scope="{scope}""#);
+        if let Ok(value) = header::HeaderValue::from_str(&challenge) {
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, value);
+        }
+    }
+    response
+}
+
+/// Turn an authentication rejection into a `401` response; pass other rejections on.
+pub(crate) async fn handle_rejection(
+    rejection: Rejection,
+) -> Result<warp::reply::Response, Rejection> {
+    if let Some(unauthorized) = rejection.find::<Unauthorized>() {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": null,
+            "error": {
+                "code": -32001,
+                "message": unauthorized.message
+            }
+        });
+        let reply = warp::reply::with_status(warp::reply::json(&body), StatusCode::UNAUTHORIZED);
+        return Ok(warp::reply::with_header(
+            reply,
+            header::WWW_AUTHENTICATE,
+            unauthorized.challenge.as_str(),
+        )
+        .into_response());
+    }
+    Err(rejection)
+}
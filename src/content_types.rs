@@ -26,68 +26,382 @@
 //! ))
 //! ```
 
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+/// Conversation role used by content annotations and prompt messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// The human user (or the client acting on their behalf)
+    User,
+    /// The model
+    Assistant,
+}
+
+/// Optional hints attached to content telling the client how to use it
+///
+/// All fields are optional; empty annotations serialize to `{}`.
+///
+/// # Examples
+///
+/// ```rust
+/// use solidmcp::content_types::{Annotations, McpContent, Role};
+///
+/// let content = McpContent::text("Internal diagnostics").with_annotations(Annotations {
+///     audience: Some(vec![Role::Assistant]),
+///     priority: Some(0.2),
+///     last_modified: None,
+/// });
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Annotations {
+    /// Who the content is intended for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audience: Option<Vec<Role>>,
+    /// Importance from 0.0 (optional) to 1.0 (required)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<f64>,
+    /// ISO 8601 timestamp of the last modification
+    #[serde(
+        default,
+        rename = "lastModified",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub last_modified: Option<String>,
+}
+
+/// Contents of a resource embedded directly in a message
+///
+/// Text resources carry their body in `text`; binary resources carry it
+/// base64 encoded in `blob`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum EmbeddedResource {
+    /// Textual resource contents
+    Text {
+        /// Resource URI
+        uri: String,
+        /// Optional MIME type
+        #[serde(rename = "mimeType", default, skip_serializing_if = "Option::is_none")]
+        mime_type: Option<String>,
+        /// Resource text
+        text: String,
+    },
+    /// Binary resource contents
+    Blob {
+        /// Resource URI
+        uri: String,
+        /// Optional MIME type
+        #[serde(rename = "mimeType", default, skip_serializing_if = "Option::is_none")]
+        mime_type: Option<String>,
+        /// Base64 encoded resource data
+        blob: String,
+    },
+}
+
+impl EmbeddedResource {
+    /// The URI of the embedded resource
+    pub fn uri(&self) -> &str {
+        match self {
+            Self::Text { uri, .. } | Self::Blob { uri, .. } => uri,
+        }
+    }
+
+    /// The MIME type of the embedded resource, if known
+    pub fn mime_type(&self) -> Option<&str> {
+        match self {
+            Self::Text { mime_type, .. } | Self::Blob { mime_type, .. } => mime_type.as_deref(),
+        }
+    }
+}
+
 /// Type-safe representation of MCP content
 ///
 /// This enum ensures that tool responses contain the proper content structure
 /// expected by MCP clients. Each variant represents a different content type
-/// that can be included in an MCP response.
+/// that can be included in an MCP response, and serializes with the spec's
+/// camelCase field names.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "type")]
 pub enum McpContent {
     /// Plain text content
     #[serde(rename = "text")]
-    Text { text: String },
-    
+    Text {
+        text: String,
+        /// Optional content annotations
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        annotations: Option<Annotations>,
+    },
+
     /// Image content (base64 encoded)
     #[serde(rename = "image")]
-    Image { 
+    Image {
         /// Base64 encoded image data
         data: String,
         /// MIME type (e.g., "image/png", "image/jpeg")
-        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(rename = "mimeType", default, skip_serializing_if = "Option::is_none")]
         mime_type: Option<String>,
+        /// Optional content annotations
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        annotations: Option<Annotations>,
     },
-    
-    /// Resource reference
-    #[serde(rename = "resource")]
-    Resource { 
+
+    /// Audio content (base64 encoded)
+    #[serde(rename = "audio")]
+    Audio {
+        /// Base64 encoded audio data
+        data: String,
+        /// MIME type (e.g., "audio/wav", "audio/mpeg")
+        #[serde(rename = "mimeType", default, skip_serializing_if = "Option::is_none")]
+        mime_type: Option<String>,
+        /// Optional content annotations
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        annotations: Option<Annotations>,
+    },
+
+    /// Link to a resource the client can read separately
+    #[serde(rename = "resource_link")]
+    ResourceLink {
         /// Resource URI
         uri: String,
+        /// Resource name
+        name: String,
+        /// Optional description
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
         /// Optional MIME type
-        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(rename = "mimeType", default, skip_serializing_if = "Option::is_none")]
         mime_type: Option<String>,
-        /// Optional display text
-        #[serde(skip_serializing_if = "Option::is_none")]
-        text: Option<String>,
+        /// Optional size in bytes
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        size: Option<u64>,
+        /// Optional content annotations
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        annotations: Option<Annotations>,
+    },
+
+    /// Resource contents embedded in the response
+    #[serde(rename = "resource")]
+    Resource {
+        /// The embedded resource (text or blob)
+        resource: EmbeddedResource,
+        /// Optional content annotations
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        annotations: Option<Annotations>,
     },
 }
 
 impl McpContent {
     /// Create text content
     pub fn text(text: impl Into<String>) -> Self {
-        Self::Text { text: text.into() }
+        Self::Text {
+            text: text.into(),
+            annotations: None,
+        }
     }
-    
-    /// Create image content
+
+    /// Create image content from already base64 encoded data
     pub fn image(data: impl Into<String>, mime_type: Option<String>) -> Self {
-        Self::Image { 
-            data: data.into(), 
-            mime_type 
+        Self::Image {
+            data: data.into(),
+            mime_type,
+            annotations: None,
         }
     }
-    
-    /// Create resource reference
+
+    /// Create image content from raw bytes
+    ///
+    /// The bytes are base64 encoded and the MIME type is detected from the
+    /// file signature, falling back to `application/octet-stream`.
+    pub fn image_from_bytes(bytes: &[u8]) -> Self {
+        Self::image(BASE64.encode(bytes), Some(sniffed_or_octet_stream(bytes)))
+    }
+
+    /// Create audio content from already base64 encoded data
+    pub fn audio(data: impl Into<String>, mime_type: Option<String>) -> Self {
+        Self::Audio {
+            data: data.into(),
+            mime_type,
+            annotations: None,
+        }
+    }
+
+    /// Create audio content from raw bytes
+    ///
+    /// The bytes are base64 encoded and the MIME type is detected from the
+    /// file signature, falling back to `application/octet-stream`.
+    pub fn audio_from_bytes(bytes: &[u8]) -> Self {
+        Self::audio(BASE64.encode(bytes), Some(sniffed_or_octet_stream(bytes)))
+    }
+
+    /// Create a link to a resource
+    pub fn resource_link(uri: impl Into<String>, name: impl Into<String>) -> Self {
+        Self::ResourceLink {
+            uri: uri.into(),
+            name: name.into(),
+            description: None,
+            mime_type: None,
+            size: None,
+            annotations: None,
+        }
+    }
+
+    /// Create an embedded text resource
+    ///
+    /// A missing `text` embeds an empty body; use [`McpContent::resource_link`]
+    /// to reference a resource without embedding its contents.
     pub fn resource(uri: impl Into<String>, mime_type: Option<String>, text: Option<String>) -> Self {
-        Self::Resource { 
-            uri: uri.into(), 
-            mime_type, 
-            text 
+        Self::embedded(EmbeddedResource::Text {
+            uri: uri.into(),
+            mime_type,
+            text: text.unwrap_or_default(),
+        })
+    }
+
+    /// Create an embedded binary resource from raw bytes
+    pub fn resource_blob(uri: impl Into<String>, mime_type: Option<String>, bytes: &[u8]) -> Self {
+        Self::embedded(EmbeddedResource::Blob {
+            uri: uri.into(),
+            mime_type,
+            blob: BASE64.encode(bytes),
+        })
+    }
+
+    /// Embed arbitrary resource contents
+    pub fn embedded(resource: EmbeddedResource) -> Self {
+        Self::Resource {
+            resource,
+            annotations: None,
         }
     }
+
+    /// Attach annotations to this content
+    pub fn with_annotations(mut self, value: Annotations) -> Self {
+        match &mut self {
+            Self::Text { annotations, .. }
+            | Self::Image { annotations, .. }
+            | Self::Audio { annotations, .. }
+            | Self::ResourceLink { annotations, .. }
+            | Self::Resource { annotations, .. } => *annotations = Some(value),
+        }
+        self
+    }
+
+    /// The annotations attached to this content, if any
+    pub fn annotations(&self) -> Option<&Annotations> {
+        match self {
+            Self::Text { annotations, .. }
+            | Self::Image { annotations, .. }
+            | Self::Audio { annotations, .. }
+            | Self::ResourceLink { annotations, .. }
+            | Self::Resource { annotations, .. } => annotations.as_ref(),
+        }
+    }
+}
+
+impl From<String> for McpContent {
+    fn from(text: String) -> Self {
+        Self::text(text)
+    }
+}
+
+impl From<&str> for McpContent {
+    fn from(text: &str) -> Self {
+        Self::text(text)
+    }
+}
+
+impl From<EmbeddedResource> for McpContent {
+    fn from(resource: EmbeddedResource) -> Self {
+        Self::embedded(resource)
+    }
+}
+
+/// Detect the MIME type of image, audio or video data from its file signature
+///
+/// Recognizes PNG, JPEG, GIF, WebP, BMP, WAV, MP3, OGG, FLAC and ISO-BMFF
+/// files. The major brand of an ISO-BMFF file tells AVIF and HEIF images and
+/// M4A audio apart from MP4 video.
+/// Returns `None` when the format is not recognized.
+///
+/// # Examples
+///
+/// ```rust
+/// use solidmcp::content_types::sniff_mime_type;
+///
+/// assert_eq!(sniff_mime_type(b"\x89PNG\r\n\x1a\n...."), Some("image/png"));
+/// assert_eq!(sniff_mime_type(b"plain text"), None);
+/// ```
+pub fn sniff_mime_type(bytes: &[u8]) -> Option<&'static str> {
+    let riff_kind = |kind: &[u8]| bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == kind;
+
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if riff_kind(b"WEBP") {
+        Some("image/webp")
+    } else if is_bmp(bytes) {
+        Some("image/bmp")
+    } else if riff_kind(b"WAVE") {
+        Some("audio/wav")
+    } else if bytes.starts_with(b"ID3") || is_mpeg_audio_frame(bytes) {
+        Some("audio/mpeg")
+    } else if bytes.starts_with(b"OggS") {
+        Some("audio/ogg")
+    } else if bytes.starts_with(b"fLaC") {
+        Some("audio/flac")
+    } else if bytes.len() >= 12 && &bytes[4..8] == b"ftyp" {
+        Some(match &bytes[8..12] {
+            b"avif" | b"avis" => "image/avif",
+            b"heic" | b"heix" | b"heim" | b"heis" => "image/heic",
+            b"mif1" | b"msf1" => "image/heif",
+            b"M4A " | b"M4B " | b"M4P " => "audio/mp4",
+            _ => "video/mp4",
+        })
+    } else {
+        None
+    }
+}
+
+/// A BMP file header followed by the size of a known DIB header version.
+fn is_bmp(bytes: &[u8]) -> bool {
+    bytes.len() >= 18
+        && bytes.starts_with(b"BM")
+        && matches!(
+            u32::from_le_bytes([bytes[14], bytes[15], bytes[16], bytes[17]]),
+            12 | 16 | 40 | 52 | 56 | 64 | 108 | 124
+        )
+}
+
+/// A complete MPEG audio frame header: the sync word followed by a version,
+/// layer, bitrate, sample rate and emphasis that are not reserved.
+fn is_mpeg_audio_frame(bytes: &[u8]) -> bool {
+    let [0xFF, b1, b2, b3, ..] = *bytes else {
+        return false;
+    };
+    let sync = b1 & 0xE0 == 0xE0;
+    let version = (b1 >> 3) & 0b11;
+    let layer = (b1 >> 1) & 0b11;
+    let bitrate = b2 >> 4;
+    let sample_rate = (b2 >> 2) & 0b11;
+    let emphasis = b3 & 0b11;
+    sync && version != 0b01
+        && layer != 0b00
+        && bitrate != 0b1111
+        && sample_rate != 0b11
+        && emphasis != 0b10
+}
+
+fn sniffed_or_octet_stream(bytes: &[u8]) -> String {
+    sniff_mime_type(bytes)
+        .unwrap_or("application/octet-stream")
+        .to_string()
 }
 
 /// Complete MCP-compliant tool response
@@ -264,7 +578,115 @@ mod tests {
         
         assert_eq!(json["type"], "image");
         assert_eq!(json["data"], "base64data");
-        assert_eq!(json["mime_type"], "image/png");
+        assert_eq!(json["mimeType"], "image/png");
+        assert!(json.get("mime_type").is_none());
+    }
+
+    #[test]
+    fn test_mcp_content_audio_from_bytes() {
+        let wav = b"RIFF\x24\x00\x00\x00WAVEfmt ";
+        let content = McpContent::audio_from_bytes(wav);
+        let json = serde_json::to_value(&content).unwrap();
+
+        assert_eq!(json["type"], "audio");
+        assert_eq!(json["mimeType"], "audio/wav");
+        assert_eq!(json["data"], BASE64.encode(wav));
+    }
+
+    #[test]
+    fn test_image_from_bytes_sniffs_mime_type() {
+        let png = McpContent::image_from_bytes(b"\x89PNG\r\n\x1a\n\x00\x00");
+        assert!(matches!(png, McpContent::Image { ref mime_type, .. } if mime_type.as_deref() == Some("image/png")));
+
+        let unknown = McpContent::image_from_bytes(b"not an image");
+        assert!(matches!(unknown, McpContent::Image { ref mime_type, .. } if mime_type.as_deref() == Some("application/octet-stream")));
+    }
+
+    #[test]
+    fn test_sniff_mime_type() {
+        assert_eq!(sniff_mime_type(&[0xFF, 0xD8, 0xFF, 0xE0]), Some("image/jpeg"));
+        assert_eq!(sniff_mime_type(b"GIF89a..."), Some("image/gif"));
+        assert_eq!(sniff_mime_type(b"RIFF\x00\x00\x00\x00WEBPVP8 "), Some("image/webp"));
+        assert_eq!(sniff_mime_type(b"ID3\x04\x00"), Some("audio/mpeg"));
+        // MPEG-1 Layer III, 128 kbit/s, 44.1 kHz
+        assert_eq!(sniff_mime_type(&[0xFF, 0xFB, 0x90, 0x64]), Some("audio/mpeg"));
+        // Frame sync followed by a reserved layer, bitrate or sample rate
+        assert_eq!(sniff_mime_type(&[0xFF, 0xF9, 0x90, 0x64]), None);
+        assert_eq!(sniff_mime_type(&[0xFF, 0xFB, 0xF0, 0x64]), None);
+        assert_eq!(sniff_mime_type(&[0xFF, 0xFB, 0x9C, 0x64]), None);
+        assert_eq!(sniff_mime_type(&[0xFF, 0xE0]), None);
+        assert_eq!(sniff_mime_type(b"OggS\x00\x02"), Some("audio/ogg"));
+        assert_eq!(sniff_mime_type(b"fLaC\x00\x00"), Some("audio/flac"));
+
+        let bmp = |dib_size: u32| {
+            let mut header = b"BM\x46\x00\x00\x00\x00\x00\x00\x00\x36\x00\x00\x00".to_vec();
+            header.extend(dib_size.to_le_bytes());
+            header
+        };
+        assert_eq!(sniff_mime_type(&bmp(40)), Some("image/bmp"));
+        assert_eq!(sniff_mime_type(&bmp(124)), Some("image/bmp"));
+        assert_eq!(sniff_mime_type(&bmp(7)), None);
+        assert_eq!(sniff_mime_type(b"BMW owners manual"), None);
+
+        let ftyp = |brand: &[u8; 4]| [b"\x00\x00\x00\x18ftyp".as_slice(), brand].concat();
+        assert_eq!(sniff_mime_type(&ftyp(b"M4A ")), Some("audio/mp4"));
+        assert_eq!(sniff_mime_type(&ftyp(b"isom")), Some("video/mp4"));
+        assert_eq!(sniff_mime_type(&ftyp(b"mp42")), Some("video/mp4"));
+        assert_eq!(sniff_mime_type(&ftyp(b"avif")), Some("image/avif"));
+        assert_eq!(sniff_mime_type(&ftyp(b"heic")), Some("image/heic"));
+        assert_eq!(sniff_mime_type(&ftyp(b"mif1")), Some("image/heif"));
+        assert_eq!(sniff_mime_type(b""), None);
+        assert_eq!(sniff_mime_type(b"{\"json\": true}"), None);
+    }
+
+    #[test]
+    fn test_mcp_content_resource_link() {
+        let content = McpContent::resource_link("file:///report.pdf", "report.pdf");
+        let json = serde_json::to_value(&content).unwrap();
+
+        assert_eq!(json, json!({
+            "type": "resource_link",
+            "uri": "file:///report.pdf",
+            "name": "report.pdf"
+        }));
+    }
+
+    #[test]
+    fn test_mcp_content_embedded_resources() {
+        let text = McpContent::resource("file:///notes.md", Some("text/markdown".to_string()), Some("# Notes".to_string()));
+        let json = serde_json::to_value(&text).unwrap();
+        assert_eq!(json["type"], "resource");
+        assert_eq!(json["resource"], json!({
+            "uri": "file:///notes.md",
+            "mimeType": "text/markdown",
+            "text": "# Notes"
+        }));
+
+        let blob = McpContent::resource_blob("file:///data.bin", None, &[0, 1, 2, 3]);
+        let json = serde_json::to_value(&blob).unwrap();
+        assert_eq!(json["resource"]["blob"], "AAECAw==");
+        assert!(json["resource"].get("text").is_none());
+
+        // Round-trips distinguish text from blob contents
+        let parsed: McpContent = serde_json::from_value(json).unwrap();
+        assert!(matches!(parsed, McpContent::Resource { resource: EmbeddedResource::Blob { .. }, .. }));
+    }
+
+    #[test]
+    fn test_mcp_content_annotations() {
+        let content = McpContent::text("For the model only").with_annotations(Annotations {
+            audience: Some(vec![Role::Assistant]),
+            priority: Some(0.5),
+            last_modified: Some("2025-01-12T15:00:58Z".to_string()),
+        });
+        let json = serde_json::to_value(&content).unwrap();
+
+        assert_eq!(json["annotations"], json!({
+            "audience": ["assistant"],
+            "priority": 0.5,
+            "lastModified": "2025-01-12T15:00:58Z"
+        }));
+        assert!(serde_json::to_value(McpContent::text("plain")).unwrap().get("annotations").is_none());
     }
 
     #[test]
@@ -292,7 +714,7 @@ mod tests {
     fn test_to_mcp_response_string() {
         let response = "Hello".to_mcp_response();
         assert_eq!(response.content.len(), 1);
-        assert!(matches!(response.content[0], McpContent::Text { ref text } if text == "Hello"));
+        assert!(matches!(response.content[0], McpContent::Text { ref text, .. } if text == "Hello"));
     }
 
     #[test]
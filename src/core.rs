@@ -3,14 +3,21 @@
 //! Core server struct and basic functionality for the Model Context Protocol server.
 
 use {
+    super::admin,
     super::handlers::McpHandlers,
+    super::health::{self, Readiness},
     super::http::HttpMcpHandler,
     super::logging::McpDebugLogger,
+    super::metrics::{self, MetricsSnapshot},
     super::protocol::McpProtocol,
+    super::server_config::ServerConfig,
+    super::server_handle::{self, ServerHandle},
     super::shared::McpProtocolEngine,
+    super::tls::{self, ReloadableTls},
     anyhow::{Context, Result},
-    std::sync::Arc,
-    tracing::debug,
+    std::{net::SocketAddr, sync::Arc},
+    tokio::sync::oneshot,
+    tracing::{debug, error},
     warp::Filter,
 };
 
@@ -149,6 +156,73 @@ impl McpServer {
     /// ```
     pub async fn start(&mut self, port: u16) -> Result<()> {
         debug!("🚀 Starting MCP Server on port {}", port);
+        self.bind(([127, 0, 0, 1], port)).await?.wait().await
+    }
+
+    /// Start serving on `addr` in the background.
+    ///
+    /// Unlike [`start`](Self::start), this returns as soon as the listener is
+    /// bound, with a handle that reports the bound address and shuts the
+    /// server down gracefully.
+    ///
+    /// # Parameters
+    ///
+    /// - `addr`: The address to listen on; port 0 lets the OS pick a free port
+    ///
+    /// # Returns
+    ///
+    /// A `ServerHandle` to the running server
+    ///
+    /// # Errors
+    ///
+    /// - Address binding errors if the address is in use or unavailable
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let handle = server.bind(([127, 0, 0, 1], 0)).await?;
+    /// let url = format!("http://{}/mcp", handle.local_addr());
+    /// // ... talk to the server ...
+    /// handle.shutdown().await?;
+    /// ```
+    pub async fn bind(&self, addr: impl Into<SocketAddr>) -> Result<ServerHandle> {
+        self.serve(ServerConfig::new(addr)).await
+    }
+
+    /// Start serving in the background as `config` says.
+    ///
+    /// Like [`bind`](Self::bind), but also serves over TLS if configured. The
+    /// certificate files are read before binding, so bad certificates fail
+    /// here rather than at the first connection.
+    ///
+    /// # Parameters
+    ///
+    /// - `config`: Address to listen on and TLS settings
+    ///
+    /// # Returns
+    ///
+    /// A `ServerHandle` to the running server
+    ///
+    /// # Errors
+    ///
+    /// - Certificate or key files that are missing or invalid
+    /// - Address binding errors if the address is in use or unavailable
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let config = ServerConfig::new(([0, 0, 0, 0], 8443))
+    ///     .with_tls(TlsConfig::new("tls.crt", "tls.key"));
+    /// let handle = server.serve(config).await?;
+    /// ```
+    pub async fn serve(&self, config: ServerConfig) -> Result<ServerHandle> {
+        let addr = config.addr();
+        let tls = config
+            .tls()
+            .cloned()
+            .map(ReloadableTls::load)
+            .transpose()?
+            .map(Arc::new);
 
         // Create HTTP handler
         let http_handler = HttpMcpHandler::new(self.protocol_engine.clone());
@@ -158,34 +232,105 @@ impl McpServer {
 
         let http_route = http_handler.route();
 
-        // Add health check endpoint
-        let health_route = warp::path!("health")
-            .and(warp::get())
-            .map(|| warp::reply::with_status("OK", warp::http::StatusCode::OK));
+        // Health endpoints: /health, /health/live and /health/ready
+        let health_route = health::routes(self.protocol_engine.clone());
 
-        // Combine routes - warp will handle content negotiation
-        let routes = ws_route.or(http_route).or(health_route);
+        let metrics_route = metrics::route(
+            self.protocol_engine.metrics().clone(),
+            self.protocol_engine.metrics_path(),
+        );
 
-        let addr = format!("127.0.0.1:{port}")
-            .parse::<std::net::SocketAddr>()
-            .context("Invalid address")?;
+        // Admin API, if the handler configured one
+        let admin_route = admin::routes(self.protocol_engine.clone());
 
-        // Try to bind the port first
+        // Combine routes - warp will handle content negotiation
+        let routes = ws_route
+            .or(http_route)
+            .or(health_route)
+            .or(metrics_route)
+            .or(admin_route);
+
+        // Try to bind the address first
         let listener = tokio::net::TcpListener::bind(addr)
             .await
             .map_err(|e| anyhow::anyhow!("Could not bind to {}: {}", addr, e))?;
+        let addr = listener
+            .local_addr()
+            .context("Could not read the bound address")?;
 
-        println!("🌐 MCP Server listening on ws://{addr}/mcp and http://{addr}/mcp");
+        let (ws, http) = match tls {
+            Some(_) => ("wss", "https"),
+            None => ("ws", "http"),
+        };
+        println!("🌐 MCP Server listening on {ws}://{addr}/mcp and {http}://{addr}/mcp");
         println!("📡 Available endpoints:");
         println!("  WS  /mcp (WebSocket upgrade)");
         println!("  POST /mcp (HTTP JSON-RPC)");
+        println!("  GET  /health/live, /health/ready (health checks)");
+        println!("  GET  {} (Prometheus metrics)", self.protocol_engine.metrics_path());
+        if let Some(admin) = self.protocol_engine.admin_api() {
+            println!("  GET/DELETE {}/sessions, {}/requests (admin API)", admin.path(), admin.path());
+        }
+
+        // Report ready once the handler has warmed up
+        let engine = self.protocol_engine.clone();
+        tokio::spawn(async move {
+            match engine.warm_up().await {
+                Ok(()) => debug!("Server warmed up and ready"),
+                Err(e) => error!("Warm-up failed, server stays unready: {}", e),
+            }
+        });
 
         use tokio_stream::wrappers::TcpListenerStream;
-        warp::serve(routes)
-            .run_incoming(TcpListenerStream::new(listener))
-            .await;
+        let (stop, stopped) = oneshot::channel::<()>();
+        let server = match &tls {
+            Some(tls) => tokio::spawn(tls::serve(listener, tls.clone(), routes, stopped)),
+            None => tokio::spawn(warp::serve(routes).serve_incoming_with_graceful_shutdown(
+                TcpListenerStream::new(listener),
+                server_handle::stop_requested(stopped),
+            )),
+        };
 
-        Ok(())
+        Ok(ServerHandle::new(
+            addr,
+            self.protocol_engine.clone(),
+            tls,
+            stop,
+            server,
+        ))
+    }
+
+    /// Get the server's current metrics.
+    ///
+    /// These are the numbers served in the Prometheus format at the metrics
+    /// path: request counts, errors and latencies per method and per tool,
+    /// error counts by code, active sessions by transport, WebSocket
+    /// connections, notification queue depths and payload sizes.
+    ///
+    /// # Returns
+    ///
+    /// A point-in-time `MetricsSnapshot`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let snapshot = server.metrics();
+    /// println!("{} tool calls", snapshot.methods["tools/call"].requests);
+    /// ```
+    pub fn metrics(&self) -> MetricsSnapshot {
+        self.protocol_engine.metrics().snapshot()
+    }
+
+    /// Get the handle to the server phase, which decides readiness.
+    ///
+    /// `/health/ready` fails until the warm-up finished and once the phase is
+    /// set to `ServerPhase::ShuttingDown`, e.g. on receiving `SIGTERM`.
+    ///
+    /// # Returns
+    ///
+    /// The server's `Readiness`
+    pub fn readiness(&self) -> Readiness {
+        self.protocol_engine.readiness().clone()
     }
 
     /// Get a new handler instance for processing messages.
@@ -7,11 +7,24 @@
 mod provider_methods;
 mod tool_methods;
 
+use crate::admin::AdminApi;
+use crate::audit::AuditLog;
 use crate::core::McpServer;
+use crate::auth::Authenticator;
+use crate::handler::{McpContext, ToolDefinition};
+use crate::health::Probe;
+use crate::middleware::Middleware;
+use crate::oauth::ProtectedResourceMetadata;
+use crate::session_store::{SessionEndReason, SessionStore};
 use anyhow::Result;
-use std::sync::Arc;
+use std::{future::Future, sync::Arc};
 
-use super::handler::FrameworkHandler;
+use super::{
+    concurrency::{ConcurrencyLimit, ToolConcurrency},
+    handler::FrameworkHandler,
+    registry::RegistryHandle,
+    visibility::ToolVisibility,
+};
 
 
 /// Convenience builder for creating MCP servers with minimal boilerplate.
@@ -114,6 +127,466 @@ impl<C: Send + Sync + 'static> McpServerBuilder<C> {
         }
     }
 
+    /// Get a handle for adding and removing tools, resources and prompts later.
+    ///
+    /// The handle shares the registry of the server being built, so it keeps
+    /// working after `build()` and while the server runs. Each change is
+    /// announced to connected sessions with a `list_changed` notification.
+    ///
+    /// # Returns
+    /// A cloneable `RegistryHandle` for this server
+    ///
+    /// # Examples
+    /// ```rust
+    /// let builder = McpServerBuilder::new(context, "plugin-host", "1.0.0");
+    /// let registry = builder.registry_handle();
+    /// let server = builder.build().await?;
+    ///
+    /// plugin_loader.on_unload(move |plugin| {
+    ///     registry.remove_tool(&plugin.tool_name);
+    /// });
+    /// ```
+    pub fn registry_handle(&self) -> RegistryHandle<C> {
+        self.handler.registry_handle()
+    }
+
+    /// Decide which tools each session can list and call.
+    ///
+    /// The filter is consulted for every `tools/list` and `tools/call` request;
+    /// a tool it rejects is hidden and uncallable for that request's session.
+    /// Overrides made through `tool_visibility()` take precedence.
+    ///
+    /// # Parameters
+    /// - `filter`: Predicate receiving the tool definition and the request context
+    ///
+    /// # Returns
+    /// The builder (for method chaining)
+    ///
+    /// # Examples
+    /// ```rust
+    /// let server = McpServerBuilder::new(context, "tiered-server", "1.0.0")
+    ///     .with_tool_filter(|tool, ctx| {
+    ///         let trusted = ctx
+    ///             .connection
+    ///             .as_ref()
+    ///             .is_some_and(|conn| conn.headers.contains_key("x-internal"));
+    ///         trusted || !tool.name.starts_with("internal_")
+    ///     })
+    ///     .build()
+    ///     .await?;
+    /// ```
+    pub fn with_tool_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&ToolDefinition, &McpContext) -> bool + Send + Sync + 'static,
+    {
+        self.handler.set_tool_filter(filter);
+        self
+    }
+
+    /// Get a handle for showing or hiding tools in individual sessions.
+    ///
+    /// Each change sends `notifications/tools/list_changed` to the affected
+    /// session only.
+    ///
+    /// # Returns
+    /// A cloneable `ToolVisibility` for this server
+    pub fn tool_visibility(&self) -> ToolVisibility {
+        self.handler.tool_visibility()
+    }
+
+    /// Limit how many calls of a tool run at once.
+    ///
+    /// Calls over the limit wait for a slot or fail immediately with JSON-RPC
+    /// error -32004, depending on the limit's policy. Waiting calls send
+    /// `notifications/progress` every second if the request carried a
+    /// `progressToken`.
+    ///
+    /// # Parameters
+    /// - `tool`: Name of the tool
+    /// - `limit`: Maximum parallel calls and what happens to calls over it
+    ///
+    /// # Returns
+    /// The builder (for method chaining)
+    ///
+    /// # Errors
+    /// `build` fails if no tool named `tool` was registered by then
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// let server = McpServerBuilder::new(context, "device-server", "1.0.0")
+    ///     .with_tool("send_command", "Send a command over the serial bridge", send_command)
+    ///     .with_tool_concurrency(
+    ///         "send_command",
+    ///         ConcurrencyLimit::exclusive().wait_up_to(Duration::from_secs(10)),
+    ///     )
+    ///     .with_tool("render", "Render a report", render)
+    ///     .with_tool_concurrency("render", ConcurrencyLimit::new(4).reject_when_full())
+    ///     .build()
+    ///     .await?;
+    /// ```
+    pub fn with_tool_concurrency(mut self, tool: &str, limit: ConcurrencyLimit) -> Self {
+        self.handler.set_tool_concurrency(tool, limit);
+        self
+    }
+
+    /// Get a handle to the per-tool concurrency limits, e.g. to monitor queue depths.
+    ///
+    /// # Returns
+    /// A cloneable `ToolConcurrency` for this server
+    pub fn tool_concurrency(&self) -> ToolConcurrency {
+        self.handler.tool_concurrency()
+    }
+
+    /// Wrap every request in a middleware layer.
+    ///
+    /// Middleware sees the method, params and context of each message and
+    /// applies uniformly to tools, resources, prompts and any other method.
+    /// Layers run in the order they are added, the first being the outermost.
+    ///
+    /// # Parameters
+    /// - `middleware`: A type implementing the `Middleware` trait
+    ///
+    /// # Returns
+    /// The builder (for method chaining)
+    ///
+    /// # Examples
+    /// ```rust
+    /// struct Timing;
+    ///
+    /// #[async_trait]
+    /// impl Middleware for Timing {
+    ///     async fn handle(&self, request: McpRequest, next: Next<'_>) -> Result<Value> {
+    ///         let method = request.method.clone();
+    ///         let started = Instant::now();
+    ///         let response = next.run(request).await;
+    ///         tracing::info!("{} took {:?}", method, started.elapsed());
+    ///         response
+    ///     }
+    /// }
+    ///
+    /// let server = McpServerBuilder::new(context, "timed-server", "1.0.0")
+    ///     .with_middleware(Timing)
+    ///     .build()
+    ///     .await?;
+    /// ```
+    pub fn with_middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.handler.add_middleware(middleware);
+        self
+    }
+
+    /// Require clients to authenticate.
+    ///
+    /// Every HTTP request to `/mcp` and every WebSocket upgrade is passed to the
+    /// authenticator. Rejected requests receive `401 Unauthorized` with a
+    /// `WWW-Authenticate` header; accepted ones carry the authenticated principal
+    /// in `McpContext::principal` (and `NotificationCtx::principal`).
+    ///
+    /// # Parameters
+    /// - `authenticator`: `ApiKeyAuthenticator` or any type implementing `Authenticator`
+    ///
+    /// # Returns
+    /// The builder (for method chaining)
+    ///
+    /// # Examples
+    /// ```rust
+    /// let server = McpServerBuilder::new(context, "private-server", "1.0.0")
+    ///     .with_authenticator(
+    ///         ApiKeyAuthenticator::new()
+    ///             .with_key(std::env::var("MCP_API_KEY")?, Principal::new("ci").with_scopes(["deploy"])),
+    ///     )
+    ///     .with_tool("deploy", "Deploy the app", |input: DeployInput, ctx, notif| async move {
+    ///         let allowed = notif.principal().is_some_and(|p| p.has_scope("deploy"));
+    ///         anyhow::ensure!(allowed, "not allowed to deploy");
+    ///         ctx.deploy(input).await
+    ///     })
+    ///     .build()
+    ///     .await?;
+    /// ```
+    pub fn with_authenticator(mut self, authenticator: impl Authenticator + 'static) -> Self {
+        self.handler.set_authenticator(authenticator);
+        self
+    }
+
+    /// Act as an OAuth 2.1 protected resource.
+    ///
+    /// Publishes `metadata` at `/.well-known/oauth-protected-resource` so
+    /// clients can discover the authorization server, and authenticates requests
+    /// with `authenticator`, usually a `JwtAuthenticator` validating that
+    /// server's access tokens.
+    ///
+    /// # Parameters
+    /// - `metadata`: The resource URI, authorization servers and supported scopes
+    /// - `authenticator`: Validates the access tokens clients present
+    ///
+    /// # Returns
+    /// The builder (for method chaining)
+    ///
+    /// # Examples
+    /// ```rust
+    /// let jwks = Jwks::from_url("https://auth.example.com/.well-known/jwks.json").await?;
+    /// let server = McpServerBuilder::new(context, "files-server", "1.0.0")
+    ///     .with_oauth(
+    ///         ProtectedResourceMetadata::new("https://files.example.com/mcp")
+    ///             .with_authorization_server("https://auth.example.com")
+    ///             .with_scopes_supported(["files:read", "files:write"]),
+    ///         JwtAuthenticator::new(jwks, "https://auth.example.com", "https://files.example.com/mcp")
+    ///             .with_resource_metadata("https://files.example.com/.well-known/oauth-protected-resource"),
+    ///     )
+    ///     .with_tool_scopes("delete_file", ["files:write"])
+    ///     .build()
+    ///     .await?;
+    /// ```
+    pub fn with_oauth(
+        mut self,
+        metadata: ProtectedResourceMetadata,
+        authenticator: impl Authenticator + 'static,
+    ) -> Self {
+        self.handler.set_protected_resource_metadata(metadata);
+        self.handler.set_authenticator(authenticator);
+        self
+    }
+
+    /// Require the caller of a tool to hold every one of `scopes`.
+    ///
+    /// Scopes are checked against the principal established by the
+    /// authenticator. Calls lacking a scope are refused before the tool runs:
+    /// over HTTP with `403 Forbidden` and a
+    /// `WWW-Authenticate: Bearer error="insufficient_scope"` challenge, over
+    /// WebSocket with a JSON-RPC error (code -32003).
+    ///
+    /// # Parameters
+    /// - `tool`: Name of the tool
+    /// - `scopes`: Scopes the caller must have been granted
+    ///
+    /// # Returns
+    /// The builder (for method chaining)
+    ///
+    /// # Errors
+    /// `build` fails if no tool named `tool` was registered by then, or if a
+    /// scope is not a valid OAuth scope token
+    pub fn with_tool_scopes<I, S>(mut self, tool: &str, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.handler.require_tool_scopes(tool, scopes);
+        self
+    }
+
+    /// Keep the protocol state of each session in a custom store.
+    ///
+    /// By default sessions live in an `InMemorySessionStore` that expires them
+    /// after an hour of inactivity and holds at most 10,000 of them. Register
+    /// eviction hooks on the store before passing it in.
+    ///
+    /// # Parameters
+    /// - `store`: `InMemorySessionStore` or any type implementing `SessionStore`
+    ///
+    /// # Returns
+    /// The builder (for method chaining)
+    ///
+    /// # Examples
+    /// ```rust
+    /// let store = InMemorySessionStore::new()
+    ///     .with_idle_ttl(Duration::from_secs(15 * 60))
+    ///     .with_max_sessions(1_000);
+    /// store.on_evict(Arc::new(|session_id, reason| {
+    ///     println!("session {session_id} ended: {reason:?}");
+    /// }));
+    ///
+    /// let server = McpServerBuilder::new(MyContext::new(), "my-server", "1.0.0")
+    ///     .with_session_store(store)
+    ///     .build()
+    ///     .await?;
+    /// ```
+    pub fn with_session_store(mut self, store: impl SessionStore + 'static) -> Self {
+        self.handler.set_session_store(store);
+        self
+    }
+
+    /// Run a hook whenever a session starts.
+    ///
+    /// A session starts when the client's `initialize` succeeds, on every
+    /// transport. Returning an error fails the `initialize` request, e.g. when
+    /// a per-client resource cannot be allocated. Hooks run in the order they
+    /// are added.
+    ///
+    /// # Parameters
+    /// - `hook`: Async function receiving the application context and the request's `McpContext`
+    ///
+    /// # Returns
+    /// The builder (for method chaining)
+    ///
+    /// # Examples
+    /// ```rust
+    /// let server = McpServerBuilder::new(AppContext::new(), "my-server", "1.0.0")
+    ///     .on_session_start(|ctx: Arc<AppContext>, request: McpContext| async move {
+    ///         let session_id = request.session_id.unwrap_or_default();
+    ///         ctx.scratch_dirs.create(&session_id).await
+    ///     })
+    ///     .on_session_end(|ctx: Arc<AppContext>, session_id: String, reason| async move {
+    ///         tracing::info!("session {session_id} ended: {reason:?}");
+    ///         ctx.scratch_dirs.remove(&session_id).await
+    ///     })
+    ///     .build()
+    ///     .await?;
+    /// ```
+    pub fn on_session_start<F, Fut>(mut self, hook: F) -> Self
+    where
+        F: Fn(Arc<C>, McpContext) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.handler.add_session_start_hook(hook);
+        self
+    }
+
+    /// Run a hook whenever a session ends.
+    ///
+    /// The hook receives the reason: `Closed` when a WebSocket disconnects,
+    /// `Deleted` when an HTTP client sends `DELETE /mcp`, `Expired` or
+    /// `Evicted` when the session store drops an idle session, and
+    /// `Reinitialized` when the client sends `initialize` again (the start hooks
+    /// run right after). Errors are logged.
+    ///
+    /// # Parameters
+    /// - `hook`: Async function receiving the application context, the session ID and the reason
+    ///
+    /// # Returns
+    /// The builder (for method chaining)
+    pub fn on_session_end<F, Fut>(mut self, hook: F) -> Self
+    where
+        F: Fn(Arc<C>, String, SessionEndReason) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.handler.add_session_end_hook(hook);
+        self
+    }
+
+    /// Add a check reported by the `/health/live` or `/health/ready` endpoint.
+    ///
+    /// An error, or taking longer than `DEFAULT_CHECK_TIMEOUT`, fails the
+    /// check and makes its endpoint answer `503`.
+    ///
+    /// # Parameters
+    /// - `name`: Name the check is reported under
+    /// - `probe`: `Probe::Liveness` or `Probe::Readiness`; readiness runs the liveness checks too
+    /// - `check`: Async function receiving the application context
+    ///
+    /// # Returns
+    /// The builder (for method chaining)
+    ///
+    /// # Examples
+    /// ```rust
+    /// let server = McpServerBuilder::new(AppContext::new(), "my-server", "1.0.0")
+    ///     .with_health_check("database", Probe::Readiness, |ctx: Arc<AppContext>| async move {
+    ///         ctx.pool.ping().await
+    ///     })
+    ///     .build()
+    ///     .await?;
+    /// ```
+    pub fn with_health_check<F, Fut>(mut self, name: &str, probe: Probe, check: F) -> Self
+    where
+        F: Fn(Arc<C>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.handler.add_health_check(name, probe, check);
+        self
+    }
+
+    /// Run `hook` once the server listens, before it reports ready.
+    ///
+    /// `/health/ready` answers `503` until every warm-up hook has returned,
+    /// and keeps doing so if one fails.
+    ///
+    /// # Parameters
+    /// - `hook`: Async function receiving the application context
+    ///
+    /// # Returns
+    /// The builder (for method chaining)
+    pub fn with_warm_up<F, Fut>(mut self, hook: F) -> Self
+    where
+        F: Fn(Arc<C>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.handler.add_warm_up(hook);
+        self
+    }
+
+    /// Serve the Prometheus metrics endpoint at a custom path.
+    ///
+    /// The server always exposes request counts, latencies and error counts per
+    /// method and per tool, active sessions, WebSocket connections, notification
+    /// queue depths and payload sizes; the default path is `/metrics`.
+    ///
+    /// # Parameters
+    /// - `path`: The HTTP path of the metrics endpoint
+    ///
+    /// # Returns
+    /// The builder (for method chaining)
+    ///
+    /// # Examples
+    /// ```rust
+    /// let server = McpServerBuilder::new(MyContext::new(), "my-server", "1.0.0")
+    ///     .with_metrics_path("/internal/metrics")
+    ///     .build()
+    ///     .await?;
+    /// ```
+    pub fn with_metrics_path(mut self, path: impl Into<String>) -> Self {
+        self.handler.set_metrics_path(path);
+        self
+    }
+
+    /// Record every tool call in an audit log.
+    ///
+    /// Each record holds the session, principal, client info, redacted
+    /// arguments, start time, duration and whether the call failed.
+    ///
+    /// # Parameters
+    /// - `audit`: The sink records go to and the rules redacting their arguments
+    ///
+    /// # Returns
+    /// The builder (for method chaining)
+    ///
+    /// # Examples
+    /// ```rust
+    /// let audit = AuditLog::new(JsonLinesAuditSink::open("audit.jsonl")?)
+    ///     .redact_field("password");
+    ///
+    /// let server = McpServerBuilder::new(MyContext::new(), "my-server", "1.0.0")
+    ///     .with_audit_log(audit)
+    ///     .build()
+    ///     .await?;
+    /// ```
+    pub fn with_audit_log(mut self, audit: AuditLog) -> Self {
+        self.handler.set_audit_log(audit);
+        self
+    }
+
+    /// Serve the authenticated admin API.
+    ///
+    /// Operators use it to list sessions and in-flight requests, terminate
+    /// sessions and cancel requests; see [`AdminApi`] for the endpoints.
+    ///
+    /// # Parameters
+    /// - `admin`: Who may use the API and where it is served
+    ///
+    /// # Returns
+    /// The builder (for method chaining)
+    ///
+    /// # Examples
+    /// ```rust
+    /// let admin = AdminApi::new(ApiKeyAuthenticator::new().with_key("sk-ops", Principal::new("oncall")));
+    ///
+    /// let server = McpServerBuilder::new(MyContext::new(), "my-server", "1.0.0")
+    ///     .with_admin_api(admin)
+    ///     .build()
+    ///     .await?;
+    /// ```
+    pub fn with_admin_api(mut self, admin: AdminApi) -> Self {
+        self.handler.set_admin_api(admin);
+        self
+    }
+
     /// Build the MCP server and prepare it for startup.
     ///
     /// This method finalizes the server configuration and creates an `McpServer`
@@ -125,6 +598,10 @@ impl<C: Send + Sync + 'static> McpServerBuilder<C> {
     ///
     /// # Errors
     /// - Configuration validation errors
+    /// - Two providers claiming the same resource route or prompt name
+    /// - A concurrency limit naming a tool that was not registered
+    /// - Scopes required for a tool that was not registered, or scopes that
+    ///   are not valid OAuth scope tokens
     /// - Schema generation errors for registered tools
     /// - Internal setup errors
     ///
@@ -145,6 +622,9 @@ impl<C: Send + Sync + 'static> McpServerBuilder<C> {
     /// }
     /// ```
     pub async fn build(self) -> Result<McpServer> {
+        self.handler.registry().validate_routes()?;
+        self.handler.validate_tool_concurrency()?;
+        self.handler.registry().validate_tool_scopes()?;
         McpServer::with_handler(Arc::new(self.handler)).await
     }
 }
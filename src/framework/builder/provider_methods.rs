@@ -1,10 +1,17 @@
 //! Provider registration methods for McpServerBuilder.
 //!
-//! This module contains methods for registering resource and prompt providers.
+//! This module contains methods for registering resource and prompt providers,
+//! and single resources and typed prompts backed by async closures.
 
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use std::{future::Future, sync::Arc};
+
+use crate::handler::ResourceData;
 use crate::framework::{
     builder::McpServerBuilder,
-    providers::{PromptProvider, ResourceProvider},
+    providers::{PromptHandler, PromptProvider, ResourceProvider},
 };
 
 impl<C: Send + Sync + 'static> McpServerBuilder<C> {
@@ -42,6 +49,7 @@ impl<C: Send + Sync + 'static> McpServerBuilder<C> {
     ///                     mime_type: mime_guess::from_path(&entry.path())
     ///                         .first()
     ///                         .map(|m| m.to_string()),
+    ///                         ..Default::default()
     ///                 });
     ///             }
     ///         }
@@ -59,7 +67,7 @@ impl<C: Send + Sync + 'static> McpServerBuilder<C> {
     ///                 mime_type: mime_guess::from_path(&full_path)
     ///                     .first()
     ///                     .map(|m| m.to_string()),
-    ///                 content,
+    ///                 content: content.into(),
     ///             })
     ///         } else {
     ///             Err(anyhow::anyhow!("Invalid file URI: {}", uri))
@@ -79,6 +87,71 @@ impl<C: Send + Sync + 'static> McpServerBuilder<C> {
         self
     }
 
+    /// Expose a single resource whose content is computed by an async closure.
+    ///
+    /// This avoids writing a full `ResourceProvider` for one document, such as a
+    /// status page or a generated report. The handler runs on every read and may
+    /// return anything convertible into `ResourceData` (`String` for text,
+    /// `Vec<u8>` for binary content). The resource is listed, read and announced
+    /// through `resources_changed` notifications exactly like provider resources.
+    ///
+    /// # Parameters
+    /// - `uri`: URI identifying the resource
+    /// - `name`: Human-readable resource name
+    /// - `mime_type`: MIME type of the content
+    /// - `handler`: Async function receiving the context and returning the content
+    ///
+    /// # Returns
+    /// The builder (for method chaining)
+    ///
+    /// # Examples
+    /// ```rust
+    /// let server = McpServerBuilder::new(context, "status-server", "1.0.0")
+    ///     .with_resource("status://server", "Server status", "application/json", |ctx| async move {
+    ///         Ok(serde_json::to_string(&ctx.status())?)
+    ///     });
+    /// ```
+    pub fn with_resource<F, Fut, T>(mut self, uri: &str, name: &str, mime_type: &str, handler: F) -> Self
+    where
+        F: Fn(Arc<C>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T>> + Send + 'static,
+        T: Into<ResourceData> + 'static,
+    {
+        self.handler
+            .registry_mut()
+            .register_resource(uri, name, mime_type, handler);
+        self
+    }
+
+    /// Expose a single resource with constant content.
+    ///
+    /// # Parameters
+    /// - `uri`: URI identifying the resource
+    /// - `name`: Human-readable resource name
+    /// - `mime_type`: MIME type of the content
+    /// - `content`: The resource content (`&str`/`String` for text, `Vec<u8>` for bytes)
+    ///
+    /// # Returns
+    /// The builder (for method chaining)
+    ///
+    /// # Examples
+    /// ```rust
+    /// let server = McpServerBuilder::new(context, "config-server", "1.0.0")
+    ///     .with_static_resource("config://app", "App config", "application/toml", include_str!("../app.toml"));
+    /// ```
+    pub fn with_static_resource(
+        mut self,
+        uri: &str,
+        name: &str,
+        mime_type: &str,
+        content: impl Into<ResourceData>,
+    ) -> Self {
+        self.handler
+            .registry_mut()
+            .register_static_resource(uri, name, mime_type, content);
+        self
+    }
+
     /// Add a prompt provider to expose reusable prompt templates.
     ///
     /// Prompt providers allow clients to discover and use parameterized prompt
@@ -133,14 +206,15 @@ impl<C: Send + Sync + 'static> McpServerBuilder<C> {
     ///                 Ok(PromptContent {
     ///                     messages: vec![
     ///                         PromptMessage {
-    ///                             role: "system".to_string(),
-    ///                             content: format!("You are a documentation expert for {} code.", language),
+    ///                             role: Role::User,
+    ///                             content: format!("You are a documentation expert for {} code.", language).into(),
     ///                         },
     ///                         PromptMessage {
-    ///                             role: "user".to_string(),
-    ///                             content: format!("Please write comprehensive documentation for this function:\n\n```{}\n{}\n```", language, code),
+    ///                             role: Role::User,
+    ///                             content: format!("Please write comprehensive documentation for this function:\n\n```{}\n{}\n```", language, code).into(),
     ///                         },
     ///                     ],
+    ///                     ..Default::default()
     ///                 })
     ///             }
     ///             _ => Err(anyhow::anyhow!("Unknown prompt: {}", name))
@@ -157,4 +231,57 @@ impl<C: Send + Sync + 'static> McpServerBuilder<C> {
             .register_prompt_provider(provider);
         self
     }
-}
\ No newline at end of file
+
+    /// Register a prompt whose arguments are described by a typed struct.
+    ///
+    /// The prompt's argument list (names, descriptions and `required` flags) is
+    /// derived from the `JsonSchema` of `A`. Before the handler runs, the
+    /// client's arguments are checked and deserialized into `A`. A missing
+    /// required argument or a value of the wrong type is answered with a
+    /// `-32602` invalid params error.
+    ///
+    /// # Type Parameters
+    /// - `A`: Argument type (must implement `JsonSchema` and `DeserializeOwned`)
+    /// - `H`: Handler function type (async closure taking `A` and the context)
+    ///
+    /// # Parameters
+    /// - `name`: Unique prompt name (used by clients to request the prompt)
+    /// - `description`: Human-readable description of the prompt
+    /// - `handler`: Async function that renders the prompt from its arguments
+    ///
+    /// # Returns
+    /// The builder (for method chaining)
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[derive(JsonSchema, Deserialize)]
+    /// struct ReviewArgs {
+    ///     /// The code to review
+    ///     code: String,
+    ///     /// Programming language of the code
+    ///     language: Option<String>,
+    /// }
+    ///
+    /// let server = McpServerBuilder::new(context, "review-server", "1.0.0")
+    ///     .with_prompt::<ReviewArgs, _>("code_review", "Review a piece of code", |args, ctx| async move {
+    ///         let language = args.language.unwrap_or_else(|| "unknown".to_string());
+    ///         Ok(PromptContent {
+    ///             description: None,
+    ///             messages: vec![PromptMessage::user(format!(
+    ///                 "Please review this {} code:\n\n{}",
+    ///                 language, args.code
+    ///             ))],
+    ///         })
+    ///     });
+    /// ```
+    pub fn with_prompt<A, H>(mut self, name: &str, description: &str, handler: H) -> Self
+    where
+        A: JsonSchema + DeserializeOwned + Send + 'static,
+        H: PromptHandler<A, C>,
+    {
+        self.handler
+            .registry_mut()
+            .register_prompt(name, description, handler);
+        self
+    }
+}
@@ -245,7 +245,10 @@ impl<C: Send + Sync + 'static> McpServerBuilder<C> {
             })
         });
         
-        self.handler.registry.tools.insert(name.to_string(), (tool_def, wrapper));
+        self.handler
+            .registry_mut()
+            .tools
+            .insert(name.to_string(), (tool_def, Arc::new(wrapper)));
         self
     }
 }
\ No newline at end of file
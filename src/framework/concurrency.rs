@@ -0,0 +1,256 @@
+//! Per-tool concurrency limits.
+//!
+//! This module provides `ConcurrencyLimit`, which caps how many calls of a tool
+//! run at once, and the `ToolConcurrency` handle, which enforces the limits and
+//! reports queue depths for monitoring.
+
+use crate::handler::{McpContext, McpNotification, SessionNotifier};
+use crate::protocol_impl::McpError;
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, PoisonError, RwLock,
+    },
+    time::Duration,
+};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// How often a queued call reports progress while it waits.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What happens to a call that arrives while a tool is at its limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueuePolicy {
+    /// Wait for a running call to finish, failing after the timeout
+    Wait(Duration),
+    /// Fail immediately
+    Reject,
+}
+
+/// Cap on the number of concurrent calls of one tool.
+///
+/// # Examples
+/// ```rust
+/// use solidmcp::framework::ConcurrencyLimit;
+/// use std::time::Duration;
+///
+/// // A serial device bridge: one call at a time, queue for up to 10 seconds
+/// ConcurrencyLimit::exclusive().wait_up_to(Duration::from_secs(10));
+///
+/// // At most four renders; turn away anything beyond that
+/// ConcurrencyLimit::new(4).reject_when_full();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConcurrencyLimit {
+    max_concurrency: usize,
+    policy: QueuePolicy,
+}
+
+impl ConcurrencyLimit {
+    /// Allow up to `max_concurrency` parallel calls; others wait up to 30 seconds.
+    pub fn new(max_concurrency: usize) -> Self {
+        Self {
+            max_concurrency: max_concurrency.max(1),
+            policy: QueuePolicy::Wait(Duration::from_secs(30)),
+        }
+    }
+
+    /// Allow a single call at a time.
+    pub fn exclusive() -> Self {
+        Self::new(1)
+    }
+
+    /// Let calls over the limit wait up to `timeout` for a slot.
+    pub fn wait_up_to(mut self, timeout: Duration) -> Self {
+        self.policy = QueuePolicy::Wait(timeout);
+        self
+    }
+
+    /// Reject calls over the limit immediately.
+    pub fn reject_when_full(mut self) -> Self {
+        self.policy = QueuePolicy::Reject;
+        self
+    }
+
+    /// Get the maximum number of parallel calls.
+    pub fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+
+    /// Get the policy for calls over the limit.
+    pub fn policy(&self) -> QueuePolicy {
+        self.policy
+    }
+}
+
+/// Current load of a concurrency-limited tool.
+///
+/// # Fields
+/// - `running`: Calls currently executing
+/// - `waiting`: Calls queued for a slot
+/// - `max_concurrency`: The tool's limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct QueueDepth {
+    pub running: usize,
+    pub waiting: usize,
+    pub max_concurrency: usize,
+}
+
+struct Gate {
+    limit: ConcurrencyLimit,
+    semaphore: Arc<Semaphore>,
+    waiting: AtomicUsize,
+}
+
+impl Gate {
+    fn depth(&self) -> QueueDepth {
+        QueueDepth {
+            running: self.limit.max_concurrency - self.semaphore.available_permits(),
+            waiting: self.waiting.load(Ordering::SeqCst),
+            max_concurrency: self.limit.max_concurrency,
+        }
+    }
+}
+
+/// Decrements the waiting count however the wait ends, including cancellation.
+struct Waiting<'a>(&'a AtomicUsize);
+
+impl<'a> Waiting<'a> {
+    fn enter(count: &'a AtomicUsize) -> Self {
+        count.fetch_add(1, Ordering::SeqCst);
+        Self(count)
+    }
+}
+
+impl Drop for Waiting<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Cloneable handle enforcing per-tool concurrency limits.
+///
+/// Calls over a tool's limit either wait for a slot or fail with
+/// `McpError::Busy`, depending on the tool's [`QueuePolicy`]. While a call
+/// waits, it sends `notifications/progress` every second to its session if the
+/// request carried a `progressToken`, with `progress` counting the seconds
+/// spent queued.
+///
+/// Obtain it from `McpServerBuilder::tool_concurrency` (or
+/// `FrameworkHandler::tool_concurrency`) to read queue depths.
+#[derive(Clone, Default)]
+pub struct ToolConcurrency {
+    gates: Arc<RwLock<HashMap<String, Arc<Gate>>>>,
+}
+
+impl ToolConcurrency {
+    /// Set or replace the concurrency limit of a tool.
+    ///
+    /// Calls already running or queued keep the previous limit.
+    pub fn set_limit(&self, tool: &str, limit: ConcurrencyLimit) {
+        let gate = Gate {
+            limit,
+            semaphore: Arc::new(Semaphore::new(limit.max_concurrency)),
+            waiting: AtomicUsize::new(0),
+        };
+        self.gates
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(tool.to_string(), Arc::new(gate));
+    }
+
+    /// Remove the concurrency limit of a tool.
+    pub fn remove_limit(&self, tool: &str) {
+        self.gates
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(tool);
+    }
+
+    /// Get the load of one tool, `None` if it has no limit.
+    pub fn queue_depth(&self, tool: &str) -> Option<QueueDepth> {
+        self.gate(tool).map(|gate| gate.depth())
+    }
+
+    /// Get the load of every concurrency-limited tool.
+    pub fn queue_depths(&self) -> HashMap<String, QueueDepth> {
+        self.gates
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(tool, gate)| (tool.clone(), gate.depth()))
+            .collect()
+    }
+
+    fn gate(&self, tool: &str) -> Option<Arc<Gate>> {
+        self.gates
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(tool)
+            .cloned()
+    }
+
+    /// Wait for a slot to call `tool`, according to its policy.
+    ///
+    /// # Returns
+    /// A permit to hold while the tool runs, `None` if the tool has no limit
+    pub(crate) async fn acquire(
+        &self,
+        tool: &str,
+        context: &McpContext,
+        notifier: &SessionNotifier,
+    ) -> Result<Option<OwnedSemaphorePermit>, McpError> {
+        let Some(gate) = self.gate(tool) else {
+            return Ok(None);
+        };
+        if let Ok(permit) = gate.semaphore.clone().try_acquire_owned() {
+            return Ok(Some(permit));
+        }
+        let busy = || {
+            McpError::Busy(format!(
+                "tool '{}' is at its limit of {} concurrent call(s)",
+                tool, gate.limit.max_concurrency
+            ))
+        };
+        let QueuePolicy::Wait(timeout) = gate.limit.policy else {
+            return Err(busy());
+        };
+
+        let _waiting = Waiting::enter(&gate.waiting);
+        let progress_token = context
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.get("progressToken"))
+            .map(|token| match token {
+                Value::String(token) => token.clone(),
+                other => other.to_string(),
+            });
+        let acquire = gate.semaphore.clone().acquire_owned();
+        let deadline = tokio::time::sleep(timeout);
+        let mut ticks = tokio::time::interval(PROGRESS_INTERVAL);
+        tokio::pin!(acquire, deadline);
+        let mut waited = 0u64;
+        loop {
+            tokio::select! {
+                permit = &mut acquire => return permit.map(Some).map_err(|_| busy()),
+                _ = &mut deadline => return Err(busy()),
+                _ = ticks.tick() => {
+                    if let (Some(token), Some(session_id)) = (&progress_token, &context.session_id) {
+                        notifier.notify(
+                            session_id,
+                            McpNotification::Progress {
+                                progress_token: token.clone(),
+                                progress: waited as f64,
+                                total: None,
+                            },
+                        );
+                    }
+                    waited += 1;
+                }
+            }
+        }
+    }
+}
@@ -6,14 +6,29 @@
 
 use crate::handler::{
     McpContext, McpHandler, PromptContent, PromptInfo, ResourceContent, ResourceInfo,
-    ToolDefinition,
+    SessionNotifier, ToolDefinition,
 };
+use crate::admin::AdminApi;
+use crate::audit::AuditLog;
+use crate::auth::{AccessDenied, Authenticator};
+use crate::health::{CheckStatus, Probe};
+use crate::middleware::Middleware;
+use crate::oauth::ProtectedResourceMetadata;
+use crate::protocol_impl::McpError;
+use crate::session_store::{SessionEndReason, SessionStore};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::Value;
-use std::sync::Arc;
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
-use super::{notification::NotificationCtx, registry::ToolRegistry};
+use super::{
+    concurrency::{ConcurrencyLimit, ToolConcurrency},
+    health::HealthChecks,
+    lifecycle::SessionHooks,
+    notification::NotificationCtx,
+    registry::{RegistryHandle, ToolRegistry},
+    visibility::{ToolFilter, ToolVisibility},
+};
 
 /// Framework handler that automatically routes MCP requests to registered tools and providers.
 ///
@@ -21,11 +36,32 @@ use super::{notification::NotificationCtx, registry::ToolRegistry};
 /// low-level MCP protocol. It maintains the application context and routing table,
 /// and handles all the protocol-level details automatically.
 ///
+/// The registry is shared with any [`RegistryHandle`] obtained from the handler,
+/// so tools, resources and prompts can change while the server is running.
+///
+/// Which tools a session can list and call is decided by the optional
+/// [`ToolFilter`] and the per-session overrides of [`ToolVisibility`].
+/// Tools can additionally require scopes of the authenticated principal, and
+/// cap how many of their calls run at once with a [`ConcurrencyLimit`].
+///
 /// # Type Parameters
 /// - `C`: The application context type (shared across all handlers)
 pub struct FrameworkHandler<C> {
     pub(super) context: Arc<C>,
-    pub(super) registry: ToolRegistry<C>,
+    pub(super) registry: Arc<RwLock<ToolRegistry<C>>>,
+    pub(super) notifier: SessionNotifier,
+    pub(super) tool_filter: Option<ToolFilter>,
+    pub(super) visibility: ToolVisibility,
+    pub(super) middleware: Vec<Arc<dyn Middleware>>,
+    pub(super) authenticator: Option<Arc<dyn Authenticator>>,
+    pub(super) resource_metadata: Option<ProtectedResourceMetadata>,
+    pub(super) concurrency: ToolConcurrency,
+    pub(super) session_store: Option<Arc<dyn SessionStore>>,
+    pub(super) session_hooks: SessionHooks<C>,
+    pub(super) health: HealthChecks<C>,
+    pub(super) metrics_path: Option<String>,
+    pub(super) audit: Option<AuditLog>,
+    pub(super) admin: Option<AdminApi>,
     pub(super) server_name: String,
     pub(super) server_version: String,
 }
@@ -56,9 +92,23 @@ impl<C: Send + Sync + 'static> FrameworkHandler<C> {
     /// let handler = FrameworkHandler::new(context, "my-mcp-server", "1.0.0");
     /// ```
     pub fn new(context: C, server_name: &str, server_version: &str) -> Self {
+        let notifier = SessionNotifier::default();
         Self {
             context: Arc::new(context),
-            registry: ToolRegistry::new(),
+            registry: Arc::new(RwLock::new(ToolRegistry::new())),
+            tool_filter: None,
+            visibility: ToolVisibility::new(notifier.clone()),
+            middleware: Vec::new(),
+            authenticator: None,
+            resource_metadata: None,
+            concurrency: ToolConcurrency::default(),
+            session_store: None,
+            session_hooks: SessionHooks::default(),
+            health: HealthChecks::default(),
+            metrics_path: None,
+            audit: None,
+            admin: None,
+            notifier,
             server_name: server_name.to_string(),
             server_version: server_version.to_string(),
         }
@@ -71,9 +121,288 @@ impl<C: Send + Sync + 'static> FrameworkHandler<C> {
     /// this directly.
     ///
     /// # Returns
-    /// Mutable reference to the internal `ToolRegistry`
-    pub fn registry_mut(&mut self) -> &mut ToolRegistry<C> {
-        &mut self.registry
+    /// Write guard over the internal `ToolRegistry`
+    pub fn registry_mut(&mut self) -> RwLockWriteGuard<'_, ToolRegistry<C>> {
+        self.registry.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Get a handle for changing the registry while the server is running.
+    ///
+    /// Every change made through the handle is announced to connected sessions
+    /// with the matching `list_changed` notification.
+    ///
+    /// # Returns
+    /// A cloneable `RegistryHandle` sharing this handler's registry
+    pub fn registry_handle(&self) -> RegistryHandle<C> {
+        RegistryHandle::new(self.registry.clone(), self.notifier.clone())
+    }
+
+    /// Set the predicate deciding which tools each session can see and call.
+    ///
+    /// Per-session overrides made through [`ToolVisibility`] take precedence
+    /// over the filter. Tools the filter rejects are neither listed nor callable.
+    ///
+    /// # Parameters
+    /// - `filter`: Predicate receiving the tool definition and the request context
+    ///
+    /// # Examples
+    /// ```rust
+    /// handler.set_tool_filter(|tool, ctx| {
+    ///     let is_admin_client = ctx
+    ///         .client_info
+    ///         .as_ref()
+    ///         .and_then(|info| info["name"].as_str())
+    ///         == Some("admin-console");
+    ///     is_admin_client || !tool.name.starts_with("admin_")
+    /// });
+    /// ```
+    pub fn set_tool_filter<F>(&mut self, filter: F)
+    where
+        F: Fn(&ToolDefinition, &McpContext) -> bool + Send + Sync + 'static,
+    {
+        self.tool_filter = Some(Arc::new(filter));
+    }
+
+    /// Get a handle for showing or hiding tools in individual sessions.
+    ///
+    /// # Returns
+    /// A cloneable `ToolVisibility` sharing this handler's per-session overrides
+    pub fn tool_visibility(&self) -> ToolVisibility {
+        self.visibility.clone()
+    }
+
+    /// Add a middleware layer around every request this handler serves.
+    ///
+    /// Layers run in the order they are added, the first being the outermost.
+    ///
+    /// # Parameters
+    /// - `middleware`: The layer to add
+    pub fn add_middleware(&mut self, middleware: impl Middleware + 'static) {
+        self.middleware.push(Arc::new(middleware));
+    }
+
+    /// Require every HTTP request and WebSocket upgrade to authenticate.
+    ///
+    /// # Parameters
+    /// - `authenticator`: Decides who each request comes from
+    pub fn set_authenticator(&mut self, authenticator: impl Authenticator + 'static) {
+        self.authenticator = Some(Arc::new(authenticator));
+    }
+
+    /// Publish OAuth protected resource metadata at `/.well-known/oauth-protected-resource`.
+    ///
+    /// # Parameters
+    /// - `metadata`: The resource URI, authorization servers and supported scopes
+    pub fn set_protected_resource_metadata(&mut self, metadata: ProtectedResourceMetadata) {
+        self.resource_metadata = Some(metadata);
+    }
+
+    /// Keep the protocol state of each session in `store` instead of the
+    /// default `InMemorySessionStore`.
+    ///
+    /// # Parameters
+    /// - `store`: The session store the engine should use
+    pub fn set_session_store(&mut self, store: impl SessionStore + 'static) {
+        self.session_store = Some(Arc::new(store));
+    }
+
+    /// Serve Prometheus metrics at `path` instead of `/metrics`.
+    ///
+    /// # Parameters
+    /// - `path`: The HTTP path of the metrics endpoint, e.g. `/internal/metrics`
+    pub fn set_metrics_path(&mut self, path: impl Into<String>) {
+        self.metrics_path = Some(path.into());
+    }
+
+    /// Record every tool call, with its caller, arguments, duration and outcome.
+    ///
+    /// # Parameters
+    /// - `audit`: The sink records go to and the rules redacting their arguments
+    pub fn set_audit_log(&mut self, audit: AuditLog) {
+        self.audit = Some(audit);
+    }
+
+    /// Serve the admin API for listing sessions and in-flight requests,
+    /// terminating sessions and cancelling requests.
+    ///
+    /// # Parameters
+    /// - `admin`: Who may use the API and where it is served
+    pub fn set_admin_api(&mut self, admin: AdminApi) {
+        self.admin = Some(admin);
+    }
+
+    /// Run `hook` whenever a session completes `initialize`.
+    ///
+    /// An error from the hook fails the `initialize` request.
+    ///
+    /// # Parameters
+    /// - `hook`: Async function receiving the application context and the request's `McpContext`
+    pub fn add_session_start_hook<F, Fut>(&mut self, hook: F)
+    where
+        F: Fn(Arc<C>, McpContext) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<()>> + Send + 'static,
+    {
+        self.session_hooks.add_start(hook);
+    }
+
+    /// Run `hook` whenever a session ends.
+    ///
+    /// # Parameters
+    /// - `hook`: Async function receiving the application context, the session ID and the reason
+    pub fn add_session_end_hook<F, Fut>(&mut self, hook: F)
+    where
+        F: Fn(Arc<C>, String, SessionEndReason) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<()>> + Send + 'static,
+    {
+        self.session_hooks.add_end(hook);
+    }
+
+    /// Add a check reported by the `/health/live` or `/health/ready` endpoint.
+    ///
+    /// Checks run at once, each failing if it errors or takes longer than
+    /// `DEFAULT_CHECK_TIMEOUT`. Readiness runs the liveness checks too.
+    ///
+    /// # Parameters
+    /// - `name`: Name the check is reported under
+    /// - `probe`: The probe the check belongs to
+    /// - `check`: Async function receiving the application context
+    pub fn add_health_check<F, Fut>(&mut self, name: &str, probe: Probe, check: F)
+    where
+        F: Fn(Arc<C>) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<()>> + Send + 'static,
+    {
+        self.health.add_check(name, probe, check);
+    }
+
+    /// Run `hook` before the server reports ready.
+    ///
+    /// Hooks run in registration order once the server listens. If one fails,
+    /// the server stays unready.
+    ///
+    /// # Parameters
+    /// - `hook`: Async function receiving the application context
+    pub fn add_warm_up<F, Fut>(&mut self, hook: F)
+    where
+        F: Fn(Arc<C>) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<()>> + Send + 'static,
+    {
+        self.health.add_warm_up(hook);
+    }
+
+    /// Require the caller of a tool to hold every one of `scopes`.
+    ///
+    /// Calls from principals missing a scope, or from unauthenticated requests,
+    /// are refused with `AccessDenied::InsufficientScope`. The tool is still
+    /// listed. The requirement lives in the registry, so a `RegistryHandle`
+    /// can read and change it at runtime.
+    ///
+    /// # Parameters
+    /// - `tool`: Name of the tool
+    /// - `scopes`: Scopes the principal must have been granted
+    pub fn require_tool_scopes<I, S>(&mut self, tool: &str, scopes: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.registry_mut().require_tool_scopes(tool, scopes);
+    }
+
+    /// Limit how many calls of a tool run at once.
+    ///
+    /// # Parameters
+    /// - `tool`: Name of the tool
+    /// - `limit`: Maximum parallel calls and what happens to calls over it
+    pub fn set_tool_concurrency(&mut self, tool: &str, limit: ConcurrencyLimit) {
+        self.concurrency.set_limit(tool, limit);
+    }
+
+    /// Get a handle to the per-tool concurrency limits and their queue depths.
+    ///
+    /// # Returns
+    /// A cloneable `ToolConcurrency` sharing this handler's limits
+    pub fn tool_concurrency(&self) -> ToolConcurrency {
+        self.concurrency.clone()
+    }
+
+    /// Check that every concurrency limit names a registered tool.
+    ///
+    /// `McpServerBuilder::build` calls this so a misspelled tool name fails at
+    /// startup instead of leaving the tool unlimited.
+    ///
+    /// # Returns
+    /// `Result<()>` - An error listing every unknown tool, if any
+    pub fn validate_tool_concurrency(&self) -> Result<()> {
+        let registry = self.registry();
+        let mut unknown: Vec<String> = self
+            .concurrency
+            .queue_depths()
+            .into_keys()
+            .filter(|tool| !registry.tools.contains_key(tool))
+            .collect();
+        if unknown.is_empty() {
+            return Ok(());
+        }
+        unknown.sort();
+        Err(anyhow::anyhow!(
+            "Concurrency limits set for unknown tools: {}",
+            unknown.join(", ")
+        ))
+    }
+
+    /// Check that the caller holds every scope a tool requires.
+    fn check_tool_scopes(&self, name: &str, context: &McpContext) -> Result<(), AccessDenied> {
+        let registry = self.registry();
+        let missing: Vec<&str> = registry
+            .tool_scopes(name)
+            .iter()
+            .filter(|scope| !context.principal.as_ref().is_some_and(|p| p.has_scope(scope)))
+            .map(String::as_str)
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(AccessDenied::InsufficientScope {
+                tool: name.to_string(),
+                scope: missing.join(" "),
+            })
+        }
+    }
+
+    /// Check whether a tool is visible to the session making a request.
+    fn is_tool_visible(&self, tool: &ToolDefinition, context: &McpContext) -> bool {
+        let session_override = context
+            .session_id
+            .as_deref()
+            .and_then(|session_id| self.visibility.session_override(session_id, &tool.name));
+        match (session_override, &self.tool_filter) {
+            (Some(visible), _) => visible,
+            (None, Some(filter)) => filter(tool, context),
+            (None, None) => true,
+        }
+    }
+
+    /// Run a tool for a `tools/call` request.
+    async fn dispatch_tool(&self, name: &str, arguments: Value, context: &McpContext) -> Result<Value> {
+        // Clone the function out so the registry can change while the call runs.
+        // Hidden tools are reported exactly like unknown ones.
+        let tool_fn = self
+            .registry()
+            .tools
+            .get(name)
+            .filter(|(def, _)| self.is_tool_visible(def, context))
+            .map(|(_, f)| f.clone());
+        if let Some(tool_fn) = tool_fn {
+            self.check_tool_scopes(name, context)?;
+            let _permit = self.concurrency.acquire(name, context, &self.notifier).await?;
+            let notification_ctx = NotificationCtx::from_mcp(context);
+            tool_fn(arguments, self.context.clone(), notification_ctx).await
+        } else {
+            Err(anyhow::anyhow!("Tool not found: {}", name))
+        }
+    }
+
+    pub(super) fn registry(&self) -> RwLockReadGuard<'_, ToolRegistry<C>> {
+        self.registry.read().unwrap_or_else(PoisonError::into_inner)
     }
 
     /// Get a shared reference to the application context.
@@ -94,9 +423,9 @@ impl<C: Send + Sync + 'static> McpHandler for FrameworkHandler<C> {
         Ok(serde_json::json!({
             "protocolVersion": "2025-06-18",
             "capabilities": {
-                "tools": {},
-                "resources": {},
-                "prompts": {}
+                "tools": { "listChanged": true },
+                "resources": { "listChanged": true },
+                "prompts": { "listChanged": true }
             },
             "serverInfo": {
                 "name": self.server_name,
@@ -105,45 +434,84 @@ impl<C: Send + Sync + 'static> McpHandler for FrameworkHandler<C> {
         }))
     }
 
-    async fn list_tools(&self, _context: &McpContext) -> Result<Vec<ToolDefinition>> {
+    async fn list_tools(&self, context: &McpContext) -> Result<Vec<ToolDefinition>> {
         Ok(self
-            .registry
+            .registry()
             .tools
             .values()
+            .filter(|(def, _)| self.is_tool_visible(def, context))
             .map(|(def, _)| def.clone())
             .collect())
     }
 
     async fn call_tool(&self, name: &str, arguments: Value, context: &McpContext) -> Result<Value> {
-        if let Some((_, tool_fn)) = self.registry.tools.get(name) {
-            let notification_ctx = NotificationCtx::from_mcp(context);
-            tool_fn(arguments, self.context.clone(), notification_ctx).await
-        } else {
-            Err(anyhow::anyhow!("Tool not found: {}", name))
-        }
+        let Some(audit) = &self.audit else {
+            return self.dispatch_tool(name, arguments, context).await;
+        };
+        let call = audit.start(name, &arguments, context);
+        let result = self.dispatch_tool(name, arguments, context).await;
+        call.finish(&result);
+        result
     }
 
     async fn list_resources(&self, _context: &McpContext) -> Result<Vec<ResourceInfo>> {
+        let providers: Vec<_> = self.registry().resources.iter().map(|(_, p)| p.clone()).collect();
         let mut all_resources = Vec::new();
-        for provider in &self.registry.resources {
+        for provider in providers {
             let mut resources = provider.list_resources(self.context.clone()).await?;
             all_resources.append(&mut resources);
         }
         Ok(all_resources)
     }
 
-    async fn read_resource(&self, uri: &str, _context: &McpContext) -> Result<ResourceContent> {
-        for provider in &self.registry.resources {
-            if let Ok(content) = provider.read_resource(uri, self.context.clone()).await {
-                return Ok(content);
+    async fn read_resource(&self, uri: &str, context: &McpContext) -> Result<ResourceContent> {
+        self.read_resource_contents(uri, context)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("Resource has no contents: {}", uri))
+    }
+
+    async fn read_resource_contents(
+        &self,
+        uri: &str,
+        _context: &McpContext,
+    ) -> Result<Vec<ResourceContent>> {
+        let (owner, unrouted) = {
+            let registry = self.registry();
+            let owner = registry
+                .resource_routes
+                .route(uri)
+                .map(|index| registry.resources[index].1.clone());
+            let unrouted: Vec<_> = registry
+                .resource_routes
+                .unrouted
+                .iter()
+                .map(|&index| registry.resources[index].1.clone())
+                .collect();
+            (owner, unrouted)
+        };
+
+        if let Some(provider) = owner {
+            return provider
+                .read_resource_contents(uri, self.context.clone())
+                .await;
+        }
+        for provider in unrouted {
+            if let Ok(contents) = provider
+                .read_resource_contents(uri, self.context.clone())
+                .await
+            {
+                return Ok(contents);
             }
         }
         Err(anyhow::anyhow!("Resource not found: {}", uri))
     }
 
     async fn list_prompts(&self, _context: &McpContext) -> Result<Vec<PromptInfo>> {
+        let providers: Vec<_> = self.registry().prompts.iter().map(|(_, p)| p.clone()).collect();
         let mut all_prompts = Vec::new();
-        for provider in &self.registry.prompts {
+        for provider in providers {
             let mut prompts = provider.list_prompts(self.context.clone()).await?;
             all_prompts.append(&mut prompts);
         }
@@ -156,14 +524,84 @@ impl<C: Send + Sync + 'static> McpHandler for FrameworkHandler<C> {
         arguments: Option<Value>,
         _context: &McpContext,
     ) -> Result<PromptContent> {
-        for provider in &self.registry.prompts {
-            if let Ok(content) = provider
+        let (owner, unrouted) = {
+            let registry = self.registry();
+            let owner = registry
+                .prompt_routes
+                .route(name)
+                .map(|index| registry.prompts[index].1.clone());
+            let unrouted: Vec<_> = registry
+                .prompt_routes
+                .unrouted
+                .iter()
+                .map(|&index| registry.prompts[index].1.clone())
+                .collect();
+            (owner, unrouted)
+        };
+
+        if let Some(provider) = owner {
+            return provider
+                .get_prompt(name, arguments, self.context.clone())
+                .await;
+        }
+        for provider in unrouted {
+            match provider
                 .get_prompt(name, arguments.clone(), self.context.clone())
                 .await
             {
-                return Ok(content);
+                Ok(content) => return Ok(content),
+                // The provider owns the prompt but rejected the request
+                Err(e) if e.is::<McpError>() => return Err(e),
+                Err(_) => continue,
             }
         }
         Err(anyhow::anyhow!("Prompt not found: {}", name))
     }
+
+    fn session_notifier(&self) -> Option<SessionNotifier> {
+        Some(self.notifier.clone())
+    }
+
+    fn middleware(&self) -> Vec<Arc<dyn Middleware>> {
+        self.middleware.clone()
+    }
+
+    fn authenticator(&self) -> Option<Arc<dyn Authenticator>> {
+        self.authenticator.clone()
+    }
+
+    fn protected_resource_metadata(&self) -> Option<ProtectedResourceMetadata> {
+        self.resource_metadata.clone()
+    }
+
+    fn session_store(&self) -> Option<Arc<dyn SessionStore>> {
+        self.session_store.clone()
+    }
+
+    fn metrics_path(&self) -> Option<String> {
+        self.metrics_path.clone()
+    }
+
+    fn admin_api(&self) -> Option<AdminApi> {
+        self.admin.clone()
+    }
+
+    async fn on_session_start(&self, context: &McpContext) -> Result<()> {
+        self.session_hooks.started(&self.context, context).await
+    }
+
+    async fn on_session_end(&self, session_id: &str, reason: SessionEndReason) -> Result<()> {
+        // Per-session tool overrides do not outlive the session
+        self.visibility.reset_session(session_id);
+        self.session_hooks.ended(&self.context, session_id, reason).await;
+        Ok(())
+    }
+
+    async fn check_health(&self, probe: Probe) -> Vec<CheckStatus> {
+        self.health.run(&self.context, probe).await
+    }
+
+    async fn warm_up(&self) -> Result<()> {
+        self.health.warm_up(&self.context).await
+    }
 }
\ No newline at end of file
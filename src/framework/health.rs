@@ -0,0 +1,78 @@
+//! Health checks and warm-up hooks.
+//!
+//! This module provides the hook types registered with
+//! `McpServerBuilder::with_health_check` and `McpServerBuilder::with_warm_up`,
+//! which run against the application context to report whether the server's
+//! dependencies work and to prepare them before the server reports ready.
+
+use crate::health::{CheckStatus, Probe, DEFAULT_CHECK_TIMEOUT};
+use anyhow::Result;
+use futures_util::future::join_all;
+use std::{future::Future, pin::Pin, sync::Arc};
+
+/// Health check called with the application context; an error fails the check.
+pub type HealthCheck<C> =
+    Arc<dyn Fn(Arc<C>) -> Pin<Box<dyn Future<Output = Result<()>> + Send>> + Send + Sync>;
+
+/// Hook called with the application context before the server reports ready.
+pub type WarmUpHook<C> = HealthCheck<C>;
+
+/// The health checks and warm-up hooks of a `FrameworkHandler`.
+pub(crate) struct HealthChecks<C> {
+    checks: Vec<(String, Probe, HealthCheck<C>)>,
+    warm_up: Vec<WarmUpHook<C>>,
+}
+
+impl<C> Default for HealthChecks<C> {
+    fn default() -> Self {
+        Self {
+            checks: Vec::new(),
+            warm_up: Vec::new(),
+        }
+    }
+}
+
+impl<C: Send + Sync + 'static> HealthChecks<C> {
+    pub(crate) fn add_check<F, Fut>(&mut self, name: &str, probe: Probe, check: F)
+    where
+        F: Fn(Arc<C>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.checks.push((
+            name.to_string(),
+            probe,
+            Arc::new(move |context| Box::pin(check(context))),
+        ));
+    }
+
+    pub(crate) fn add_warm_up<F, Fut>(&mut self, hook: F)
+    where
+        F: Fn(Arc<C>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.warm_up
+            .push(Arc::new(move |context| Box::pin(hook(context))));
+    }
+
+    /// Run the checks of a probe at once, in registration order.
+    ///
+    /// Readiness runs the liveness checks too.
+    pub(crate) async fn run(&self, context: &Arc<C>, probe: Probe) -> Vec<CheckStatus> {
+        let checks = self
+            .checks
+            .iter()
+            .filter(|(_, kind, _)| probe == Probe::Readiness || *kind == Probe::Liveness)
+            .map(|(name, _, check)| {
+                CheckStatus::run(name.as_str(), check(context.clone()), DEFAULT_CHECK_TIMEOUT)
+            });
+        join_all(checks).await
+    }
+
+    /// Run the warm-up hooks, stopping at the first error.
+    pub(crate) async fn warm_up(&self, context: &Arc<C>) -> Result<()> {
+        for hook in &self.warm_up {
+            hook(context.clone()).await?;
+        }
+        Ok(())
+    }
+}
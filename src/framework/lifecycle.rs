@@ -0,0 +1,79 @@
+//! Session lifecycle hooks.
+//!
+//! This module provides the hook types registered with
+//! `McpServerBuilder::on_session_start` and `McpServerBuilder::on_session_end`,
+//! which let applications set up and tear down per-client resources.
+
+use crate::handler::McpContext;
+use crate::session_store::SessionEndReason;
+use anyhow::Result;
+use std::{future::Future, pin::Pin, sync::Arc};
+use tracing::warn;
+
+/// Hook called with the application context and the `initialize` request's
+/// context when a session starts.
+pub type SessionStartHook<C> = Arc<
+    dyn Fn(Arc<C>, McpContext) -> Pin<Box<dyn Future<Output = Result<()>> + Send>> + Send + Sync,
+>;
+
+/// Hook called with the application context, the session ID and the reason
+/// when a session ends.
+pub type SessionEndHook<C> = Arc<
+    dyn Fn(Arc<C>, String, SessionEndReason) -> Pin<Box<dyn Future<Output = Result<()>> + Send>>
+        + Send
+        + Sync,
+>;
+
+/// The session lifecycle hooks of a `FrameworkHandler`, run in registration order.
+pub(crate) struct SessionHooks<C> {
+    start: Vec<SessionStartHook<C>>,
+    end: Vec<SessionEndHook<C>>,
+}
+
+impl<C> Default for SessionHooks<C> {
+    fn default() -> Self {
+        Self {
+            start: Vec::new(),
+            end: Vec::new(),
+        }
+    }
+}
+
+impl<C: Send + Sync + 'static> SessionHooks<C> {
+    pub(crate) fn add_start<F, Fut>(&mut self, hook: F)
+    where
+        F: Fn(Arc<C>, McpContext) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.start.push(Arc::new(move |context, request| {
+            Box::pin(hook(context, request))
+        }));
+    }
+
+    pub(crate) fn add_end<F, Fut>(&mut self, hook: F)
+    where
+        F: Fn(Arc<C>, String, SessionEndReason) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.end.push(Arc::new(move |context, session_id, reason| {
+            Box::pin(hook(context, session_id, reason))
+        }));
+    }
+
+    /// Run the start hooks, stopping at the first error.
+    pub(crate) async fn started(&self, context: &Arc<C>, request: &McpContext) -> Result<()> {
+        for hook in &self.start {
+            hook(context.clone(), request.clone()).await?;
+        }
+        Ok(())
+    }
+
+    /// Run every end hook, logging errors so one failing hook does not skip the rest.
+    pub(crate) async fn ended(&self, context: &Arc<C>, session_id: &str, reason: SessionEndReason) {
+        for hook in &self.end {
+            if let Err(e) = hook(context.clone(), session_id.to_string(), reason).await {
+                warn!("Session end hook failed for {}: {}", session_id, e);
+            }
+        }
+    }
+}
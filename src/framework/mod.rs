@@ -59,22 +59,32 @@
 
 // Module declarations
 pub mod builder;
+pub mod concurrency;
 pub mod handler;
+pub mod health;
+pub mod lifecycle;
 pub mod notification;
 pub mod providers;
 pub mod registry;
+pub mod routing;
+pub mod visibility;
 
 // Re-export main types
 pub use builder::McpServerBuilder;
+pub use concurrency::{ConcurrencyLimit, QueueDepth, QueuePolicy, ToolConcurrency};
 pub use handler::FrameworkHandler;
+pub use health::{HealthCheck, WarmUpHook};
+pub use lifecycle::{SessionEndHook, SessionStartHook};
 pub use notification::{
     notify_prompts_changed, notify_resources_changed, notify_tools_changed, send_notification,
     NotificationCtx,
 };
-pub use providers::{PromptProvider, ResourceProvider};
+pub use providers::{PromptHandler, PromptProvider, ResourceProvider};
+pub use routing::ResourceRoute;
+pub use visibility::{ToolFilter, ToolVisibility};
 
 // Re-export the registry for advanced users who might need it
-pub use registry::ToolRegistry;
+pub use registry::{ProviderId, RegistryHandle, ToolRegistry};
 
 // Re-export helper functions at the module level for backward compatibility
 pub use notification::send_notification as send_notification_helper;
\ No newline at end of file
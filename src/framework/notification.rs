@@ -4,10 +4,14 @@
 //! interface for sending notifications to MCP clients, including log messages,
 //! progress updates, and resource change notifications.
 
-use crate::handler::{LogLevel, McpContext, McpNotification};
+use crate::auth::Principal;
+use crate::handler::{ConnectionInfo, LogLevel, McpContext, McpNotification};
+use crate::session_store::{SessionData, SessionExtensions};
+use crate::trace_context::TraceContext;
 use anyhow::Result;
 use serde_json::Value;
 use tokio::sync::mpsc;
+use tracing::Span;
 
 /// Ergonomic notification context that simplifies sending notifications to MCP clients.
 ///
@@ -42,6 +46,7 @@ use tokio::sync::mpsc;
 #[derive(Clone)]
 pub struct NotificationCtx {
     sender: Option<mpsc::UnboundedSender<McpNotification>>,
+    request: McpContext,
 }
 
 impl NotificationCtx {
@@ -58,9 +63,136 @@ impl NotificationCtx {
     pub fn from_mcp(mcp: &McpContext) -> Self {
         Self {
             sender: mcp.notification_sender.clone(),
+            request: mcp.clone(),
         }
     }
 
+    /// Get the full MCP context of the request this tool is handling.
+    ///
+    /// The context carries the session ID, the client info, capabilities and protocol
+    /// version negotiated at `initialize`, the JSON-RPC request ID, the raw `_meta`
+    /// object and transport details such as HTTP headers and the remote address.
+    ///
+    /// # Examples
+    /// ```rust
+    /// let client_name = notif
+    ///     .request()
+    ///     .client_info
+    ///     .as_ref()
+    ///     .and_then(|info| info.get("name"))
+    ///     .and_then(|name| name.as_str())
+    ///     .unwrap_or("unknown");
+    /// ```
+    pub fn request(&self) -> &McpContext {
+        &self.request
+    }
+
+    /// Get the session ID of the client that sent this request.
+    pub fn session_id(&self) -> Option<&str> {
+        self.request.session_id.as_deref()
+    }
+
+    /// Get the JSON-RPC ID of the request being handled.
+    pub fn request_id(&self) -> Option<&Value> {
+        self.request.request_id.as_ref()
+    }
+
+    /// Get the client information sent during initialization.
+    pub fn client_info(&self) -> Option<&Value> {
+        self.request.client_info.as_ref()
+    }
+
+    /// Get the capabilities the client declared during initialization.
+    pub fn client_capabilities(&self) -> Option<&Value> {
+        self.request.client_capabilities.as_ref()
+    }
+
+    /// Get the protocol version negotiated with the client.
+    pub fn protocol_version(&self) -> Option<&str> {
+        self.request.protocol_version.as_deref()
+    }
+
+    /// Get the raw `_meta` object from the request params.
+    pub fn meta(&self) -> Option<&Value> {
+        self.request.meta.as_ref()
+    }
+
+    /// Get the HTTP headers and remote address of the client connection.
+    pub fn connection(&self) -> Option<&ConnectionInfo> {
+        self.request.connection.as_ref()
+    }
+
+    /// Get the identity the request was authenticated as.
+    pub fn principal(&self) -> Option<&Principal> {
+        self.request.principal.as_ref()
+    }
+
+    /// Get the W3C trace context of the request.
+    ///
+    /// The context continues the caller's trace when the request carried a
+    /// `traceparent`, in `params._meta` or in the HTTP headers.
+    pub fn trace(&self) -> Option<&TraceContext> {
+        self.request.trace.as_ref()
+    }
+
+    /// Get the `traceparent` to send with downstream calls so they join the request's trace.
+    ///
+    /// With the `otel` feature it is taken from the OpenTelemetry context of
+    /// the request's span.
+    ///
+    /// # Examples
+    /// ```rust
+    /// let mut request = client.get(url);
+    /// if let Some(traceparent) = notif.traceparent() {
+    ///     request = request.header("traceparent", traceparent);
+    /// }
+    /// ```
+    pub fn traceparent(&self) -> Option<String> {
+        #[cfg(feature = "otel")]
+        if let Some(traceparent) = crate::trace_context::span_traceparent(&self.span()) {
+            return Some(traceparent);
+        }
+        self.trace().map(TraceContext::traceparent)
+    }
+
+    /// Get the `tracing` span the request is handled in.
+    ///
+    /// The tool already runs inside this span; use it to enter the span from
+    /// tasks the tool spawns, or as the parent of spans of your own.
+    pub fn span(&self) -> Span {
+        self.request.span.clone().unwrap_or_else(Span::none)
+    }
+
+    /// Get the typed values kept with the client's session.
+    ///
+    /// Unlike the application context `C`, which all clients share, these values
+    /// belong to one session and are dropped when it ends.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[derive(Clone)]
+    /// struct Login { user: String }
+    ///
+    /// notif.session().insert(Login { user: input.user });
+    /// let user = notif.session().get::<Login>().map(|login| login.user);
+    /// ```
+    pub fn session(&self) -> &SessionExtensions {
+        &self.request.session
+    }
+
+    /// Get the JSON data kept with the session of the client that sent this request.
+    ///
+    /// Unlike [`session`](Self::session), the data is saved with the session
+    /// state, so a durable `SessionStore` keeps it across restarts.
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// notif.session_data().insert("workspace", json!(input.path));
+    /// ```
+    pub fn session_data(&self) -> &SessionData {
+        &self.request.data
+    }
+
     /// Send an informational notification to the client.
     ///
     /// This is the most common type of notification for general status updates
@@ -299,4 +431,4 @@ pub fn notify_tools_changed(context: &McpContext) -> Result<()> {
 /// `Result<()>` - Ok if sent successfully, Err if channel is closed
 pub fn notify_prompts_changed(context: &McpContext) -> Result<()> {
     send_notification(context, McpNotification::PromptsListChanged)
-}
\ No newline at end of file
+}
@@ -4,10 +4,11 @@
 //! providers that can be registered with the MCP framework.
 
 use crate::handler::{PromptContent, PromptInfo, ResourceContent, ResourceInfo};
+use super::routing::ResourceRoute;
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::Value;
-use std::sync::Arc;
+use std::{future::Future, sync::Arc};
 
 /// Trait for providing resources dynamically to MCP clients.
 ///
@@ -33,6 +34,7 @@ use std::sync::Arc;
 ///                 name: "User Database".to_string(),
 ///                 description: Some("All registered users".to_string()),
 ///                 mime_type: Some("application/json".to_string()),
+///                 ..Default::default()
 ///             }
 ///         ])
 ///     }
@@ -46,7 +48,7 @@ use std::sync::Arc;
 ///                 Ok(ResourceContent {
 ///                     uri: uri.to_string(),
 ///                     mime_type: Some("application/json".to_string()),
-///                     content: serde_json::to_string_pretty(&users)?,
+///                     content: serde_json::to_string_pretty(&users)?.into(),
 ///                 })
 ///             }
 ///             _ => Err(anyhow::anyhow!("Resource not found: {}", uri))
@@ -74,6 +76,40 @@ pub trait ResourceProvider<C>: Send + Sync {
     /// # Returns
     /// `Result<ResourceContent>` - The resource content or an error if not found
     async fn read_resource(&self, uri: &str, context: Arc<C>) -> Result<ResourceContent>;
+
+    /// Read every content entry of a resource identified by URI.
+    ///
+    /// The default implementation returns the single entry from `read_resource`.
+    /// Override this when one URI maps to several contents, such as a directory.
+    ///
+    /// # Parameters
+    /// - `uri`: The unique identifier for the resource to read
+    /// - `context`: Shared application context
+    ///
+    /// # Returns
+    /// `Result<Vec<ResourceContent>>` - The resource contents or an error if not found
+    async fn read_resource_contents(&self, uri: &str, context: Arc<C>) -> Result<Vec<ResourceContent>>
+    where
+        C: Send + Sync + 'async_trait,
+    {
+        Ok(vec![self.read_resource(uri, context).await?])
+    }
+
+    /// Declare the URIs this provider owns.
+    ///
+    /// Reads of a URI covered by one of these routes go straight to this
+    /// provider, and its errors are returned to the client as-is. Two providers
+    /// declaring the same route is reported as an error by `build()`.
+    ///
+    /// The default declares nothing. Such providers are tried in registration
+    /// order for URIs no provider owns, and their errors are treated as
+    /// "not mine".
+    ///
+    /// # Returns
+    /// `Vec<ResourceRoute>` - Schemes, prefixes, templates or exact URIs owned by this provider
+    fn routes(&self) -> Vec<ResourceRoute> {
+        Vec::new()
+    }
 }
 
 /// Trait for providing dynamic prompt templates to MCP clients.
@@ -130,10 +166,11 @@ pub trait ResourceProvider<C>: Send + Sync {
 ///                 Ok(PromptContent {
 ///                     messages: vec![
 ///                         PromptMessage {
-///                             role: "user".to_string(),
-///                             content: format!("Please review this {} code:\n\n{}", language, code),
+///                             role: Role::User,
+///                             content: format!("Please review this {} code:\n\n{}", language, code).into(),
 ///                         }
 ///                     ],
+///                     ..Default::default()
 ///                 })
 ///             }
 ///             _ => Err(anyhow::anyhow!("Prompt not found: {}", name))
@@ -167,4 +204,44 @@ pub trait PromptProvider<C>: Send + Sync {
         arguments: Option<Value>,
         context: Arc<C>,
     ) -> Result<PromptContent>;
-}
\ No newline at end of file
+
+    /// Declare the prompt names this provider owns.
+    ///
+    /// Requests for these names go straight to this provider, and its errors
+    /// are returned to the client as-is. Two providers declaring the same name
+    /// is reported as an error by `build()`.
+    ///
+    /// The default declares nothing, in which case the provider is tried in
+    /// registration order for names no provider owns.
+    ///
+    /// # Returns
+    /// `Vec<String>` - Names of the prompts served by this provider
+    fn prompt_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Async function that renders a prompt from typed arguments.
+///
+/// This is implemented for every `Fn(A, Arc<C>) -> impl Future<Output = Result<PromptContent>>`,
+/// so any async closure with that shape can be passed to `McpServerBuilder::with_prompt`.
+/// It exists so the future type does not have to be named, which keeps
+/// `with_prompt::<Args, _>(...)` usable.
+///
+/// # Type Parameters
+/// - `A`: The prompt argument type
+/// - `C`: The application context type
+pub trait PromptHandler<A, C>:
+    Fn(A, Arc<C>) -> <Self as PromptHandler<A, C>>::Future + Send + Sync + 'static
+{
+    /// The future returned by the handler
+    type Future: Future<Output = Result<PromptContent>> + Send + 'static;
+}
+
+impl<A, C, F, Fut> PromptHandler<A, C> for F
+where
+    F: Fn(A, Arc<C>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<PromptContent>> + Send + 'static,
+{
+    type Future = Fut;
+}
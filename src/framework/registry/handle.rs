@@ -0,0 +1,331 @@
+//! Runtime registry handle.
+//!
+//! This module contains `RegistryHandle`, which changes the tools, resources and
+//! prompts of a running server and announces each change to connected sessions.
+
+use crate::{
+    handler::{McpNotification, ResourceData, SessionNotifier},
+    tool_response::IntoToolResponse,
+};
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    future::Future,
+    sync::{Arc, PoisonError, RwLock, RwLockWriteGuard},
+};
+
+use crate::framework::{
+    notification::NotificationCtx,
+    providers::{PromptHandler, PromptProvider, ResourceProvider},
+    registry::{validate_scopes, ProviderId, ToolRegistry},
+};
+
+/// Cloneable handle for changing a server's registry while it is running.
+///
+/// Obtain it from `McpServerBuilder::registry_handle` (or
+/// `FrameworkHandler::registry_handle`) before building the server, then keep
+/// it wherever plugins or feature flags are managed. After every change the
+/// handle sends `notifications/tools/list_changed`,
+/// `notifications/resources/list_changed` or `notifications/prompts/list_changed`
+/// to every session with a push channel (WebSocket connections). HTTP clients see
+/// the new lists on their next request.
+///
+/// Calls that are already running keep using the tool or provider they started
+/// with; only new requests see the change.
+///
+/// Additions of resource providers, resources, prompt providers and prompts
+/// fail if they claim a route or prompt name that is already owned, leaving the
+/// registry unchanged.
+///
+/// # Type Parameters
+/// - `C`: The application context type
+///
+/// # Examples
+/// ```rust
+/// let builder = McpServerBuilder::new(context, "plugin-host", "1.0.0");
+/// let registry = builder.registry_handle();
+/// let mut server = builder.build().await?;
+///
+/// tokio::spawn(async move {
+///     // Later, when a plugin is loaded:
+///     registry.add_tool("plugin_echo", "Echo from a plugin", |input: EchoInput, _ctx, _notif| async move {
+///         Ok(EchoOutput { text: input.text })
+///     });
+///     // ... and when it is unloaded:
+///     registry.remove_tool("plugin_echo");
+/// });
+///
+/// server.start(3000).await?;
+/// ```
+pub struct RegistryHandle<C> {
+    registry: Arc<RwLock<ToolRegistry<C>>>,
+    notifier: SessionNotifier,
+}
+
+impl<C> Clone for RegistryHandle<C> {
+    fn clone(&self) -> Self {
+        Self {
+            registry: self.registry.clone(),
+            notifier: self.notifier.clone(),
+        }
+    }
+}
+
+impl<C: Send + Sync + 'static> RegistryHandle<C> {
+    pub(crate) fn new(registry: Arc<RwLock<ToolRegistry<C>>>, notifier: SessionNotifier) -> Self {
+        Self { registry, notifier }
+    }
+
+    /// Add or replace a tool, with the same handler shape as `McpServerBuilder::with_tool`.
+    ///
+    /// # Parameters
+    /// - `name`: Unique tool name (an existing tool with this name is replaced)
+    /// - `description`: Human-readable description of what the tool does
+    /// - `handler`: Async function that takes input type I and returns output type O
+    pub fn add_tool<I, O, F, Fut>(&self, name: &str, description: &str, handler: F)
+    where
+        I: JsonSchema + DeserializeOwned + Send + 'static,
+        O: Serialize + JsonSchema + Send + 'static,
+        F: Fn(I, Arc<C>, NotificationCtx) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<O>> + Send + 'static,
+    {
+        let handler = Arc::new(handler);
+        let wrapped_handler = move |input: I, ctx: Arc<C>, notif: NotificationCtx| {
+            let handler = Arc::clone(&handler);
+            async move {
+                let result = handler(input, ctx, notif).await?;
+                Ok(result.into_tool_response())
+            }
+        };
+
+        self.write()
+            .register_tool(name, description, wrapped_handler);
+        self.notifier.broadcast(McpNotification::ToolsListChanged);
+    }
+
+    /// Remove a tool.
+    ///
+    /// # Returns
+    /// `true` if the tool existed (clients are only notified in that case)
+    pub fn remove_tool(&self, name: &str) -> bool {
+        let removed = self.write().unregister_tool(name);
+        if removed {
+            self.notifier.broadcast(McpNotification::ToolsListChanged);
+        }
+        removed
+    }
+
+    /// Require the caller of a tool to hold every one of `scopes`, like
+    /// `McpServerBuilder::with_tool_scopes`.
+    ///
+    /// The tool does not have to exist yet: setting its scopes before calling
+    /// `add_tool` means it is never callable without them. Scopes stay when
+    /// the tool is removed.
+    ///
+    /// # Returns
+    /// `Result<()>` - An error, leaving the requirements unchanged, if a scope
+    /// is not a valid OAuth scope token
+    pub fn require_tool_scopes<I, S>(&self, tool: &str, scopes: I) -> Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let scopes: Vec<String> = scopes.into_iter().map(Into::into).collect();
+        validate_scopes(&scopes)?;
+        self.write().require_tool_scopes(tool, scopes);
+        Ok(())
+    }
+
+    /// Get the scopes the caller of a tool must hold.
+    ///
+    /// # Returns
+    /// The required scopes, empty if the tool requires none
+    pub fn tool_scopes(&self, tool: &str) -> Vec<String> {
+        self.registry
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .tool_scopes(tool)
+            .to_vec()
+    }
+
+    /// Stop requiring scopes for a tool.
+    ///
+    /// # Returns
+    /// `true` if the tool required any scopes
+    pub fn clear_tool_scopes(&self, tool: &str) -> bool {
+        self.write().clear_tool_scopes(tool)
+    }
+
+    /// Add a resource provider.
+    ///
+    /// # Returns
+    /// `Result<ProviderId>` - Identifier for `remove_resource_provider`, or an
+    /// error if one of the provider's routes is already owned
+    pub fn add_resource_provider(
+        &self,
+        provider: Box<dyn ResourceProvider<C>>,
+    ) -> Result<ProviderId> {
+        self.add_resource_with(|registry| registry.register_resource_provider(provider))
+    }
+
+    /// Remove a resource provider.
+    ///
+    /// # Returns
+    /// `true` if the provider existed (clients are only notified in that case)
+    pub fn remove_resource_provider(&self, id: ProviderId) -> bool {
+        let removed = self.write().unregister_resource_provider(id);
+        if removed {
+            self.notifier
+                .broadcast(McpNotification::ResourcesListChanged);
+        }
+        removed
+    }
+
+    /// Add a single resource backed by an async closure, like `McpServerBuilder::with_resource`.
+    ///
+    /// # Returns
+    /// `Result<()>` - An error if `uri` is already owned by another provider
+    pub fn add_resource<F, Fut, T>(
+        &self,
+        uri: &str,
+        name: &str,
+        mime_type: &str,
+        handler: F,
+    ) -> Result<()>
+    where
+        F: Fn(Arc<C>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T>> + Send + 'static,
+        T: Into<ResourceData> + 'static,
+    {
+        self.add_resource_with(|registry| registry.register_resource(uri, name, mime_type, handler))
+            .map(|_| ())
+    }
+
+    /// Add a single resource with constant content, like `McpServerBuilder::with_static_resource`.
+    ///
+    /// # Returns
+    /// `Result<()>` - An error if `uri` is already owned by another provider
+    pub fn add_static_resource(
+        &self,
+        uri: &str,
+        name: &str,
+        mime_type: &str,
+        content: impl Into<ResourceData>,
+    ) -> Result<()> {
+        self.add_resource_with(|registry| {
+            registry.register_static_resource(uri, name, mime_type, content)
+        })
+        .map(|_| ())
+    }
+
+    /// Remove a resource added with `add_resource` or `add_static_resource`
+    /// (or the builder equivalents).
+    ///
+    /// # Returns
+    /// `true` if the resource existed (clients are only notified in that case)
+    pub fn remove_resource(&self, uri: &str) -> bool {
+        let removed = self.write().unregister_resource(uri);
+        if removed {
+            self.notifier
+                .broadcast(McpNotification::ResourcesListChanged);
+        }
+        removed
+    }
+
+    /// Add a prompt provider.
+    ///
+    /// # Returns
+    /// `Result<ProviderId>` - Identifier for `remove_prompt_provider`, or an
+    /// error if one of the provider's prompt names is already owned
+    pub fn add_prompt_provider(&self, provider: Box<dyn PromptProvider<C>>) -> Result<ProviderId> {
+        self.add_prompt_with(|registry| registry.register_prompt_provider(provider))
+    }
+
+    /// Remove a prompt provider.
+    ///
+    /// # Returns
+    /// `true` if the provider existed (clients are only notified in that case)
+    pub fn remove_prompt_provider(&self, id: ProviderId) -> bool {
+        let removed = self.write().unregister_prompt_provider(id);
+        if removed {
+            self.notifier.broadcast(McpNotification::PromptsListChanged);
+        }
+        removed
+    }
+
+    /// Add a typed prompt, like `McpServerBuilder::with_prompt`.
+    ///
+    /// # Returns
+    /// `Result<()>` - An error if a prompt named `name` is already owned by another provider
+    pub fn add_prompt<A, H>(&self, name: &str, description: &str, handler: H) -> Result<()>
+    where
+        A: JsonSchema + DeserializeOwned + Send + 'static,
+        H: PromptHandler<A, C>,
+    {
+        self.add_prompt_with(|registry| registry.register_prompt(name, description, handler))
+            .map(|_| ())
+    }
+
+    /// Remove a prompt added with `add_prompt` (or `McpServerBuilder::with_prompt`).
+    ///
+    /// # Returns
+    /// `true` if the prompt existed (clients are only notified in that case)
+    pub fn remove_prompt(&self, name: &str) -> bool {
+        let removed = self.write().unregister_prompt(name);
+        if removed {
+            self.notifier.broadcast(McpNotification::PromptsListChanged);
+        }
+        removed
+    }
+
+    /// Check that no resource route or prompt name is claimed twice.
+    ///
+    /// # Returns
+    /// `Result<()>` - An error listing every conflict, if any
+    pub fn validate_routes(&self) -> Result<()> {
+        self.registry
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .validate_routes()
+    }
+
+    /// Register a resource provider, rolling back if its routes conflict.
+    fn add_resource_with(
+        &self,
+        register: impl FnOnce(&mut ToolRegistry<C>) -> ProviderId,
+    ) -> Result<ProviderId> {
+        let mut registry = self.write();
+        let id = register(&mut registry);
+        if let Err(e) = registry.validate_routes() {
+            registry.unregister_resource_provider(id);
+            return Err(e);
+        }
+        drop(registry);
+        self.notifier
+            .broadcast(McpNotification::ResourcesListChanged);
+        Ok(id)
+    }
+
+    /// Register a prompt provider, rolling back if its names conflict.
+    fn add_prompt_with(
+        &self,
+        register: impl FnOnce(&mut ToolRegistry<C>) -> ProviderId,
+    ) -> Result<ProviderId> {
+        let mut registry = self.write();
+        let id = register(&mut registry);
+        if let Err(e) = registry.validate_routes() {
+            registry.unregister_prompt_provider(id);
+            return Err(e);
+        }
+        drop(registry);
+        self.notifier.broadcast(McpNotification::PromptsListChanged);
+        Ok(id)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, ToolRegistry<C>> {
+        self.registry
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -4,9 +4,14 @@
 //! of registered functionality that can be exposed to MCP clients. It provides
 //! type-safe registration methods with automatic schema generation.
 
+mod handle;
+mod prompt_registration;
 mod provider_registration;
+mod resource_registration;
 mod tool_registration;
 
+pub use handle::RegistryHandle;
+
 use crate::{
     handler::ToolDefinition,
     typed_response::OutputSchema,
@@ -14,7 +19,11 @@ use crate::{
 use serde_json::Value;
 use std::{collections::HashMap, future::Future, pin::Pin, sync::Arc};
 
-use super::{notification::NotificationCtx, providers::{ResourceProvider, PromptProvider}};
+use super::{
+    notification::NotificationCtx,
+    providers::{PromptProvider, ResourceProvider},
+    routing::{PromptRouter, ResourceRoute, ResourceRouter},
+};
 
 
 /// Type alias for tool handler functions.
@@ -46,12 +55,20 @@ pub type ToolFunction<C> = Box<
 /// # Type Parameters
 /// - `C`: The application context type shared across all registered handlers
 pub struct ToolRegistry<C> {
-    pub(crate) tools: HashMap<String, (ToolDefinition, ToolFunction<C>)>,
+    pub(crate) tools: HashMap<String, (ToolDefinition, Arc<ToolFunction<C>>)>,
     pub(crate) output_schemas: HashMap<String, OutputSchema>,
-    pub(crate) resources: Vec<Box<dyn ResourceProvider<C>>>,
-    pub(crate) prompts: Vec<Box<dyn PromptProvider<C>>>,
+    pub(crate) resources: Vec<(ProviderId, Arc<dyn ResourceProvider<C>>)>,
+    pub(crate) prompts: Vec<(ProviderId, Arc<dyn PromptProvider<C>>)>,
+    pub(crate) resource_routes: ResourceRouter,
+    pub(crate) prompt_routes: PromptRouter,
+    pub(crate) tool_scopes: HashMap<String, Vec<String>>,
+    next_provider_id: u64,
 }
 
+/// Identifies a registered resource or prompt provider so it can be removed later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProviderId(u64);
+
 impl<C> Default for ToolRegistry<C> {
     fn default() -> Self {
         Self {
@@ -59,6 +76,10 @@ impl<C> Default for ToolRegistry<C> {
             output_schemas: HashMap::new(),
             resources: Vec::new(),
             prompts: Vec::new(),
+            resource_routes: ResourceRouter::default(),
+            prompt_routes: PromptRouter::default(),
+            tool_scopes: HashMap::new(),
+            next_provider_id: 0,
         }
     }
 }
@@ -78,4 +99,205 @@ impl<C: Send + Sync + 'static> ToolRegistry<C> {
     pub fn get_tool_output_schema(&self, name: &str) -> Option<&OutputSchema> {
         self.output_schemas.get(name)
     }
-}
\ No newline at end of file
+
+    /// Remove a tool by name.
+    ///
+    /// Calls to the tool that are already running complete normally. Scopes
+    /// required for the tool stay, so a tool added again under the same name
+    /// is protected from its first call.
+    ///
+    /// # Returns
+    /// `true` if a tool with that name was registered
+    pub fn unregister_tool(&mut self, name: &str) -> bool {
+        self.output_schemas.remove(name);
+        self.tools.remove(name).is_some()
+    }
+
+    /// Remove a resource provider by the ID returned at registration.
+    ///
+    /// # Returns
+    /// `true` if the provider was registered
+    pub fn unregister_resource_provider(&mut self, id: ProviderId) -> bool {
+        let before = self.resources.len();
+        self.resources.retain(|(provider_id, _)| *provider_id != id);
+        let removed = self.resources.len() != before;
+        if removed {
+            self.rebuild_resource_routes();
+        }
+        removed
+    }
+
+    /// Remove the single resource registered for `uri` with `register_resource`
+    /// or `register_static_resource`.
+    ///
+    /// # Returns
+    /// `true` if such a resource was registered
+    pub fn unregister_resource(&mut self, uri: &str) -> bool {
+        let owner = self.resources.iter().find_map(|(id, provider)| {
+            (provider.routes() == [ResourceRoute::exact(uri)]).then_some(*id)
+        });
+        owner.is_some_and(|id| self.unregister_resource_provider(id))
+    }
+
+    /// Remove a prompt provider by the ID returned at registration.
+    ///
+    /// # Returns
+    /// `true` if the provider was registered
+    pub fn unregister_prompt_provider(&mut self, id: ProviderId) -> bool {
+        let before = self.prompts.len();
+        self.prompts.retain(|(provider_id, _)| *provider_id != id);
+        let removed = self.prompts.len() != before;
+        if removed {
+            self.rebuild_prompt_routes();
+        }
+        removed
+    }
+
+    /// Remove the prompt registered for `name` with `register_prompt`.
+    ///
+    /// # Returns
+    /// `true` if such a prompt was registered
+    pub fn unregister_prompt(&mut self, name: &str) -> bool {
+        let owner = self
+            .prompts
+            .iter()
+            .find_map(|(id, provider)| (provider.prompt_names() == [name]).then_some(*id));
+        owner.is_some_and(|id| self.unregister_prompt_provider(id))
+    }
+
+    /// Require the caller of a tool to hold every one of `scopes`, in addition
+    /// to any scopes it already requires.
+    ///
+    /// The tool does not have to be registered yet.
+    pub fn require_tool_scopes<I, S>(&mut self, tool: &str, scopes: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let required = self.tool_scopes.entry(tool.to_string()).or_default();
+        for scope in scopes {
+            let scope = scope.into();
+            if !required.contains(&scope) {
+                required.push(scope);
+            }
+        }
+    }
+
+    /// Get the scopes the caller of a tool must hold.
+    ///
+    /// # Returns
+    /// The required scopes, empty if the tool requires none
+    pub fn tool_scopes(&self, tool: &str) -> &[String] {
+        self.tool_scopes.get(tool).map_or(&[], Vec::as_slice)
+    }
+
+    /// Stop requiring scopes for a tool.
+    ///
+    /// # Returns
+    /// `true` if the tool required any scopes
+    pub fn clear_tool_scopes(&mut self, tool: &str) -> bool {
+        self.tool_scopes.remove(tool).is_some()
+    }
+
+    /// Check that every tool with required scopes is registered and that every
+    /// scope is a valid OAuth scope token (RFC 6749 section 3.3).
+    ///
+    /// `McpServerBuilder::build` calls this so a misspelled tool name fails at
+    /// startup instead of leaving the tool unprotected.
+    ///
+    /// # Returns
+    /// `Result<()>` - An error listing every unknown tool and invalid scope, if any
+    pub fn validate_tool_scopes(&self) -> anyhow::Result<()> {
+        let mut unknown: Vec<&str> = self
+            .tool_scopes
+            .keys()
+            .filter(|tool| !self.tools.contains_key(*tool))
+            .map(String::as_str)
+            .collect();
+        unknown.sort_unstable();
+        let mut problems = Vec::new();
+        if !unknown.is_empty() {
+            problems.push(format!(
+                "Scopes required for unknown tools: {}",
+                unknown.join(", ")
+            ));
+        }
+        let mut scopes: Vec<&String> = self.tool_scopes.values().flatten().collect();
+        scopes.sort_unstable();
+        scopes.dedup();
+        if let Err(e) = validate_scopes(scopes) {
+            problems.push(e.to_string());
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(problems.join("; ")))
+        }
+    }
+
+    pub(crate) fn next_provider_id(&mut self) -> ProviderId {
+        self.next_provider_id += 1;
+        ProviderId(self.next_provider_id)
+    }
+
+    fn rebuild_resource_routes(&mut self) {
+        self.resource_routes = ResourceRouter::default();
+        for (index, (_, provider)) in self.resources.iter().enumerate() {
+            self.resource_routes.insert(provider.routes(), index);
+        }
+    }
+
+    fn rebuild_prompt_routes(&mut self) {
+        self.prompt_routes = PromptRouter::default();
+        for (index, (_, provider)) in self.prompts.iter().enumerate() {
+            self.prompt_routes.insert(provider.prompt_names(), index);
+        }
+    }
+
+    /// Check that no resource route or prompt name is claimed by two providers.
+    ///
+    /// `McpServerBuilder::build` calls this so ownership conflicts surface at
+    /// startup rather than as misrouted requests.
+    ///
+    /// # Returns
+    /// `Result<()>` - An error listing every conflict, if any
+    pub fn validate_routes(&self) -> anyhow::Result<()> {
+        let conflicts: Vec<&str> = self
+            .resource_routes
+            .conflicts
+            .iter()
+            .chain(&self.prompt_routes.conflicts)
+            .map(String::as_str)
+            .collect();
+        if conflicts.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(
+                "Conflicting provider registrations: {}",
+                conflicts.join("; ")
+            ))
+        }
+    }
+}
+
+/// Check that every scope is a valid OAuth scope token: one or more printable
+/// ASCII characters other than space, `"` and `\`.
+pub(crate) fn validate_scopes<'a>(
+    scopes: impl IntoIterator<Item = &'a String>,
+) -> anyhow::Result<()> {
+    let invalid: Vec<String> = scopes
+        .into_iter()
+        .filter(|scope| {
+            scope.is_empty()
+                || !scope
+                    .bytes()
+                    .all(|b| matches!(b, 0x21 | 0x23..=0x5B | 0x5D..=0x7E))
+        })
+        .map(|scope| format!("{scope:?}"))
+        .collect();
+    if invalid.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!("Invalid scopes: {}", invalid.join(", ")))
+    }
+}
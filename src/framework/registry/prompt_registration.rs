@@ -0,0 +1,132 @@
+//! Typed prompt registration for ToolRegistry.
+//!
+//! This module contains register_prompt, which turns an argument struct and an
+//! async handler into a prompt provider with schema-derived arguments and
+//! validation.
+
+use crate::{
+    handler::{PromptContent, PromptInfo},
+    protocol_impl::McpError,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::{marker::PhantomData, sync::Arc};
+
+use crate::framework::{
+    providers::{PromptHandler, PromptProvider},
+    registry::{ProviderId, ToolRegistry},
+};
+
+impl<C: Send + Sync + 'static> ToolRegistry<C> {
+    pub fn register_prompt<A, H>(&mut self, name: &str, description: &str, handler: H) -> ProviderId
+    where
+        A: JsonSchema + DeserializeOwned + Send + 'static,
+        H: PromptHandler<A, C>,
+    {
+        let schema = serde_json::to_value(schemars::schema_for!(A)).unwrap_or_default();
+        self.register_prompt_provider(Box::new(TypedPrompt {
+            info: PromptInfo::from_schema::<A>(name, description),
+            properties: schema["properties"]
+                .as_object()
+                .cloned()
+                .unwrap_or_default(),
+            handler,
+            _args: PhantomData,
+        }))
+    }
+}
+
+/// A single prompt backed by a typed handler
+struct TypedPrompt<A, H> {
+    info: PromptInfo,
+    properties: Map<String, Value>,
+    handler: H,
+    _args: PhantomData<fn() -> A>,
+}
+
+impl<A: DeserializeOwned, H> TypedPrompt<A, H> {
+    /// Check required arguments and deserialize the rest into `A`.
+    ///
+    /// Prompt arguments arrive as strings, so string values for non-string
+    /// properties (numbers, booleans) are parsed as JSON before deserializing.
+    fn parse_arguments(&self, arguments: Option<Value>) -> Result<A> {
+        let mut arguments = match arguments {
+            None | Some(Value::Null) => Map::new(),
+            Some(Value::Object(map)) => map,
+            Some(_) => {
+                return Err(McpError::InvalidParams(format!(
+                    "Arguments for prompt '{}' must be an object",
+                    self.info.name
+                ))
+                .into())
+            }
+        };
+
+        for argument in self.info.arguments.iter().filter(|a| a.required) {
+            if arguments.get(&argument.name).is_none_or(Value::is_null) {
+                return Err(McpError::InvalidParams(format!(
+                    "Missing required argument '{}' for prompt '{}'",
+                    argument.name, self.info.name
+                ))
+                .into());
+            }
+        }
+
+        for (key, value) in arguments.iter_mut() {
+            let accepts_string = self
+                .properties
+                .get(key)
+                .map(|property| match &property["type"] {
+                    Value::String(t) => t == "string",
+                    Value::Array(types) => types.iter().any(|t| t == "string"),
+                    _ => true,
+                })
+                .unwrap_or(true);
+            if let (false, Value::String(text)) = (accepts_string, &*value) {
+                if let Ok(parsed) = serde_json::from_str::<Value>(text) {
+                    *value = parsed;
+                }
+            }
+        }
+
+        serde_json::from_value(Value::Object(arguments)).map_err(|e| {
+            McpError::InvalidParams(format!(
+                "Invalid arguments for prompt '{}': {}",
+                self.info.name, e
+            ))
+            .into()
+        })
+    }
+}
+
+#[async_trait]
+impl<C, A, H> PromptProvider<C> for TypedPrompt<A, H>
+where
+    C: Send + Sync + 'static,
+    A: DeserializeOwned + Send + 'static,
+    H: PromptHandler<A, C>,
+{
+    async fn list_prompts(&self, _context: Arc<C>) -> Result<Vec<PromptInfo>> {
+        Ok(vec![self.info.clone()])
+    }
+
+    fn prompt_names(&self) -> Vec<String> {
+        vec![self.info.name.clone()]
+    }
+
+    async fn get_prompt(
+        &self,
+        name: &str,
+        arguments: Option<Value>,
+        context: Arc<C>,
+    ) -> Result<PromptContent> {
+        if name != self.info.name {
+            return Err(anyhow::anyhow!("Prompt not found: {}", name));
+        }
+        let arguments = self.parse_arguments(arguments)?;
+        (self.handler)(arguments, context).await
+    }
+}
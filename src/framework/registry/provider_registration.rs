@@ -2,9 +2,11 @@
 //!
 //! This module contains methods for registering resource and prompt providers.
 
+use std::sync::Arc;
+
 use crate::framework::{
     providers::{PromptProvider, ResourceProvider},
-    registry::ToolRegistry,
+    registry::{ProviderId, ToolRegistry},
 };
 
 impl<C: Send + Sync + 'static> ToolRegistry<C> {
@@ -34,8 +36,15 @@ impl<C: Send + Sync + 'static> ToolRegistry<C> {
     ///
     /// registry.register_resource_provider(Box::new(FileSystemProvider));
     /// ```
-    pub fn register_resource_provider(&mut self, provider: Box<dyn ResourceProvider<C>>) {
-        self.resources.push(provider);
+    ///
+    /// # Returns
+    /// `ProviderId` - Identifier for removing the provider with `unregister_resource_provider`
+    pub fn register_resource_provider(&mut self, provider: Box<dyn ResourceProvider<C>>) -> ProviderId {
+        let id = self.next_provider_id();
+        self.resource_routes
+            .insert(provider.routes(), self.resources.len());
+        self.resources.push((id, Arc::from(provider)));
+        id
     }
 
     /// Register a prompt provider for dynamic prompt template management.
@@ -64,7 +73,14 @@ impl<C: Send + Sync + 'static> ToolRegistry<C> {
     ///
     /// registry.register_prompt_provider(Box::new(CodeReviewProvider));
     /// ```  
-    pub fn register_prompt_provider(&mut self, provider: Box<dyn PromptProvider<C>>) {
-        self.prompts.push(provider);
+    ///
+    /// # Returns
+    /// `ProviderId` - Identifier for removing the provider with `unregister_prompt_provider`
+    pub fn register_prompt_provider(&mut self, provider: Box<dyn PromptProvider<C>>) -> ProviderId {
+        let id = self.next_provider_id();
+        self.prompt_routes
+            .insert(provider.prompt_names(), self.prompts.len());
+        self.prompts.push((id, Arc::from(provider)));
+        id
     }
 }
\ No newline at end of file
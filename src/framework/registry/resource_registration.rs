@@ -0,0 +1,124 @@
+//! Single-resource registration for ToolRegistry.
+//!
+//! This module contains register_resource and register_static_resource, which
+//! expose one URI backed by an async closure or constant content without a
+//! full `ResourceProvider` implementation.
+
+use crate::handler::{ResourceContent, ResourceData, ResourceInfo};
+use anyhow::Result;
+use async_trait::async_trait;
+use std::{future::Future, sync::Arc};
+
+use crate::framework::{
+    providers::ResourceProvider,
+    registry::{ProviderId, ToolRegistry},
+    routing::ResourceRoute,
+};
+
+impl<C: Send + Sync + 'static> ToolRegistry<C> {
+    /// Register a single resource whose content is produced by an async closure.
+    ///
+    /// The handler runs on every `resources/read` of `uri`. The resource is
+    /// listed and read through the same path as provider resources.
+    ///
+    /// # Parameters
+    /// - `uri`: URI identifying the resource
+    /// - `name`: Human-readable resource name
+    /// - `mime_type`: MIME type of the content
+    /// - `handler`: Async function returning the current content
+    ///
+    /// # Returns
+    /// `ProviderId` - Identifier of the provider serving the resource
+    pub fn register_resource<F, Fut, T>(
+        &mut self,
+        uri: &str,
+        name: &str,
+        mime_type: &str,
+        handler: F,
+    ) -> ProviderId
+    where
+        F: Fn(Arc<C>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T>> + Send + 'static,
+        T: Into<ResourceData> + 'static,
+    {
+        let info = ResourceInfo {
+            uri: uri.to_string(),
+            name: name.to_string(),
+            mime_type: Some(mime_type.to_string()),
+            ..Default::default()
+        };
+        self.register_resource_provider(Box::new(SingleResource { info, handler }))
+    }
+
+    /// Register a single resource with constant content.
+    ///
+    /// The content size is reported in `resources/list`.
+    ///
+    /// # Parameters
+    /// - `uri`: URI identifying the resource
+    /// - `name`: Human-readable resource name
+    /// - `mime_type`: MIME type of the content
+    /// - `content`: The resource content (text or bytes)
+    ///
+    /// # Returns
+    /// `ProviderId` - Identifier of the provider serving the resource
+    pub fn register_static_resource(
+        &mut self,
+        uri: &str,
+        name: &str,
+        mime_type: &str,
+        content: impl Into<ResourceData>,
+    ) -> ProviderId {
+        let content = content.into();
+        let size = match &content {
+            ResourceData::Text(text) => text.len(),
+            ResourceData::Blob(bytes) => bytes.len(),
+        };
+        let info = ResourceInfo {
+            uri: uri.to_string(),
+            name: name.to_string(),
+            mime_type: Some(mime_type.to_string()),
+            size: Some(size as u64),
+            ..Default::default()
+        };
+        let handler = move |_context: Arc<C>| {
+            let content = content.clone();
+            async move { Ok(content) }
+        };
+        self.register_resource_provider(Box::new(SingleResource { info, handler }))
+    }
+}
+
+/// A single resource backed by an async closure
+struct SingleResource<F> {
+    info: ResourceInfo,
+    handler: F,
+}
+
+#[async_trait]
+impl<C, F, Fut, T> ResourceProvider<C> for SingleResource<F>
+where
+    C: Send + Sync + 'static,
+    F: Fn(Arc<C>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<T>> + Send + 'static,
+    T: Into<ResourceData> + 'static,
+{
+    async fn list_resources(&self, _context: Arc<C>) -> Result<Vec<ResourceInfo>> {
+        Ok(vec![self.info.clone()])
+    }
+
+    fn routes(&self) -> Vec<ResourceRoute> {
+        vec![ResourceRoute::exact(&self.info.uri)]
+    }
+
+    async fn read_resource(&self, uri: &str, context: Arc<C>) -> Result<ResourceContent> {
+        if uri != self.info.uri {
+            return Err(anyhow::anyhow!("Resource not found: {}", uri));
+        }
+        Ok(ResourceContent {
+            uri: uri.to_string(),
+            mime_type: self.info.mime_type.clone(),
+            content: (self.handler)(context).await?.into(),
+        })
+    }
+}
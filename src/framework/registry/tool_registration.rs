@@ -87,7 +87,7 @@ impl<C: Send + Sync + 'static> ToolRegistry<C> {
             })
         });
 
-        self.tools.insert(name.to_string(), (tool_def, wrapper));
+        self.tools.insert(name.to_string(), (tool_def, Arc::new(wrapper)));
     }
 
     /// Register a tool with both input and output schema types.
@@ -162,7 +162,7 @@ impl<C: Send + Sync + 'static> ToolRegistry<C> {
             })
         });
 
-        self.tools.insert(name.to_string(), (tool_def, wrapper));
+        self.tools.insert(name.to_string(), (tool_def, Arc::new(wrapper)));
     }
 
     /// Register a tool with enforced typed output.
@@ -276,6 +276,6 @@ impl<C: Send + Sync + 'static> ToolRegistry<C> {
             })
         });
 
-        self.tools.insert(name.to_string(), (tool_def, wrapper));
+        self.tools.insert(name.to_string(), (tool_def, Arc::new(wrapper)));
     }
 }
\ No newline at end of file
@@ -0,0 +1,232 @@
+//! Ownership-based routing for resources and prompts.
+//!
+//! Providers declare the URIs and prompt names they own, and the registry keeps
+//! an index of those declarations so reads and prompt requests are dispatched
+//! straight to the owning provider. Providers that declare nothing are still
+//! tried in registration order, as before.
+
+use std::collections::HashMap;
+
+/// A set of resource URIs owned by a provider.
+///
+/// When several routes match a URI, the most specific one wins: exact URIs,
+/// then templates, then the longest prefix, then schemes.
+///
+/// # Examples
+/// ```rust
+/// use solidmcp::framework::ResourceRoute;
+///
+/// assert!(ResourceRoute::scheme("note").matches("note://shopping"));
+/// assert!(ResourceRoute::prefix("file:///srv/").matches("file:///srv/readme.md"));
+/// assert!(ResourceRoute::template("db://{table}/{id}").matches("db://users/42"));
+/// assert!(!ResourceRoute::template("db://{table}/{id}").matches("db://users"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceRoute {
+    /// A single URI
+    Exact(String),
+    /// Every URI of a scheme, e.g. `note` for `note://...`
+    Scheme(String),
+    /// Every URI starting with a prefix
+    Prefix(String),
+    /// URIs matching a template whose `{name}` placeholders match one path segment
+    Template(String),
+}
+
+impl ResourceRoute {
+    /// Own a single URI.
+    pub fn exact(uri: impl Into<String>) -> Self {
+        Self::Exact(uri.into())
+    }
+
+    /// Own every URI of a scheme (given without `:` or `://`).
+    pub fn scheme(scheme: impl Into<String>) -> Self {
+        Self::Scheme(scheme.into())
+    }
+
+    /// Own every URI starting with `prefix`.
+    pub fn prefix(prefix: impl Into<String>) -> Self {
+        Self::Prefix(prefix.into())
+    }
+
+    /// Own every URI matching a template such as `note://{id}`.
+    pub fn template(template: impl Into<String>) -> Self {
+        Self::Template(template.into())
+    }
+
+    /// Check whether this route covers `uri`.
+    pub fn matches(&self, uri: &str) -> bool {
+        match self {
+            Self::Exact(exact) => uri == exact,
+            Self::Scheme(scheme) => uri
+                .strip_prefix(scheme.as_str())
+                .is_some_and(|rest| rest.starts_with(':')),
+            Self::Prefix(prefix) => uri.starts_with(prefix.as_str()),
+            Self::Template(template) => template_matches(template, uri),
+        }
+    }
+
+    /// Key used to detect two providers claiming the same route.
+    ///
+    /// Template placeholder names are dropped, so `note://{id}` and
+    /// `note://{name}` are the same route.
+    fn conflict_key(&self) -> String {
+        match self {
+            Self::Exact(uri) => format!("exact:{}", uri),
+            Self::Scheme(scheme) => format!("scheme:{}", scheme),
+            Self::Prefix(prefix) => format!("prefix:{}", prefix),
+            Self::Template(template) => {
+                let mut key = String::from("template:");
+                let mut rest = template.as_str();
+                while let Some(start) = rest.find('{') {
+                    key.push_str(&rest[..start]);
+                    key.push_str("{}");
+                    rest = rest[start..]
+                        .find('}')
+                        .map_or("", |end| &rest[start + end + 1..]);
+                }
+                key.push_str(rest);
+                key
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for ResourceRoute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exact(uri) => write!(f, "{}", uri),
+            Self::Scheme(scheme) => write!(f, "{}:*", scheme),
+            Self::Prefix(prefix) => write!(f, "{}*", prefix),
+            Self::Template(template) => write!(f, "{}", template),
+        }
+    }
+}
+
+/// Match a URI against a template, where each `{name}` matches a non-empty
+/// run of characters other than `/`.
+fn template_matches(template: &str, uri: &str) -> bool {
+    match template.find('{') {
+        None => template == uri,
+        Some(start) => {
+            let Some(rest) = uri.strip_prefix(&template[..start]) else {
+                return false;
+            };
+            let Some(end) = template[start..].find('}') else {
+                return false;
+            };
+            let after = &template[start + end + 1..];
+            let segment = &rest[..rest.find('/').unwrap_or(rest.len())];
+            segment
+                .char_indices()
+                .skip(1)
+                .map(|(i, _)| i)
+                .chain(std::iter::once(segment.len()))
+                .filter(|&len| len > 0)
+                .any(|len| template_matches(after, &rest[len..]))
+        }
+    }
+}
+
+/// Index from resource routes to provider positions in the registry.
+#[derive(Default)]
+pub(crate) struct ResourceRouter {
+    exact: HashMap<String, usize>,
+    templates: Vec<(ResourceRoute, usize)>,
+    prefixes: Vec<(String, usize)>,
+    schemes: HashMap<String, usize>,
+    claimed: HashMap<String, usize>,
+    pub(crate) unrouted: Vec<usize>,
+    pub(crate) conflicts: Vec<String>,
+}
+
+impl ResourceRouter {
+    /// Record the routes declared by the provider at `index`.
+    pub(crate) fn insert(&mut self, routes: Vec<ResourceRoute>, index: usize) {
+        if routes.is_empty() {
+            self.unrouted.push(index);
+            return;
+        }
+        for route in routes {
+            let key = route.conflict_key();
+            if let Some(&owner) = self.claimed.get(&key) {
+                if owner != index {
+                    self.conflicts.push(format!(
+                        "resource route '{}' is claimed by more than one provider",
+                        route
+                    ));
+                }
+                continue;
+            }
+            self.claimed.insert(key, index);
+            match route {
+                ResourceRoute::Exact(uri) => {
+                    self.exact.insert(uri, index);
+                }
+                ResourceRoute::Scheme(scheme) => {
+                    self.schemes.insert(scheme, index);
+                }
+                ResourceRoute::Prefix(prefix) => {
+                    self.prefixes.push((prefix, index));
+                    self.prefixes
+                        .sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+                }
+                template @ ResourceRoute::Template(_) => self.templates.push((template, index)),
+            }
+        }
+    }
+
+    /// Find the provider that owns `uri`, if any declared it.
+    pub(crate) fn route(&self, uri: &str) -> Option<usize> {
+        if let Some(&index) = self.exact.get(uri) {
+            return Some(index);
+        }
+        if let Some((_, index)) = self.templates.iter().find(|(t, _)| t.matches(uri)) {
+            return Some(*index);
+        }
+        if let Some((_, index)) = self
+            .prefixes
+            .iter()
+            .find(|(p, _)| uri.starts_with(p.as_str()))
+        {
+            return Some(*index);
+        }
+        let scheme = uri.split_once(':')?.0;
+        self.schemes.get(scheme).copied()
+    }
+}
+
+/// Index from prompt names to provider positions in the registry.
+#[derive(Default)]
+pub(crate) struct PromptRouter {
+    names: HashMap<String, usize>,
+    pub(crate) unrouted: Vec<usize>,
+    pub(crate) conflicts: Vec<String>,
+}
+
+impl PromptRouter {
+    /// Record the prompt names declared by the provider at `index`.
+    pub(crate) fn insert(&mut self, names: Vec<String>, index: usize) {
+        if names.is_empty() {
+            self.unrouted.push(index);
+            return;
+        }
+        for name in names {
+            match self.names.get(&name) {
+                Some(&owner) if owner != index => self.conflicts.push(format!(
+                    "prompt '{}' is claimed by more than one provider",
+                    name
+                )),
+                Some(_) => {}
+                None => {
+                    self.names.insert(name, index);
+                }
+            }
+        }
+    }
+
+    /// Find the provider that owns the prompt `name`, if any declared it.
+    pub(crate) fn route(&self, name: &str) -> Option<usize> {
+        self.names.get(name).copied()
+    }
+}
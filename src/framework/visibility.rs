@@ -0,0 +1,142 @@
+//! Per-session tool visibility.
+//!
+//! This module provides the `ToolFilter` predicate type and the `ToolVisibility`
+//! handle, which together decide which tools each session can list and call.
+
+use crate::handler::{McpContext, McpNotification, SessionNotifier, ToolDefinition};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+/// Predicate deciding whether a tool is visible to the session making a request.
+///
+/// The filter receives the tool definition and the `McpContext` of the request,
+/// so it can look at the client info, connection headers or session ID. It is
+/// consulted for both `tools/list` and `tools/call`.
+pub type ToolFilter = Arc<dyn Fn(&ToolDefinition, &McpContext) -> bool + Send + Sync>;
+
+/// Cloneable handle for showing or hiding tools in individual sessions.
+///
+/// Per-session overrides take precedence over the handler's `ToolFilter`. A
+/// hidden tool is left out of `tools/list` and rejected by `tools/call` as if it
+/// did not exist. Every change that affects a session sends
+/// `notifications/tools/list_changed` to that session only.
+///
+/// Obtain it from `McpServerBuilder::tool_visibility` (or
+/// `FrameworkHandler::tool_visibility`); tools can keep a clone in their context
+/// and use `notif.session_id()` to change the visibility of their own session.
+///
+/// # Examples
+/// ```rust
+/// let builder = McpServerBuilder::new(context, "staged-server", "1.0.0");
+/// let visibility = builder.tool_visibility();
+///
+/// let server = builder
+///     .with_tool("unlock", "Unlock admin tools", move |_input: Empty, _ctx, notif| {
+///         let visibility = visibility.clone();
+///         async move {
+///             if let Some(session_id) = notif.session_id() {
+///                 visibility.show_tool(session_id, "purge_cache");
+///             }
+///             Ok(Empty {})
+///         }
+///     })
+///     .build()
+///     .await?;
+/// ```
+#[derive(Clone)]
+pub struct ToolVisibility {
+    overrides: Arc<Mutex<HashMap<String, HashMap<String, bool>>>>,
+    notifier: SessionNotifier,
+}
+
+impl ToolVisibility {
+    pub(crate) fn new(notifier: SessionNotifier) -> Self {
+        Self {
+            overrides: Arc::new(Mutex::new(HashMap::new())),
+            notifier,
+        }
+    }
+
+    /// Make a tool visible in one session, regardless of the tool filter.
+    ///
+    /// # Returns
+    /// `true` if the session's override for the tool changed
+    pub fn show_tool(&self, session_id: &str, name: &str) -> bool {
+        self.set(session_id, name, true)
+    }
+
+    /// Hide a tool from one session, regardless of the tool filter.
+    ///
+    /// # Returns
+    /// `true` if the session's override for the tool changed
+    pub fn hide_tool(&self, session_id: &str, name: &str) -> bool {
+        self.set(session_id, name, false)
+    }
+
+    /// Drop a session's override for one tool, so the tool filter decides again.
+    ///
+    /// # Returns
+    /// `true` if the session had an override for the tool
+    pub fn reset_tool(&self, session_id: &str, name: &str) -> bool {
+        let removed = {
+            let mut overrides = self.lock();
+            let removed = overrides
+                .get_mut(session_id)
+                .is_some_and(|tools| tools.remove(name).is_some());
+            if overrides.get(session_id).is_some_and(HashMap::is_empty) {
+                overrides.remove(session_id);
+            }
+            removed
+        };
+        if removed {
+            self.notifier
+                .notify(session_id, McpNotification::ToolsListChanged);
+        }
+        removed
+    }
+
+    /// Drop every override of a session.
+    ///
+    /// # Returns
+    /// `true` if the session had any overrides
+    pub fn reset_session(&self, session_id: &str) -> bool {
+        let removed = self.lock().remove(session_id).is_some();
+        if removed {
+            self.notifier
+                .notify(session_id, McpNotification::ToolsListChanged);
+        }
+        removed
+    }
+
+    /// Get the override for a tool in a session, if one was set.
+    ///
+    /// # Returns
+    /// `Some(true)` if shown, `Some(false)` if hidden, `None` if the filter decides
+    pub fn session_override(&self, session_id: &str, name: &str) -> Option<bool> {
+        self.lock()
+            .get(session_id)
+            .and_then(|tools| tools.get(name).copied())
+    }
+
+    fn set(&self, session_id: &str, name: &str, visible: bool) -> bool {
+        let previous = self
+            .lock()
+            .entry(session_id.to_string())
+            .or_default()
+            .insert(name.to_string(), visible);
+        let changed = previous != Some(visible);
+        if changed {
+            self.notifier
+                .notify(session_id, McpNotification::ToolsListChanged);
+        }
+        changed
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, HashMap<String, bool>>> {
+        self.overrides
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
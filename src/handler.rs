@@ -68,15 +68,35 @@
 //! ```
 
 use {
-    anyhow::Result, async_trait::async_trait, schemars::JsonSchema, serde_json::Value,
+    crate::content_types::{Annotations, EmbeddedResource, McpContent, Role},
+    crate::admin::AdminApi,
+    crate::auth::{Authenticator, Principal},
+    crate::health::{CheckStatus, Probe},
+    crate::middleware::Middleware,
+    crate::oauth::ProtectedResourceMetadata,
+    crate::session_store::{SessionData, SessionEndReason, SessionExtensions, SessionStore},
+    crate::tls::ClientCertificate,
+    crate::trace_context::TraceContext,
+    anyhow::Result,
+    async_trait::async_trait,
+    base64::{engine::general_purpose::STANDARD as BASE64, Engine as _},
+    schemars::JsonSchema,
+    serde_json::Value,
+    std::{
+        collections::HashMap,
+        net::SocketAddr,
+        sync::{Arc, Mutex, MutexGuard, PoisonError},
+    },
     tokio::sync::mpsc,
+    tracing::Span,
+    warp::http::HeaderMap,
 };
 
 /// Context provided to MCP handler methods.
 ///
 /// This struct contains session-specific information and capabilities that are
-/// available to handler methods. It includes the session ID, notification sender,
-/// protocol version, and client information.
+/// available to handler methods. It is rebuilt for every incoming message from the
+/// state stored at `initialize` plus the details of the current request.
 ///
 /// # Fields
 ///
@@ -84,6 +104,10 @@ use {
 /// - `notification_sender`: Channel for sending notifications back to the client
 /// - `protocol_version`: The MCP protocol version negotiated during initialization
 /// - `client_info`: Client-provided information from the initialization request
+/// - `client_capabilities`: Capabilities the client declared during initialization
+/// - `request_id`: JSON-RPC `id` of the message being handled (`None` for notifications)
+/// - `meta`: The raw `params._meta` object of the current request, if any
+/// - `connection`: Transport-level details such as HTTP headers and the remote address
 ///
 /// # Example Usage
 ///
@@ -109,7 +133,7 @@ use {
 ///     Ok(json!({ "status": "completed" }))
 /// }
 /// ```
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct McpContext {
     /// Session ID for this client connection
     pub session_id: Option<String>,
@@ -119,6 +143,75 @@ pub struct McpContext {
     pub protocol_version: Option<String>,
     /// Client information from initialization
     pub client_info: Option<Value>,
+    /// Client capabilities from initialization
+    pub client_capabilities: Option<Value>,
+    /// JSON-RPC request ID of the current message
+    pub request_id: Option<Value>,
+    /// Raw `_meta` object from the current request params
+    pub meta: Option<Value>,
+    /// Transport-level information about the connection that sent this request
+    pub connection: Option<ConnectionInfo>,
+    /// Identity the request was authenticated as, if the server requires authentication
+    pub principal: Option<Principal>,
+    /// Typed values kept with the session that sent this request
+    pub session: SessionExtensions,
+    /// JSON data kept with the session that sent this request, persisted by durable stores
+    pub data: SessionData,
+    /// W3C trace context of this request, continuing the caller's trace if it sent a `traceparent`
+    pub trace: Option<TraceContext>,
+    /// Tracing span the request is handled in
+    pub span: Option<Span>,
+}
+
+impl McpContext {
+    /// Get the progress token from the request `_meta`, if the client sent one.
+    pub fn progress_token(&self) -> Option<&Value> {
+        self.meta.as_ref().and_then(|m| m.get("progressToken"))
+    }
+
+    /// Get the certificate the client presented over mutual TLS, if any.
+    pub fn client_certificate(&self) -> Option<&ClientCertificate> {
+        self.connection.as_ref()?.client_certificate.as_ref()
+    }
+}
+
+/// Transport-level information about the connection a request arrived on.
+///
+/// For HTTP this reflects the current POST request; for WebSocket it reflects
+/// the upgrade request, since individual frames carry no headers.
+///
+/// # Fields
+///
+/// - `remote_addr`: The peer address, if known
+/// - `headers`: HTTP request headers (upgrade headers for WebSocket)
+/// - `principal`: Identity established by the server's `Authenticator`, if any
+/// - `client_certificate`: Verified client certificate, for mutual TLS
+///
+/// # Example
+///
+/// ```rust
+/// use solidmcp::handler::McpContext;
+///
+/// fn user_agent(context: &McpContext) -> Option<&str> {
+///     context
+///         .connection
+///         .as_ref()?
+///         .headers
+///         .get("user-agent")?
+///         .to_str()
+///         .ok()
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionInfo {
+    /// Remote socket address of the client
+    pub remote_addr: Option<SocketAddr>,
+    /// HTTP headers sent by the client
+    pub headers: HeaderMap,
+    /// Identity the connection was authenticated as
+    pub principal: Option<Principal>,
+    /// Certificate the client presented during the TLS handshake
+    pub client_certificate: Option<ClientCertificate>,
 }
 
 /// Notification types that can be sent from server to client.
@@ -190,6 +283,161 @@ pub enum McpNotification {
     },
 }
 
+impl McpNotification {
+    /// Convert the notification into the JSON-RPC message sent to clients.
+    ///
+    /// # Returns
+    ///
+    /// A JSON-RPC notification (no `id`) with the MCP method name and params
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use solidmcp::handler::McpNotification;
+    ///
+    /// let message = McpNotification::ToolsListChanged.to_json_rpc();
+    /// assert_eq!(message["method"], "notifications/tools/list_changed");
+    /// ```
+    pub fn to_json_rpc(&self) -> Value {
+        let (method, params) = match self {
+            Self::ToolsListChanged => ("notifications/tools/list_changed", None),
+            Self::ResourcesListChanged => ("notifications/resources/list_changed", None),
+            Self::PromptsListChanged => ("notifications/prompts/list_changed", None),
+            Self::Progress {
+                progress_token,
+                progress,
+                total,
+            } => {
+                let mut params = serde_json::json!({
+                    "progressToken": progress_token,
+                    "progress": progress,
+                });
+                if let Some(total) = total {
+                    params["total"] = serde_json::json!(total);
+                }
+                ("notifications/progress", Some(params))
+            }
+            Self::LogMessage {
+                level,
+                logger,
+                message,
+                data,
+            } => {
+                let level = match level {
+                    LogLevel::Debug => "debug",
+                    LogLevel::Info => "info",
+                    LogLevel::Warning => "warning",
+                    LogLevel::Error => "error",
+                };
+                let mut params = serde_json::json!({
+                    "level": level,
+                    "data": data.clone().unwrap_or_else(|| Value::String(message.clone())),
+                });
+                if let Some(logger) = logger {
+                    params["logger"] = serde_json::json!(logger);
+                }
+                ("notifications/message", Some(params))
+            }
+            Self::Custom { method, params } => (method.as_str(), params.clone()),
+        };
+
+        let mut message = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": method,
+        });
+        if let Some(params) = params {
+            message["params"] = params;
+        }
+        message
+    }
+}
+
+/// Delivers server-initiated notifications to connected sessions.
+///
+/// Transports that can push messages to the client (WebSocket) subscribe a
+/// session when the connection opens and unsubscribe it when it closes.
+/// Handlers use the notifier to reach one session or every subscribed
+/// session, for example to announce `notifications/tools/list_changed`.
+/// Sessions without a push channel (plain HTTP) are not subscribed and simply
+/// re-list on their next request.
+///
+/// Cloning is cheap; all clones share the same set of sessions.
+///
+/// # Example
+///
+/// ```rust
+/// use solidmcp::handler::{McpNotification, SessionNotifier};
+///
+/// let notifier = SessionNotifier::default();
+/// let mut receiver = notifier.subscribe("ws-1");
+///
+/// assert_eq!(notifier.broadcast(McpNotification::ToolsListChanged), 1);
+/// assert!(matches!(receiver.try_recv(), Ok(McpNotification::ToolsListChanged)));
+/// ```
+#[derive(Clone, Default)]
+pub struct SessionNotifier {
+    sessions: Arc<Mutex<HashMap<String, mpsc::UnboundedSender<McpNotification>>>>,
+}
+
+impl SessionNotifier {
+    /// Start delivering notifications to a session.
+    ///
+    /// Subscribing a session again replaces its previous channel.
+    ///
+    /// # Returns
+    ///
+    /// The receiving end, which the transport forwards to the client
+    pub fn subscribe(&self, session_id: &str) -> mpsc::UnboundedReceiver<McpNotification> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.lock().insert(session_id.to_string(), sender);
+        receiver
+    }
+
+    /// Stop delivering notifications to a session.
+    pub fn unsubscribe(&self, session_id: &str) {
+        self.lock().remove(session_id);
+    }
+
+    /// Send a notification to one session.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the session is subscribed and its channel is open
+    pub fn notify(&self, session_id: &str, notification: McpNotification) -> bool {
+        let mut sessions = self.lock();
+        match sessions.get(session_id) {
+            Some(sender) if sender.send(notification).is_ok() => true,
+            Some(_) => {
+                sessions.remove(session_id);
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Send a notification to every subscribed session.
+    ///
+    /// Sessions whose channel has closed are dropped.
+    ///
+    /// # Returns
+    ///
+    /// The number of sessions the notification was delivered to
+    pub fn broadcast(&self, notification: McpNotification) -> usize {
+        let mut sessions = self.lock();
+        sessions.retain(|_, sender| sender.send(notification.clone()).is_ok());
+        sessions.len()
+    }
+
+    /// IDs of the sessions currently subscribed.
+    pub fn sessions(&self) -> Vec<String> {
+        self.lock().keys().cloned().collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, mpsc::UnboundedSender<McpNotification>>> {
+        self.sessions.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
 /// Log levels for log message notifications.
 ///
 /// These levels follow standard logging conventions and help clients
@@ -542,8 +790,11 @@ impl<T: JsonSchema> TypedToolDefinition<T> {
 ///
 /// - `uri`: Unique identifier for the resource (e.g., "file:///path/to/file")
 /// - `name`: Human-readable name for the resource
+/// - `title`: Optional display title, preferred over `name` by clients that show it
 /// - `description`: Optional description of the resource's contents
 /// - `mime_type`: Optional MIME type hint (e.g., "text/plain", "application/json")
+/// - `size`: Optional size of the raw contents in bytes
+/// - `annotations`: Optional audience, priority and last-modified hints
 ///
 /// # Example
 ///
@@ -555,44 +806,119 @@ impl<T: JsonSchema> TypedToolDefinition<T> {
 ///     name: "User Database".to_string(),
 ///     description: Some("JSON file containing user records".to_string()),
 ///     mime_type: Some("application/json".to_string()),
+///     size: Some(2048),
+///     ..Default::default()
 /// };
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ResourceInfo {
     pub uri: String,
     pub name: String,
+    pub title: Option<String>,
     pub description: Option<String>,
     pub mime_type: Option<String>,
+    pub size: Option<u64>,
+    pub annotations: Option<Annotations>,
 }
 
 /// Resource content for MCP resources/read response.
 ///
-/// This struct contains the actual content of a resource when read through
+/// This struct contains one content entry of a resource when read through
 /// the MCP protocol. It includes the resource URI, optional MIME type,
-/// and the content as a string.
+/// and the content as text or binary data. A single read may return several
+/// entries, see [`McpHandler::read_resource_contents`].
 ///
 /// # Fields
 ///
 /// - `uri`: The URI of the resource being returned
 /// - `mime_type`: Optional MIME type of the content
-/// - `content`: The actual content of the resource as a string
+/// - `content`: The actual content of the resource, text or binary
 ///
 /// # Example
 ///
 /// ```rust
-/// use solidmcp::handler::ResourceContent;
+/// use solidmcp::handler::{ResourceContent, ResourceData};
 ///
 /// let content = ResourceContent {
 ///     uri: "db://users/123".to_string(),
 ///     mime_type: Some("application/json".to_string()),
-///     content: r#"{"id": 123, "name": "Alice", "email": "alice@example.com"}"#.to_string(),
+///     content: r#"{"id": 123, "name": "Alice", "email": "alice@example.com"}"#.into(),
 /// };
+///
+/// let logo = ResourceContent::blob("file:///logo.png", Some("image/png".to_string()), vec![0x89, b'P', b'N', b'G']);
+/// assert!(matches!(logo.content, ResourceData::Blob(_)));
 /// ```
 #[derive(Debug, Clone)]
 pub struct ResourceContent {
     pub uri: String,
     pub mime_type: Option<String>,
-    pub content: String,
+    pub content: ResourceData,
+}
+
+impl ResourceContent {
+    /// Create a text content entry
+    pub fn text(uri: impl Into<String>, mime_type: Option<String>, text: impl Into<String>) -> Self {
+        Self {
+            uri: uri.into(),
+            mime_type,
+            content: ResourceData::Text(text.into()),
+        }
+    }
+
+    /// Create a binary content entry, sent to the client base64 encoded
+    pub fn blob(uri: impl Into<String>, mime_type: Option<String>, bytes: impl Into<Vec<u8>>) -> Self {
+        Self {
+            uri: uri.into(),
+            mime_type,
+            content: ResourceData::Blob(bytes.into()),
+        }
+    }
+}
+
+/// The body of a resource content entry.
+///
+/// Text is sent to the client as `text`; binary data (PDFs, images, archives)
+/// is sent base64 encoded as `blob`. Strings convert into `Text` and byte
+/// vectors into `Blob`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResourceData {
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+impl From<String> for ResourceData {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for ResourceData {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl From<Vec<u8>> for ResourceData {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::Blob(bytes)
+    }
+}
+
+impl From<ResourceContent> for EmbeddedResource {
+    fn from(content: ResourceContent) -> Self {
+        match content.content {
+            ResourceData::Text(text) => EmbeddedResource::Text {
+                uri: content.uri,
+                mime_type: content.mime_type,
+                text,
+            },
+            ResourceData::Blob(bytes) => EmbeddedResource::Blob {
+                uri: content.uri,
+                mime_type: content.mime_type,
+                blob: BASE64.encode(bytes),
+            },
+        }
+    }
 }
 
 /// Prompt information for MCP prompts/list response.
@@ -636,6 +962,77 @@ pub struct PromptInfo {
     pub arguments: Vec<PromptArgument>,
 }
 
+impl PromptInfo {
+    /// Create prompt information with arguments derived from a JsonSchema type.
+    ///
+    /// Every property of `T` becomes a `PromptArgument`. The property's doc
+    /// comment becomes the argument description, and fields that are not
+    /// optional are marked as required.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `T`: Type that implements `schemars::JsonSchema`
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The unique name for this prompt
+    /// - `description`: Human-readable description of the prompt
+    ///
+    /// # Returns
+    ///
+    /// A new `PromptInfo` with arguments generated from type `T`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use solidmcp::handler::PromptInfo;
+    /// use schemars::JsonSchema;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(JsonSchema, Deserialize)]
+    /// struct ReviewArgs {
+    ///     /// The code to review
+    ///     code: String,
+    ///     /// Programming language of the code
+    ///     language: Option<String>,
+    /// }
+    ///
+    /// let prompt = PromptInfo::from_schema::<ReviewArgs>("code_review", "Review a piece of code");
+    /// assert_eq!(prompt.arguments.len(), 2);
+    /// assert!(prompt.arguments.iter().any(|a| a.name == "code" && a.required));
+    /// ```
+    pub fn from_schema<T: JsonSchema>(
+        name: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        let schema = serde_json::to_value(schemars::schema_for!(T)).unwrap_or_default();
+        let required: Vec<&str> = schema["required"]
+            .as_array()
+            .map(|names| names.iter().filter_map(|n| n.as_str()).collect())
+            .unwrap_or_default();
+
+        let arguments = schema["properties"]
+            .as_object()
+            .map(|properties| {
+                properties
+                    .iter()
+                    .map(|(arg_name, property)| PromptArgument {
+                        name: arg_name.clone(),
+                        description: property["description"].as_str().map(|d| d.to_string()),
+                        required: required.contains(&arg_name.as_str()),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            name: name.into(),
+            description: Some(description.into()),
+            arguments,
+        }
+    }
+}
+
 /// Prompt argument definition.
 ///
 /// Represents a single argument that can be passed to a prompt template.
@@ -673,6 +1070,7 @@ pub struct PromptArgument {
 ///
 /// # Fields
 ///
+/// - `description`: Optional description of the generated prompt
 /// - `messages`: List of messages forming the prompt conversation
 ///
 /// # Example
@@ -681,52 +1079,68 @@ pub struct PromptArgument {
 /// use solidmcp::handler::{PromptContent, PromptMessage};
 ///
 /// let content = PromptContent {
+///     description: Some("Code review prompt".to_string()),
 ///     messages: vec![
-///         PromptMessage {
-///             role: "system".to_string(),
-///             content: "You are a helpful code reviewer.".to_string(),
-///         },
-///         PromptMessage {
-///             role: "user".to_string(),
-///             content: "Please review this Python function for bugs.".to_string(),
-///         },
+///         PromptMessage::user("You are a helpful code reviewer."),
+///         PromptMessage::user("Please review this Python function for bugs."),
 ///     ],
 /// };
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct PromptContent {
+    pub description: Option<String>,
     pub messages: Vec<PromptMessage>,
 }
 
 /// Prompt message.
 ///
 /// Represents a single message in a prompt conversation. Each message has
-/// a role (typically "system", "user", or "assistant") and content.
+/// a role (user or assistant) and a content item, which can be text, an
+/// image, audio, a resource link or an embedded resource.
 ///
 /// # Fields
 ///
-/// - `role`: The role of the message sender (e.g., "system", "user", "assistant")
-/// - `content`: The actual message content
+/// - `role`: The role of the message sender
+/// - `content`: The message content, using the same content types as tool results
 ///
 /// # Example
 ///
 /// ```rust
-/// use solidmcp::handler::PromptMessage;
+/// use solidmcp::content_types::{McpContent, Role};
+/// use solidmcp::handler::{PromptMessage, ResourceContent};
 ///
-/// let system_message = PromptMessage {
-///     role: "system".to_string(),
-///     content: "You are an expert Rust programmer.".to_string(),
+/// let question = PromptMessage {
+///     role: Role::User,
+///     content: "How do I implement error handling in Rust?".into(),
 /// };
 ///
-/// let user_message = PromptMessage {
-///     role: "user".to_string(),
-///     content: "How do I implement error handling in Rust?".to_string(),
-/// };
+/// let screenshot = PromptMessage::user(McpContent::image_from_bytes(b"\x89PNG\r\n\x1a\n"));
+///
+/// let file = ResourceContent::text("file:///src/main.rs", None, "fn main() {}");
+/// let embedded = PromptMessage::user(McpContent::embedded(file.into()));
 /// ```
 #[derive(Debug, Clone)]
 pub struct PromptMessage {
-    pub role: String,
-    pub content: String,
+    pub role: Role,
+    pub content: McpContent,
+}
+
+impl PromptMessage {
+    /// Create a message from the user
+    pub fn user(content: impl Into<McpContent>) -> Self {
+        Self {
+            role: Role::User,
+            content: content.into(),
+        }
+    }
+
+    /// Create a message from the assistant
+    pub fn assistant(content: impl Into<McpContent>) -> Self {
+        Self {
+            role: Role::Assistant,
+            content: content.into(),
+        }
+    }
 }
 
 /// Core trait that users must implement to provide MCP functionality.
@@ -746,10 +1160,12 @@ pub struct PromptMessage {
 /// - `initialize`: Customize server capabilities and info
 /// - `list_resources`: Provide available resources
 /// - `read_resource`: Implement resource reading
+/// - `read_resource_contents`: Return several content entries for one resource
 /// - `list_prompts`: Provide available prompts
 /// - `get_prompt`: Generate prompt content
 /// - `cancel_notification`: Handle cancellation requests
 /// - `handle_initialized`: React to client initialization
+/// - `on_session_start` / `on_session_end`: Set up and clean up per-session resources
 ///
 /// # Example Implementation
 ///
@@ -896,6 +1312,28 @@ pub trait McpHandler: Send + Sync {
         Err(anyhow::anyhow!("Resource not found: {}", uri))
     }
 
+    /// Read all content entries of a resource.
+    ///
+    /// Called by the engine for resources/read. The default implementation
+    /// returns the single entry from `read_resource`; override this to
+    /// return several entries for one URI, such as the files of a directory.
+    ///
+    /// # Parameters
+    ///
+    /// - `uri`: The URI of the resource to read
+    /// - `context`: The MCP context for this session
+    ///
+    /// # Returns
+    ///
+    /// The resource's content entries
+    async fn read_resource_contents(
+        &self,
+        uri: &str,
+        context: &McpContext,
+    ) -> Result<Vec<ResourceContent>> {
+        Ok(vec![self.read_resource(uri, context).await?])
+    }
+
     /// List available prompts.
     ///
     /// Called when a client sends a prompts/list request. Override this
@@ -987,4 +1425,189 @@ pub trait McpHandler: Send + Sync {
         // Default implementation - do nothing
         Ok(())
     }
+
+    /// Called when a session starts.
+    ///
+    /// A session starts when `initialize` succeeds, on every transport.
+    /// Returning an error fails the `initialize` request and leaves the session
+    /// uninitialized, so this is the place to allocate per-client resources.
+    ///
+    /// # Parameters
+    ///
+    /// - `context`: The MCP context of the `initialize` request
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) to accept the session
+    ///
+    /// # Default Implementation
+    ///
+    /// Does nothing and returns Ok(())
+    async fn on_session_start(&self, _context: &McpContext) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when a session ends.
+    ///
+    /// Fired when a WebSocket closes, an HTTP client deletes its session, the
+    /// session store expires or evicts the session, or the client initializes
+    /// again. Only sessions that started are reported, each exactly once.
+    /// Errors are logged.
+    ///
+    /// # Parameters
+    ///
+    /// - `session_id`: The session that ended
+    /// - `reason`: Why the session ended
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) on success
+    ///
+    /// # Default Implementation
+    ///
+    /// Does nothing and returns Ok(())
+    async fn on_session_end(&self, _session_id: &str, _reason: SessionEndReason) -> Result<()> {
+        Ok(())
+    }
+
+    /// Notifier the protocol engine should use to push notifications to sessions.
+    ///
+    /// The engine subscribes sessions that have a push channel (WebSocket
+    /// connections) to this notifier, so the handler can announce changes such
+    /// as `notifications/tools/list_changed` outside of a request.
+    ///
+    /// # Returns
+    ///
+    /// The handler's notifier, or `None` to let the engine use its own
+    ///
+    /// # Default Implementation
+    ///
+    /// Returns `None`
+    fn session_notifier(&self) -> Option<SessionNotifier> {
+        None
+    }
+
+    /// Middleware the protocol engine should wrap around every request.
+    ///
+    /// Layers run in order, the first being the outermost. They apply to every
+    /// method, including ones this trait has no dedicated function for.
+    ///
+    /// # Returns
+    ///
+    /// The middleware chain, outermost first
+    ///
+    /// # Default Implementation
+    ///
+    /// Returns an empty chain
+    fn middleware(&self) -> Vec<Arc<dyn Middleware>> {
+        Vec::new()
+    }
+
+    /// Authenticator the transports should apply to HTTP requests and WebSocket upgrades.
+    ///
+    /// Requests it rejects receive `401 Unauthorized` with a `WWW-Authenticate`
+    /// header and never reach the handler. The principal of accepted requests is
+    /// available as `McpContext::principal`.
+    ///
+    /// # Returns
+    ///
+    /// The authenticator, or `None` to accept every request
+    ///
+    /// # Default Implementation
+    ///
+    /// Returns `None`
+    fn authenticator(&self) -> Option<Arc<dyn Authenticator>> {
+        None
+    }
+
+    /// OAuth protected resource metadata the HTTP transport should publish.
+    ///
+    /// When present, it is served at `/.well-known/oauth-protected-resource`
+    /// so clients can discover the authorization server to get tokens from.
+    ///
+    /// # Returns
+    ///
+    /// The metadata, or `None` if the server is not an OAuth protected resource
+    ///
+    /// # Default Implementation
+    ///
+    /// Returns `None`
+    fn protected_resource_metadata(&self) -> Option<ProtectedResourceMetadata> {
+        None
+    }
+
+    /// Store the engine should keep the protocol state of each session in.
+    ///
+    /// # Returns
+    ///
+    /// The session store, or `None` to use the default `InMemorySessionStore`
+    ///
+    /// # Default Implementation
+    ///
+    /// Returns `None`
+    fn session_store(&self) -> Option<Arc<dyn SessionStore>> {
+        None
+    }
+
+    /// Path `McpServer` should serve Prometheus metrics at.
+    ///
+    /// # Returns
+    ///
+    /// The path, or `None` to use `/metrics`
+    ///
+    /// # Default Implementation
+    ///
+    /// Returns `None`
+    fn metrics_path(&self) -> Option<String> {
+        None
+    }
+
+    /// Admin API `McpServer` should serve, for inspecting sessions and in-flight requests.
+    ///
+    /// # Returns
+    ///
+    /// The admin API's configuration, or `None` to not serve it
+    ///
+    /// # Default Implementation
+    ///
+    /// Returns `None`
+    fn admin_api(&self) -> Option<AdminApi> {
+        None
+    }
+
+    /// Run the health checks of a probe.
+    ///
+    /// `McpServer` calls this for every request to `/health/live` and
+    /// `/health/ready`. Readiness should run the liveness checks too.
+    ///
+    /// # Parameters
+    ///
+    /// - `probe`: Which endpoint is asking
+    ///
+    /// # Returns
+    ///
+    /// The outcome of each check; see `CheckStatus::run`
+    ///
+    /// # Default Implementation
+    ///
+    /// Returns no checks
+    async fn check_health(&self, _probe: Probe) -> Vec<CheckStatus> {
+        Vec::new()
+    }
+
+    /// Prepare the server before it reports ready.
+    ///
+    /// `McpServer` calls this once it listens, and answers `/health/ready`
+    /// with `503` until it returns. A failed warm-up leaves the server unready.
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) once the server can take traffic
+    ///
+    /// # Default Implementation
+    ///
+    /// Does nothing and returns Ok(())
+    async fn warm_up(&self) -> Result<()> {
+        Ok(())
+    }
 }
@@ -0,0 +1,232 @@
+//! Health Checks
+//!
+//! `McpServer` serves three health endpoints:
+//!
+//! - `GET /health` answers `OK` while the process is up
+//! - `GET /health/live` runs the liveness checks
+//! - `GET /health/ready` runs every check, and fails while the server is
+//!   warming up or shutting down
+//!
+//! Both probes answer with a JSON [`HealthReport`] giving the status and
+//! latency of each check, with status `200 OK` when healthy and
+//! `503 Service Unavailable` otherwise. Checks come from
+//! `McpHandler::check_health`; with the framework, register them with
+//! `McpServerBuilder::with_health_check`.
+//!
+//! # Example
+//!
+//! ```rust
+//! let server = McpServerBuilder::new(AppContext::new(), "my-server", "1.0.0")
+//!     .with_health_check("database", Probe::Readiness, |ctx: Arc<AppContext>| async move {
+//!         ctx.pool.ping().await
+//!     })
+//!     .with_warm_up(|ctx: Arc<AppContext>| async move { ctx.cache.load().await })
+//!     .build()
+//!     .await?;
+//! ```
+//!
+//! ```text
+//! GET /health/ready
+//! 503 {"status":"fail","phase":"ready","checks":[{"name":"database","status":"fail","latency_ms":1.2,"error":"connection refused"}]}
+//! ```
+
+use {
+    crate::shared::McpProtocolEngine,
+    anyhow::Result,
+    serde::Serialize,
+    std::{
+        future::Future,
+        sync::{
+            atomic::{AtomicU8, Ordering},
+            Arc,
+        },
+        time::{Duration, Instant},
+    },
+    warp::{http::StatusCode, Filter, Rejection, Reply},
+};
+
+/// How long a check may run before it counts as failed.
+pub const DEFAULT_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Which endpoint a check belongs to.
+///
+/// Readiness runs the liveness checks too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Probe {
+    /// Whether the process works at all; failing means it should be restarted
+    Liveness,
+    /// Whether the server can take traffic; failing means it should get none
+    Readiness,
+}
+
+/// Where the server is in its lifetime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServerPhase {
+    /// Warming up; not ready yet
+    Starting,
+    /// Serving requests
+    Ready,
+    /// Draining before it stops; no longer ready
+    ShuttingDown,
+}
+
+/// Result of a check, or of a whole probe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    /// Healthy
+    Pass,
+    /// Failed, timed out, or not ready
+    Fail,
+}
+
+/// Shared handle to the phase of a server, which decides its readiness.
+#[derive(Debug, Clone)]
+pub struct Readiness {
+    phase: Arc<AtomicU8>,
+}
+
+impl Default for Readiness {
+    fn default() -> Self {
+        Self {
+            phase: Arc::new(AtomicU8::new(ServerPhase::Starting as u8)),
+        }
+    }
+}
+
+impl Readiness {
+    /// Get the current phase.
+    pub fn phase(&self) -> ServerPhase {
+        match self.phase.load(Ordering::SeqCst) {
+            0 => ServerPhase::Starting,
+            1 => ServerPhase::Ready,
+            _ => ServerPhase::ShuttingDown,
+        }
+    }
+
+    /// Move the server to `phase`.
+    pub fn set_phase(&self, phase: ServerPhase) {
+        self.phase.store(phase as u8, Ordering::SeqCst);
+    }
+
+    /// Mark the server ready, unless it is already shutting down.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the server was starting
+    pub fn mark_ready(&self) -> bool {
+        self.phase
+            .compare_exchange(
+                ServerPhase::Starting as u8,
+                ServerPhase::Ready as u8,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            )
+            .is_ok()
+    }
+}
+
+/// Outcome of one health check.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CheckStatus {
+    /// Name the check was registered under
+    pub name: String,
+    /// Whether the check passed
+    pub status: HealthStatus,
+    /// How long the check took, in milliseconds
+    pub latency_ms: f64,
+    /// Why the check failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl CheckStatus {
+    /// Run a check, failing it if it takes longer than `timeout`.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: Name of the check
+    /// - `check`: The check; an error fails it
+    /// - `timeout`: How long the check may run
+    pub async fn run(
+        name: impl Into<String>,
+        check: impl Future<Output = Result<()>>,
+        timeout: Duration,
+    ) -> Self {
+        let started = Instant::now();
+        let error = match tokio::time::timeout(timeout, check).await {
+            Ok(Ok(())) => None,
+            Ok(Err(e)) => Some(e.to_string()),
+            Err(_) => Some(format!("timed out after {}ms", timeout.as_millis())),
+        };
+        Self {
+            name: name.into(),
+            status: if error.is_none() {
+                HealthStatus::Pass
+            } else {
+                HealthStatus::Fail
+            },
+            latency_ms: started.elapsed().as_secs_f64() * 1000.0,
+            error,
+        }
+    }
+}
+
+/// Answer of a probe.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HealthReport {
+    /// `pass` if every check passed (and, for readiness, the server is ready)
+    pub status: HealthStatus,
+    /// Phase of the server when the probe ran
+    pub phase: ServerPhase,
+    /// Outcome of each check
+    pub checks: Vec<CheckStatus>,
+}
+
+impl HealthReport {
+    pub(crate) fn new(probe: Probe, phase: ServerPhase, checks: Vec<CheckStatus>) -> Self {
+        let passing = checks
+            .iter()
+            .all(|check| check.status == HealthStatus::Pass)
+            && (probe == Probe::Liveness || phase == ServerPhase::Ready);
+        Self {
+            status: if passing {
+                HealthStatus::Pass
+            } else {
+                HealthStatus::Fail
+            },
+            phase,
+            checks,
+        }
+    }
+}
+
+/// Create the filter serving `/health`, `/health/live` and `/health/ready`.
+pub(crate) fn routes(
+    engine: Arc<McpProtocolEngine>,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    let health = warp::path!("health")
+        .and(warp::get())
+        .map(|| warp::reply::with_status("OK", StatusCode::OK));
+    let probe = warp::path!("health" / String)
+        .and(warp::get())
+        .and_then(move |probe: String| {
+            let engine = engine.clone();
+            async move {
+                let probe = match probe.as_str() {
+                    "live" => Probe::Liveness,
+                    "ready" => Probe::Readiness,
+                    _ => return Err(warp::reject::not_found()),
+                };
+                let report = engine.check_health(probe).await;
+                let status = match report.status {
+                    HealthStatus::Pass => StatusCode::OK,
+                    HealthStatus::Fail => StatusCode::SERVICE_UNAVAILABLE,
+                };
+                Ok(warp::reply::with_status(warp::reply::json(&report), status))
+            }
+        });
+    health.or(probe)
+}
@@ -3,7 +3,11 @@
 //! HTTP transport for MCP protocol messages with intelligent transport negotiation.
 
 use {
+    super::auth::{self, AccessDenied},
+    super::handler::ConnectionInfo,
+    super::session_store::SessionEndReason,
     super::shared::McpProtocolEngine,
+    super::tls,
     super::transport::{
         cors_headers, transport_capabilities, TransportCapabilities, TransportInfo,
         TransportNegotiation,
@@ -77,7 +81,8 @@ impl HttpMcpHandler {
             .and(warp::post())
             .and(transport_capabilities())
             .and(warp::body::json())
-            .and(warp::header::optional::<String>("cookie"))
+            .and(requested_session_id())
+            .and(with_connection_info(self.protocol_engine.clone()))
             .and(with_handler(self.protocol_engine.clone()))
             .and_then(handle_mcp_enhanced_post);
 
@@ -87,16 +92,36 @@ impl HttpMcpHandler {
             .and(warp::header::optional::<String>("content-type"))
             .and(warp::header::optional::<String>("accept"))
             .and(warp::header::optional::<String>("connection"))
-            .and(warp::header::optional::<String>("cookie"))
+            .and(requested_session_id())
+            .and(with_connection_info(self.protocol_engine.clone()))
             .and(with_handler(self.protocol_engine.clone()))
             .and_then(handle_mcp_http);
 
-        // Try enhanced routes first, then SSE fallback, then legacy for backward compatibility
+        let delete_route = warp::path!("mcp")
+            .and(warp::delete())
+            .and(requested_session_id())
+            .and(with_connection_info(self.protocol_engine.clone()))
+            .and(with_handler(self.protocol_engine.clone()))
+            .and_then(handle_mcp_delete);
+
+        // RFC 9728 metadata, also under the path-suffixed form for the /mcp resource
+        let resource_metadata_route = warp::path!(".well-known" / "oauth-protected-resource")
+            .or(warp::path!(".well-known" / "oauth-protected-resource" / "mcp"))
+            .unify()
+            .and(warp::get())
+            .and(with_handler(self.protocol_engine.clone()))
+            .and_then(handle_protected_resource_metadata);
+
+        // Try enhanced routes first, then SSE fallback, then legacy for backward compatibility.
+        // Requests that fail authentication get a 401 with a WWW-Authenticate challenge.
         options_route
+            .or(resource_metadata_route)
             .or(get_route)
             .or(sse_route)
             .or(post_route)
             .or(legacy_route)
+            .or(delete_route)
+            .recover(auth::handle_rejection)
     }
 }
 
@@ -106,12 +131,105 @@ fn with_handler(
     warp::any().map(move || handler.clone())
 }
 
+/// The session a request names with the `Mcp-Session-Id` header or, for
+/// clients that keep cookies, the `mcp_session` cookie.
+fn requested_session_id() -> impl Filter<Extract = (Option<String>,), Error = Rejection> + Clone {
+    warp::header::optional::<String>("mcp-session-id")
+        .and(warp::header::optional::<String>("cookie"))
+        .map(|header: Option<String>, cookie: Option<String>| {
+            header.or_else(|| extract_session_id_from_cookie(&cookie))
+        })
+}
+
+/// Capture the request headers and peer address so handlers can see them in `McpContext`.
+///
+/// If the engine has an authenticator, the request is authenticated here and
+/// rejected with `Unauthorized` when that fails.
+pub(crate) fn with_connection_info(
+    engine: Arc<McpProtocolEngine>,
+) -> impl Filter<Extract = (ConnectionInfo,), Error = Rejection> + Clone {
+    tls::peer()
+        .and(warp::header::headers_cloned())
+        .and_then(move |remote_addr, client_certificate, headers| {
+            let engine = engine.clone();
+            async move {
+                let connection = ConnectionInfo {
+                    remote_addr,
+                    headers,
+                    principal: None,
+                    client_certificate,
+                };
+                match engine.authenticator() {
+                    Some(authenticator) => auth::authenticate(authenticator.as_ref(), connection).await,
+                    None => Ok(connection),
+                }
+            }
+        })
+}
+
+/// End the session named by the request's `Mcp-Session-Id` header or cookie.
+///
+/// Replies `204 No Content` and clears the cookie when the session existed,
+/// `403 Forbidden` when another principal initialized it, `404 Not Found`
+/// otherwise.
+async fn handle_mcp_delete(
+    session_id: Option<String>,
+    connection: ConnectionInfo,
+    handler: Arc<McpProtocolEngine>,
+) -> Result<warp::reply::Response, Rejection> {
+    let ended = match session_id {
+        Some(session_id) => handler
+            .end_owned_session(
+                &session_id,
+                connection.principal.as_ref(),
+                SessionEndReason::Deleted,
+            )
+            .await
+            .map(|ended| ended.then_some(session_id)),
+        None => Ok(None),
+    };
+    let status = match ended {
+        Ok(Some(session_id)) => {
+            debug!("🍪 Deleted session {}", session_id);
+            StatusCode::NO_CONTENT
+        }
+        Ok(None) => StatusCode::NOT_FOUND,
+        Err(denied) => {
+            let mut response = auth::forbidden(&Value::Null, &denied);
+            response.headers_mut().extend(cors_headers());
+            return Ok(response);
+        }
+    };
+    let mut response = reply::with_status(reply::reply(), status).into_response();
+    response.headers_mut().extend(cors_headers());
+    if status == StatusCode::NO_CONTENT {
+        response.headers_mut().insert(
+            "set-cookie",
+            HeaderValue::from_static("mcp_session=; Path=/mcp; HttpOnly; SameSite=Strict; Max-Age=0"),
+        );
+    }
+    Ok(response)
+}
+
+/// Serve the OAuth protected resource metadata, or 404 if the server has none.
+async fn handle_protected_resource_metadata(
+    handler: Arc<McpProtocolEngine>,
+) -> Result<impl Reply, Rejection> {
+    let metadata = handler
+        .protected_resource_metadata()
+        .ok_or_else(warp::reject::not_found)?;
+    let mut response = reply::json(metadata).into_response();
+    response.headers_mut().extend(cors_headers());
+    Ok(response)
+}
+
 async fn handle_mcp_http(
     message: Value,
     content_type: Option<String>,
     accept: Option<String>,
     connection: Option<String>,
-    cookie: Option<String>,
+    session_id: Option<String>,
+    connection_info: ConnectionInfo,
     handler: Arc<McpProtocolEngine>,
 ) -> Result<impl Reply, Rejection> {
     use std::time::Instant;
@@ -131,12 +249,11 @@ async fn handle_mcp_http(
     trace!("   Content-Type: {}", content_type);
     trace!("   Accept: {}", accept);
     trace!("   Connection: {}", connection);
-    trace!("   Cookie: {:?}", cookie);
+    trace!("   Session ID: {:?}", session_id);
 
     // Detect Cursor client from User-Agent patterns in headers
     let is_cursor_client = content_type.contains("Cursor")
-        || accept.contains("Cursor")
-        || cookie.as_ref().is_some_and(|c| c.contains("Cursor"));
+        || accept.contains("Cursor");
 
     if is_cursor_client {
         warn!("🎯 === CURSOR CLIENT DETECTED ===");
@@ -211,36 +328,25 @@ async fn handle_mcp_http(
     trace!("   📋 Content-Type: {}", content_type);
     trace!("   📋 Accept: {}", accept);
     trace!("   📋 Connection: {}", connection);
-    trace!("   📋 Cookie: {:?}", cookie);
-
-    // Extract session ID from cookie
-    let session_id = extract_session_id_from_cookie(&cookie);
+    trace!("   📋 Session ID: {:?}", session_id);
 
     // Extract method for session ID logic
     let method = message.get("method").and_then(|m| m.as_str()).unwrap_or("");
 
-    // For HTTP clients that don't handle cookies properly (like Claude), we need a fallback
-    // Use a consistent session ID for the duration of the server process
-    let effective_session_id = if method == "initialize" || session_id.is_none() {
-        // Always use a consistent session for initialize requests
-        // or when clients don't send cookies (like Claude)
-        if method != "initialize" && session_id.is_none() {
-            debug!(
-                "⚠️  No session cookie found for method '{}'. Using default HTTP session.",
-                method
-            );
-        }
-        Some("http_default_session".to_string())
-    } else {
-        session_id.clone()
-    };
+    let effective_session_id = effective_session_id(method, session_id.clone());
+    let principal = connection_info.principal.clone();
+    if effective_session_id.is_none() {
+        debug!(
+            "⚠️  No session ID found for method '{}'. Handling it statelessly.",
+            method
+        );
+    }
 
     trace!("🔍 SESSION DEBUG:");
     trace!(
         "   📋 Method: {}",
         message.get("method").and_then(|m| m.as_str()).unwrap_or("")
     );
-    trace!("   📋 Cookie header: {:?}", cookie);
     trace!("   📋 Extracted session: {:?}", session_id);
     trace!("   📋 Effective session: {:?}", effective_session_id);
     trace!("   📋 Message ID: {:?}", message.get("id"));
@@ -287,7 +393,7 @@ async fn handle_mcp_http(
     );
     let message_clone = message.clone();
 
-    debug!("🍪 Session ID from cookie: {:?}", session_id);
+    debug!("🍪 Session ID from request: {:?}", session_id);
     debug!("🍪 Effective session ID: {:?}", effective_session_id);
 
     // Check if this is a tools/call with progress token
@@ -368,9 +474,13 @@ async fn handle_mcp_http(
 
         // Process the message normally
         let start_time = std::time::Instant::now();
-        let response_result = handler
-            .handle_message(message_clone, effective_session_id.clone())
-            .await;
+        let response_result = handle_in_session(
+            &handler,
+            message_clone,
+            effective_session_id.clone(),
+            connection_info,
+        )
+        .await;
 
         // Strategy 1: Send completion progress notification
         if let Some(ref token) = progress_token {
@@ -395,9 +505,13 @@ async fn handle_mcp_http(
         (response_result, Some(notifications))
     } else {
         // Standard processing without progress notifications
-        let response_result = handler
-            .handle_message(message_clone, effective_session_id.clone())
-            .await;
+        let response_result = handle_in_session(
+            &handler,
+            message_clone,
+            effective_session_id.clone(),
+            connection_info,
+        )
+        .await;
         (response_result, None)
     };
 
@@ -608,8 +722,26 @@ async fn handle_mcp_http(
             // Add connection header
             let base_reply = reply::with_header(base_reply, "connection", connection_header);
 
-            // Set session cookie for initialize responses
-            let set_cookie_value = if method == "initialize" && response.get("result").is_some() {
+            // Set session cookie and header for initialize responses
+            let new_session_id = if method == "initialize" && response.get("result").is_some() {
+                effective_session_id.clone()
+            } else {
+                None
+            };
+            // The client started over, so the session it came from is done
+            if let (Some(_), Some(previous)) = (&new_session_id, &session_id) {
+                let replaced = handler
+                    .end_owned_session(
+                        previous,
+                        principal.as_ref(),
+                        SessionEndReason::Reinitialized,
+                    )
+                    .await;
+                if replaced == Ok(true) {
+                    debug!("🍪 Replaced session {} on re-initialization", previous);
+                }
+            }
+            let set_cookie_value = if new_session_id.is_some() {
                 let cookie_value = format!(
                     "mcp_session={}; Path=/mcp; HttpOnly; SameSite=Strict",
                     effective_session_id
@@ -629,7 +761,7 @@ async fn handle_mcp_http(
                         .unwrap_or(&"default".to_string())
                 );
                 debug!(
-                    "🍪 [COOKIE] Incoming session_id from cookie: {:?}",
+                    "🍪 [COOKIE] Incoming session_id from request: {:?}",
                     session_id
                 );
                 debug!(
@@ -704,13 +836,20 @@ async fn handle_mcp_http(
             tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
 
             trace!("✅ === REQUEST {} COMPLETE ===", request_id);
-            Ok(final_reply.into_response())
+            let mut response = final_reply.into_response();
+            if let Some(value) = new_session_id.and_then(|id| HeaderValue::from_str(&id).ok()) {
+                response.headers_mut().insert("mcp-session-id", value);
+            }
+            Ok(response)
         }
         Err(e) => {
             error!(
                 "❌ HTTP MCP error: {} (id={:?}, method={:?})",
                 e, message_id, method
             );
+            if let Some(denied) = e.downcast_ref::<AccessDenied>() {
+                return Ok(auth::forbidden(&message_id, denied));
+            }
             // Create error response with JSON-RPC error codes
             // Always return 200 OK status, let JSON-RPC handle the error details
             let error_code = match e.to_string() {
@@ -829,7 +968,8 @@ async fn handle_mcp_get(
 async fn handle_mcp_enhanced_post(
     capabilities: TransportCapabilities,
     message: Value,
-    cookie: Option<String>,
+    session_id: Option<String>,
+    connection_info: ConnectionInfo,
     handler: Arc<McpProtocolEngine>,
 ) -> Result<warp::reply::Response, Rejection> {
     debug!(
@@ -862,7 +1002,8 @@ async fn handle_mcp_enhanced_post(
                 Some("application/json".to_string()),
                 Some("application/json".to_string()),
                 Some("close".to_string()),
-                cookie,
+                session_id,
+                connection_info,
                 handler,
             )
             .await
@@ -958,6 +1099,40 @@ async fn handle_mcp_sse_fallback(
     Err(warp::reject::not_found())
 }
 
+/// Pick the session a request is handled in.
+///
+/// `initialize` always starts a new session with a fresh ID. Other requests
+/// use the session the client names; `None` means the client sent no session
+/// ID and is handled statelessly.
+fn effective_session_id(method: &str, session_id: Option<String>) -> Option<String> {
+    if method == "initialize" {
+        Some(session::generate_session_id())
+    } else {
+        session_id
+    }
+}
+
+/// Handle a message in its session, or statelessly when it names none.
+async fn handle_in_session(
+    handler: &McpProtocolEngine,
+    message: Value,
+    session_id: Option<String>,
+    connection_info: ConnectionInfo,
+) -> Result<Value> {
+    match session_id {
+        Some(session_id) => {
+            handler
+                .handle_message_with_connection(message, Some(session_id), Some(connection_info))
+                .await
+        }
+        None => {
+            handler
+                .handle_stateless_message(message, Some(connection_info))
+                .await
+        }
+    }
+}
+
 /// Extract session ID from cookie header
 fn extract_session_id_from_cookie(cookie: &Option<String>) -> Option<String> {
     if let Some(cookie_str) = cookie {
@@ -1091,46 +1266,19 @@ mod tests {
 
     #[test]
     fn test_effective_session_id_logic() {
-        // Test initialize method without session
-        let method = "initialize";
-        let session_id: Option<String> = None;
-
-        let effective_session_id = if method == "initialize" || session_id.is_none() {
-            Some("http_default_session".to_string())
-        } else {
-            session_id.clone()
-        };
+        // Every initialize starts a new session, even when the client names one
+        let first = effective_session_id("initialize", None).unwrap();
+        let second = effective_session_id("initialize", Some(first.clone())).unwrap();
+        assert_eq!(first.len(), 32);
+        assert_ne!(first, second);
 
+        // Other requests stay in the session they name
         assert_eq!(
-            effective_session_id,
-            Some("http_default_session".to_string())
+            effective_session_id("tools/list", Some("existing_session".to_string())),
+            Some("existing_session".to_string())
         );
 
-        // Test non-initialize method without session
-        let method = "tools/list";
-        let session_id: Option<String> = None;
-
-        let effective_session_id = if method == "initialize" || session_id.is_none() {
-            Some("http_default_session".to_string())
-        } else {
-            session_id.clone()
-        };
-
-        assert_eq!(
-            effective_session_id,
-            Some("http_default_session".to_string())
-        );
-
-        // Test with existing session
-        let method = "tools/list";
-        let session_id = Some("existing_session".to_string());
-
-        let effective_session_id = if method == "initialize" || session_id.is_none() {
-            Some("http_default_session".to_string())
-        } else {
-            session_id.clone()
-        };
-
-        assert_eq!(effective_session_id, Some("existing_session".to_string()));
+        // Without a session ID the request is stateless
+        assert_eq!(effective_session_id("tools/list", None), None);
     }
 }
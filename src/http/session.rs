@@ -16,7 +16,6 @@ pub(crate) fn extract_session_id_from_cookie(cookie: &Option<String>) -> Option<
     })
 }
 
-#[allow(dead_code)]
 pub(crate) fn generate_session_id() -> String {
     rand::rng()
         .sample_iter(&Alphanumeric)
@@ -0,0 +1,358 @@
+//! Server Introspection
+//!
+//! The protocol engine keeps track of its initialized sessions and of the
+//! messages it is handling, so operators can see what a server is doing when
+//! an agent seems stuck. `McpProtocolEngine::sessions` and
+//! `McpProtocolEngine::in_flight_requests` return read-only snapshots;
+//! `cancel_request` and `terminate_session` act on them. The admin API (see
+//! [`crate::admin`]) serves the same data over HTTP.
+//!
+//! # Example
+//!
+//! ```rust
+//! for request in engine.in_flight_requests() {
+//!     if request.elapsed_ms > 60_000.0 {
+//!         engine.cancel_request(request.id);
+//!     }
+//! }
+//! ```
+
+use {
+    crate::{metrics::TransportKind, protocol_impl::McpProtocolHandlerImpl},
+    serde::Serialize,
+    serde_json::{json, Value},
+    std::{
+        collections::{BTreeMap, HashMap},
+        sync::{Arc, Mutex, MutexGuard, PoisonError},
+        time::{Instant, SystemTime, UNIX_EPOCH},
+    },
+    tokio::sync::Notify,
+};
+
+/// JSON-RPC error code of requests cancelled through `McpProtocolEngine::cancel_request`.
+pub const REQUEST_CANCELLED: i64 = -32800;
+
+/// An initialized session, as seen by the engine.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionInfo {
+    /// ID of the session
+    pub session_id: String,
+    /// Transport the session is connected over
+    pub transport: TransportKind,
+    /// `clientInfo` the client sent in `initialize`
+    pub client_info: Option<Value>,
+    /// Protocol version negotiated in `initialize`
+    pub protocol_version: Option<String>,
+    /// When the session initialized, in seconds since the Unix epoch
+    pub started_at: u64,
+    /// Seconds since the session initialized
+    pub age_secs: f64,
+    /// Seconds since the session's last message
+    pub idle_secs: f64,
+    /// Number of the session's messages being handled
+    pub in_flight: usize,
+}
+
+/// A message the engine is handling.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InFlightRequest {
+    /// Engine-wide ID, for `McpProtocolEngine::cancel_request`
+    pub id: u64,
+    /// Session that sent the message
+    pub session_id: String,
+    /// JSON-RPC method
+    pub method: String,
+    /// Tool being called, for `tools/call`
+    pub tool: Option<String>,
+    /// JSON-RPC ID the client gave the request
+    pub request_id: Option<Value>,
+    /// Milliseconds since the engine received the message
+    pub elapsed_ms: f64,
+}
+
+/// Live sessions and in-flight messages of an engine.
+#[derive(Clone, Default)]
+pub(crate) struct Introspection {
+    state: Arc<Mutex<State>>,
+    /// Signalled whenever the last in-flight message finishes
+    idle: Arc<Notify>,
+}
+
+#[derive(Default)]
+struct State {
+    sessions: HashMap<String, SessionEntry>,
+    /// Signals that close each open WebSocket
+    websockets: HashMap<String, Arc<Notify>>,
+    requests: BTreeMap<u64, RequestEntry>,
+    next_id: u64,
+}
+
+struct SessionEntry {
+    client_info: Option<Value>,
+    protocol_version: Option<String>,
+    started_at: SystemTime,
+    started: Instant,
+    last_activity: Instant,
+}
+
+struct RequestEntry {
+    session_id: String,
+    method: String,
+    tool: Option<String>,
+    request_id: Option<Value>,
+    started: Instant,
+    cancel: Arc<Notify>,
+}
+
+/// Registration of an in-flight message, removed when dropped.
+pub(crate) struct RequestGuard {
+    state: Arc<Mutex<State>>,
+    idle: Arc<Notify>,
+    id: u64,
+    request_id: Option<Value>,
+    cancel: Arc<Notify>,
+}
+
+impl RequestGuard {
+    /// Wait until the message is cancelled.
+    pub(crate) async fn cancelled(&self) {
+        self.cancel.notified().await
+    }
+
+    /// Build the response sent in place of a cancelled message's.
+    pub(crate) fn cancelled_response(&self) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": self.request_id,
+            "error": {
+                "code": REQUEST_CANCELLED,
+                "message": "Request cancelled"
+            }
+        })
+    }
+}
+
+impl Drop for RequestGuard {
+    fn drop(&mut self) {
+        let mut state = lock(&self.state);
+        state.requests.remove(&self.id);
+        if state.requests.is_empty() {
+            self.idle.notify_waiters();
+        }
+    }
+}
+
+impl Introspection {
+    /// Register a message the engine starts handling.
+    pub(crate) fn begin(&self, session_id: &str, message: &Value) -> RequestGuard {
+        let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+        let tool = match method {
+            "tools/call" => message
+                .get("params")
+                .and_then(|params| params.get("name"))
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            _ => None,
+        };
+        let request_id = message.get("id").cloned();
+        let cancel = Arc::new(Notify::new());
+        let mut state = lock(&self.state);
+        state.next_id += 1;
+        let id = state.next_id;
+        state.requests.insert(
+            id,
+            RequestEntry {
+                session_id: session_id.to_string(),
+                method: method.to_string(),
+                tool,
+                request_id: request_id.clone(),
+                started: Instant::now(),
+                cancel: cancel.clone(),
+            },
+        );
+        RequestGuard {
+            state: self.state.clone(),
+            idle: self.idle.clone(),
+            id,
+            request_id,
+            cancel,
+        }
+    }
+
+    /// Record that a session handled a message, starting to track it once initialized.
+    ///
+    /// A new `initialize` restarts the session's entry.
+    pub(crate) fn session_active(
+        &self,
+        session_id: &str,
+        initializing: bool,
+        session: &McpProtocolHandlerImpl,
+    ) {
+        let mut state = lock(&self.state);
+        if !session.initialized {
+            state.sessions.remove(session_id);
+            return;
+        }
+        let now = Instant::now();
+        match state.sessions.get_mut(session_id) {
+            Some(entry) if !initializing => entry.last_activity = now,
+            _ => {
+                state.sessions.insert(
+                    session_id.to_string(),
+                    SessionEntry {
+                        client_info: session.client_info.clone(),
+                        protocol_version: session.protocol_version.clone(),
+                        started_at: SystemTime::now(),
+                        started: now,
+                        last_activity: now,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Stop tracking a session that ended.
+    pub(crate) fn session_ended(&self, session_id: &str) {
+        lock(&self.state).sessions.remove(session_id);
+    }
+
+    /// Register an open WebSocket, returning the signal that closes it.
+    pub(crate) fn websocket_opened(&self, session_id: &str) -> Arc<Notify> {
+        let close = Arc::new(Notify::new());
+        lock(&self.state)
+            .websockets
+            .insert(session_id.to_string(), close.clone());
+        close
+    }
+
+    pub(crate) fn websocket_closed(&self, session_id: &str) {
+        lock(&self.state).websockets.remove(session_id);
+    }
+
+    /// Signal a session's WebSocket to close.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the session has an open WebSocket
+    pub(crate) fn close_websocket(&self, session_id: &str) -> bool {
+        match lock(&self.state).websockets.get(session_id) {
+            Some(close) => {
+                close.notify_one();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Cancel an in-flight message by its engine-wide ID.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the message was still in flight
+    pub(crate) fn cancel(&self, id: u64) -> bool {
+        match lock(&self.state).requests.get(&id) {
+            Some(request) => {
+                request.cancel.notify_one();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Signal every open WebSocket to close.
+    pub(crate) fn close_websockets(&self) {
+        lock(&self.state)
+            .websockets
+            .values()
+            .for_each(|close| close.notify_one());
+    }
+
+    /// Wait until no message is in flight.
+    pub(crate) async fn drained(&self) {
+        loop {
+            // Registered before checking, so a finish in between is not missed
+            let idle = self.idle.notified();
+            if lock(&self.state).requests.is_empty() {
+                return;
+            }
+            idle.await;
+        }
+    }
+
+    /// Cancel every in-flight message.
+    ///
+    /// # Returns
+    ///
+    /// The number of messages cancelled
+    pub(crate) fn cancel_all(&self) -> usize {
+        let state = lock(&self.state);
+        state
+            .requests
+            .values()
+            .for_each(|request| request.cancel.notify_one());
+        state.requests.len()
+    }
+
+    /// Cancel every in-flight message of a session.
+    pub(crate) fn cancel_session(&self, session_id: &str) {
+        lock(&self.state)
+            .requests
+            .values()
+            .filter(|request| request.session_id == session_id)
+            .for_each(|request| request.cancel.notify_one());
+    }
+
+    /// Take a snapshot of the initialized sessions, oldest first.
+    pub(crate) fn sessions(&self) -> Vec<SessionInfo> {
+        let state = lock(&self.state);
+        let now = Instant::now();
+        let mut sessions: Vec<SessionInfo> = state
+            .sessions
+            .iter()
+            .map(|(session_id, entry)| SessionInfo {
+                session_id: session_id.clone(),
+                transport: if state.websockets.contains_key(session_id) {
+                    TransportKind::WebSocket
+                } else {
+                    TransportKind::Http
+                },
+                client_info: entry.client_info.clone(),
+                protocol_version: entry.protocol_version.clone(),
+                started_at: entry
+                    .started_at
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+                age_secs: now.duration_since(entry.started).as_secs_f64(),
+                idle_secs: now.duration_since(entry.last_activity).as_secs_f64(),
+                in_flight: state
+                    .requests
+                    .values()
+                    .filter(|request| &request.session_id == session_id)
+                    .count(),
+            })
+            .collect();
+        sessions.sort_by(|a, b| b.age_secs.total_cmp(&a.age_secs));
+        sessions
+    }
+
+    /// Take a snapshot of the in-flight messages, oldest first.
+    pub(crate) fn requests(&self) -> Vec<InFlightRequest> {
+        lock(&self.state)
+            .requests
+            .iter()
+            .map(|(id, request)| InFlightRequest {
+                id: *id,
+                session_id: request.session_id.clone(),
+                method: request.method.clone(),
+                tool: request.tool.clone(),
+                request_id: request.request_id.clone(),
+                elapsed_ms: request.started.elapsed().as_secs_f64() * 1000.0,
+            })
+            .collect()
+    }
+}
+
+fn lock(state: &Mutex<State>) -> MutexGuard<'_, State> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
@@ -4,21 +4,35 @@
 //! supporting both WebSocket and HTTP transports.
 
 // Re-export the main modules
+pub mod audit;
+pub mod admin;
+pub mod auth;
 pub mod content_types;
 pub mod core;
 pub mod framework;
 pub mod handler;
+pub mod health;
 pub mod handlers;
 pub mod http;
+pub mod introspection;
 pub mod logging;
+pub mod metrics;
+pub mod middleware;
+pub mod oauth;
 pub mod protocol;
 pub mod protocol_impl;
+pub mod rate_limit;
+pub mod server_config;
+pub mod server_handle;
+pub mod session_store;
 // Legacy trait removed - internal use only
 // pub mod protocol_testable;
 // Legacy server module removed - use framework module instead
 pub mod shared;
+pub mod tls;
 pub mod tool_response;
 pub mod tools;
+pub mod trace_context;
 pub mod transport;
 pub mod typed_response;
 pub mod validation;
@@ -30,25 +44,65 @@ pub mod tests;
 
 // Re-export key types
 pub use core::McpServer;
+pub use server_config::ServerConfig;
+pub use server_handle::ServerHandle;
+pub use tls::{ClientAuth, ClientCertificate, TlsConfig};
 pub use protocol::McpProtocol;
-pub use protocol_impl::McpProtocolHandlerImpl;
+pub use protocol_impl::{McpError, McpProtocolHandlerImpl};
 // Legacy trait removed - McpProtocolHandler is now internal
 pub use tools::McpTools;
 
 // Re-export core handler trait and types
 pub use handler::{
-    LogLevel, McpContext, McpHandler, McpNotification, PromptArgument, PromptContent, PromptInfo,
-    PromptMessage, ResourceContent, ResourceInfo, ToolDefinition, TypedToolDefinition,
+    ConnectionInfo, LogLevel, McpContext, McpHandler, McpNotification, PromptArgument,
+    PromptContent, PromptInfo, PromptMessage, ResourceContent, ResourceData, ResourceInfo,
+    SessionNotifier, ToolDefinition, TypedToolDefinition,
+};
+
+// Re-export authentication types
+pub use auth::{AccessDenied, ApiKeyAuthenticator, AuthError, Authenticator, Principal};
+
+// Re-export middleware types
+pub use middleware::{McpRequest, Middleware, Next};
+
+// Re-export audit types
+pub use audit::{AuditLog, AuditOutcome, AuditRecord, AuditSink, JsonLinesAuditSink};
+
+// Re-export admin and introspection types
+pub use admin::AdminApi;
+pub use introspection::{InFlightRequest, SessionInfo};
+
+// Re-export health check types
+pub use health::{HealthReport, HealthStatus, Probe, Readiness, ServerPhase};
+
+// Re-export metrics types
+pub use metrics::{Metrics, MetricsSnapshot};
+
+// Re-export OAuth resource server types
+pub use oauth::ProtectedResourceMetadata;
+#[cfg(feature = "oauth")]
+pub use oauth::{Jwks, JwtAuthenticator};
+
+// Re-export tracing types
+pub use trace_context::TraceContext;
+
+// Re-export rate limiting types
+pub use rate_limit::{LimitKey, QuotaUsage, RateLimit, RateLimiter};
+
+// Re-export session store types
+pub use session_store::{
+    EvictionReason, FileSessionStore, InMemorySessionStore, SessionData, SessionEndReason,
+    SessionExtensions, SessionStore,
 };
 
 // Re-export schemars for convenience
 pub use schemars::JsonSchema;
 
 // Re-export new framework API
-pub use framework::{FrameworkHandler, McpServerBuilder, PromptProvider, ResourceProvider};
+pub use framework::{FrameworkHandler, McpServerBuilder, PromptProvider, ResourceProvider, ResourceRoute};
 
 // Re-export content types for type-safe MCP responses
-pub use content_types::{McpContent, McpResponse, ToMcpResponse};
+pub use content_types::{Annotations, EmbeddedResource, McpContent, McpResponse, Role, ToMcpResponse};
 
 // Re-export WebSocket handler for convenience
 pub use websocket::handle_mcp_ws_main as handle_mcp_ws;
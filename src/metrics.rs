@@ -0,0 +1,579 @@
+//! Operational Metrics
+//!
+//! The protocol engine records every message it handles in a `Metrics`
+//! registry: request counts, errors and latency per JSON-RPC method and per
+//! tool, error counts by JSON-RPC code, and request and response sizes. The
+//! transports add active sessions, WebSocket connections and notification
+//! queue depths.
+//!
+//! `McpServer` serves the numbers in the Prometheus text format at `/metrics`
+//! (see `McpServerBuilder::with_metrics_path`), and `McpServer::metrics`
+//! returns them as a `MetricsSnapshot`.
+//!
+//! # Example
+//!
+//! ```rust
+//! let snapshot = server.metrics();
+//! if let Some(calls) = snapshot.tools.get("search") {
+//!     println!("search: {} calls, {} errors", calls.requests, calls.errors);
+//! }
+//! ```
+
+use {
+    serde::Serialize,
+    serde_json::Value,
+    std::{
+        collections::{BTreeMap, HashMap, HashSet},
+        fmt::Write as _,
+        io,
+        sync::{Arc, Mutex, MutexGuard, PoisonError},
+        time::{Duration, Instant},
+    },
+    warp::{filters::path::FullPath, http::StatusCode, Filter, Rejection, Reply},
+};
+
+/// Path the Prometheus endpoint is served at unless configured otherwise.
+pub const DEFAULT_METRICS_PATH: &str = "/metrics";
+
+/// Upper bounds of the latency buckets, in seconds.
+const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Upper bounds of the payload size buckets, in bytes.
+const SIZE_BUCKETS: [f64; 8] = [
+    128.0, 512.0, 2048.0, 8192.0, 32768.0, 131072.0, 524288.0, 2097152.0,
+];
+
+/// JSON-RPC code of "method not found"; such methods and tools are counted as `unknown`.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Most distinct methods or tools tracked, so clients sending made-up names
+/// cannot grow the label set without bound; further names count as `unknown`.
+const MAX_LABELS: usize = 512;
+
+/// Code of the error response transports send when the engine fails.
+const INTERNAL_ERROR: i64 = -32603;
+
+/// Transport a session is connected over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransportKind {
+    /// HTTP POST requests tied together by the session cookie
+    Http,
+    /// A WebSocket connection
+    WebSocket,
+}
+
+impl TransportKind {
+    fn label(self) -> &'static str {
+        match self {
+            TransportKind::Http => "http",
+            TransportKind::WebSocket => "websocket",
+        }
+    }
+}
+
+/// A histogram with fixed buckets.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Histogram {
+    /// Upper bound of each bucket and the number of observations at or below it
+    pub buckets: Vec<(f64, u64)>,
+    /// Number of observations
+    pub count: u64,
+    /// Sum of all observations
+    pub sum: f64,
+}
+
+impl Histogram {
+    fn new(bounds: &[f64]) -> Self {
+        Self {
+            buckets: bounds.iter().map(|&bound| (bound, 0)).collect(),
+            count: 0,
+            sum: 0.0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        for (bound, count) in &mut self.buckets {
+            if value <= *bound {
+                *count += 1;
+            }
+        }
+        self.count += 1;
+        self.sum += value;
+    }
+
+    /// Get the mean of the observations, if there are any.
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.sum / self.count as f64)
+    }
+}
+
+/// Counts and latency of one JSON-RPC method or tool.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RequestStats {
+    /// Messages handled
+    pub requests: u64,
+    /// Messages answered with an error (for tools, also results with `isError`)
+    pub errors: u64,
+    /// Time taken to handle each message, in seconds
+    pub latency: Histogram,
+}
+
+impl Default for RequestStats {
+    fn default() -> Self {
+        Self {
+            requests: 0,
+            errors: 0,
+            latency: Histogram::new(&LATENCY_BUCKETS),
+        }
+    }
+}
+
+impl RequestStats {
+    fn record(&mut self, failed: bool, elapsed: Duration) {
+        self.requests += 1;
+        if failed {
+            self.errors += 1;
+        }
+        self.latency.observe(elapsed.as_secs_f64());
+    }
+}
+
+/// A message being handled, from `Metrics::start` until `Metrics::finish`.
+pub(crate) struct Observation {
+    session_id: String,
+    method: Option<String>,
+    tool: Option<String>,
+    request_size: usize,
+    started: Instant,
+}
+
+/// Point-in-time copy of a server's metrics.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricsSnapshot {
+    /// Statistics per JSON-RPC method
+    pub methods: BTreeMap<String, RequestStats>,
+    /// Statistics per tool called through `tools/call`
+    pub tools: BTreeMap<String, RequestStats>,
+    /// Error responses per JSON-RPC error code
+    pub errors: BTreeMap<i64, u64>,
+    /// Initialized sessions per transport
+    pub active_sessions: BTreeMap<TransportKind, usize>,
+    /// Open WebSocket connections
+    pub websocket_connections: usize,
+    /// Notifications waiting to be sent, per WebSocket session
+    pub notification_queues: BTreeMap<String, usize>,
+    /// Serialized size of incoming messages, in bytes
+    pub request_size: Histogram,
+    /// Serialized size of responses, in bytes
+    pub response_size: Histogram,
+}
+
+impl MetricsSnapshot {
+    /// Render the snapshot in the Prometheus text exposition format.
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        write_stats(
+            &mut out,
+            "solidmcp_requests",
+            "JSON-RPC messages",
+            "method",
+            &self.methods,
+        );
+        write_stats(
+            &mut out,
+            "solidmcp_tool_calls",
+            "tool calls",
+            "tool",
+            &self.tools,
+        );
+
+        header(
+            &mut out,
+            "solidmcp_errors_total",
+            "Error responses by JSON-RPC code",
+            "counter",
+        );
+        for (code, count) in &self.errors {
+            let _ = writeln!(out, "solidmcp_errors_total{{code=\"{code}\"}} {count}");
+        }
+
+        header(
+            &mut out,
+            "solidmcp_active_sessions",
+            "Initialized sessions by transport",
+            "gauge",
+        );
+        for transport in [TransportKind::Http, TransportKind::WebSocket] {
+            let count = self.active_sessions.get(&transport).copied().unwrap_or(0);
+            let _ = writeln!(
+                out,
+                "solidmcp_active_sessions{{transport=\"{}\"}} {count}",
+                transport.label()
+            );
+        }
+
+        header(
+            &mut out,
+            "solidmcp_websocket_connections",
+            "Open WebSocket connections",
+            "gauge",
+        );
+        let _ = writeln!(
+            out,
+            "solidmcp_websocket_connections {}",
+            self.websocket_connections
+        );
+
+        header(
+            &mut out,
+            "solidmcp_notification_queue_depth",
+            "Notifications waiting to be sent, summed over sessions",
+            "gauge",
+        );
+        let queued: usize = self.notification_queues.values().sum();
+        let _ = writeln!(out, "solidmcp_notification_queue_depth {queued}");
+        header(
+            &mut out,
+            "solidmcp_notification_queue_depth_max",
+            "Notifications waiting to be sent to the most backed-up session",
+            "gauge",
+        );
+        let deepest = self
+            .notification_queues
+            .values()
+            .max()
+            .copied()
+            .unwrap_or(0);
+        let _ = writeln!(out, "solidmcp_notification_queue_depth_max {deepest}");
+
+        header(
+            &mut out,
+            "solidmcp_request_size_bytes",
+            "Size of incoming messages",
+            "histogram",
+        );
+        write_histogram(
+            &mut out,
+            "solidmcp_request_size_bytes",
+            None,
+            &self.request_size,
+        );
+        header(
+            &mut out,
+            "solidmcp_response_size_bytes",
+            "Size of responses",
+            "histogram",
+        );
+        write_histogram(
+            &mut out,
+            "solidmcp_response_size_bytes",
+            None,
+            &self.response_size,
+        );
+        out
+    }
+}
+
+/// Live metrics of a protocol engine.
+///
+/// Cloning is cheap; all clones record into the same registry.
+#[derive(Clone, Default)]
+pub struct Metrics {
+    state: Arc<Mutex<State>>,
+}
+
+struct State {
+    methods: HashMap<String, RequestStats>,
+    tools: HashMap<String, RequestStats>,
+    errors: HashMap<i64, u64>,
+    sessions: HashMap<String, TransportKind>,
+    websockets: HashSet<String>,
+    queues: HashMap<String, usize>,
+    request_size: Histogram,
+    response_size: Histogram,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            methods: HashMap::new(),
+            tools: HashMap::new(),
+            errors: HashMap::new(),
+            sessions: HashMap::new(),
+            websockets: HashSet::new(),
+            queues: HashMap::new(),
+            request_size: Histogram::new(&SIZE_BUCKETS),
+            response_size: Histogram::new(&SIZE_BUCKETS),
+        }
+    }
+}
+
+impl Metrics {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy the current values.
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let state = self.lock();
+        let mut active_sessions = BTreeMap::new();
+        for transport in state.sessions.values() {
+            *active_sessions.entry(*transport).or_insert(0) += 1;
+        }
+        MetricsSnapshot {
+            methods: state.methods.clone().into_iter().collect(),
+            tools: state.tools.clone().into_iter().collect(),
+            errors: state.errors.clone().into_iter().collect(),
+            active_sessions,
+            websocket_connections: state.websockets.len(),
+            notification_queues: state.queues.clone().into_iter().collect(),
+            request_size: state.request_size.clone(),
+            response_size: state.response_size.clone(),
+        }
+    }
+
+    /// Start observing a message the engine is about to handle.
+    pub(crate) fn start(&self, session_id: &str, message: &Value) -> Observation {
+        let method = message.get("method").and_then(Value::as_str);
+        let tool = match method {
+            Some("tools/call") => message
+                .get("params")
+                .and_then(|params| params.get("name"))
+                .and_then(Value::as_str),
+            _ => None,
+        };
+        Observation {
+            session_id: session_id.to_string(),
+            method: method.map(str::to_string),
+            tool: tool.map(str::to_string),
+            request_size: serialized_size(message),
+            started: Instant::now(),
+        }
+    }
+
+    /// Record the outcome of an observed message.
+    ///
+    /// `response` is `None` when the engine failed, which transports answer
+    /// with an internal error. A successful `initialize` starts counting the
+    /// session as active.
+    pub(crate) fn finish(&self, observation: Observation, response: Option<&Value>) {
+        let elapsed = observation.started.elapsed();
+        let Some(method) = observation.method.as_deref() else {
+            return;
+        };
+        let code = match response {
+            Some(response) => response.get("error").map(|error| {
+                error
+                    .get("code")
+                    .and_then(Value::as_i64)
+                    .unwrap_or(INTERNAL_ERROR)
+            }),
+            None => Some(INTERNAL_ERROR),
+        };
+        let response_size = response.map(serialized_size);
+
+        let mut state = self.lock();
+        let method_label = match code {
+            Some(METHOD_NOT_FOUND) if method != "tools/call" => None,
+            _ => Some(method),
+        };
+        stats_for(&mut state.methods, method_label).record(code.is_some(), elapsed);
+        if let Some(code) = code {
+            *state.errors.entry(code).or_insert(0) += 1;
+        }
+        if method == "tools/call" {
+            let tool = match code {
+                Some(METHOD_NOT_FOUND) => None,
+                _ => observation.tool.as_deref(),
+            };
+            let tool_failed = code.is_some()
+                || response
+                    .and_then(|response| response.get("result"))
+                    .and_then(|result| result.get("isError"))
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
+            stats_for(&mut state.tools, tool).record(tool_failed, elapsed);
+        }
+        state.request_size.observe(observation.request_size as f64);
+        if let Some(size) = response_size {
+            state.response_size.observe(size as f64);
+        }
+        if method == "initialize" && code.is_none() {
+            let transport = if state.websockets.contains(&observation.session_id) {
+                TransportKind::WebSocket
+            } else {
+                TransportKind::Http
+            };
+            state.sessions.insert(observation.session_id, transport);
+        }
+    }
+
+    /// Stop counting a session as active.
+    pub(crate) fn session_ended(&self, session_id: &str) {
+        self.lock().sessions.remove(session_id);
+    }
+
+    /// Count a WebSocket connection serving `session_id`.
+    pub(crate) fn websocket_opened(&self, session_id: &str) {
+        self.lock().websockets.insert(session_id.to_string());
+    }
+
+    /// Stop counting the WebSocket connection serving `session_id`.
+    pub(crate) fn websocket_closed(&self, session_id: &str) {
+        let mut state = self.lock();
+        state.websockets.remove(session_id);
+        state.queues.remove(session_id);
+    }
+
+    /// Record how many notifications are waiting to be sent to a session.
+    pub(crate) fn notification_queue(&self, session_id: &str, depth: usize) {
+        self.lock().queues.insert(session_id.to_string(), depth);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Serve the Prometheus text format for `GET <path>`.
+pub(crate) fn route(
+    metrics: Metrics,
+    path: &str,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    let path = path.trim_matches('/').to_string();
+    warp::get()
+        .and(warp::path::full())
+        .and_then(move |full: FullPath| {
+            let served = full.as_str().trim_matches('/') == path;
+            let metrics = metrics.clone();
+            async move {
+                if !served {
+                    return Err(warp::reject::not_found());
+                }
+                Ok(warp::reply::with_status(
+                    warp::reply::with_header(
+                        metrics.snapshot().to_prometheus(),
+                        "content-type",
+                        "text/plain; version=0.0.4; charset=utf-8",
+                    ),
+                    StatusCode::OK,
+                ))
+            }
+        })
+}
+
+/// Get the statistics of `name`, or of `unknown` if there is no name or too many.
+fn stats_for<'a>(
+    stats: &'a mut HashMap<String, RequestStats>,
+    name: Option<&str>,
+) -> &'a mut RequestStats {
+    let name = match name {
+        Some(name) if stats.len() < MAX_LABELS || stats.contains_key(name) => name,
+        _ => "unknown",
+    };
+    stats.entry(name.to_string()).or_default()
+}
+
+fn header(out: &mut String, name: &str, help: &str, kind: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}.");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn write_stats(
+    out: &mut String,
+    prefix: &str,
+    noun: &str,
+    label: &str,
+    stats: &BTreeMap<String, RequestStats>,
+) {
+    header(
+        out,
+        &format!("{prefix}_total"),
+        &format!("Handled {noun}"),
+        "counter",
+    );
+    for (name, stats) in stats {
+        let _ = writeln!(
+            out,
+            "{prefix}_total{{{label}=\"{}\"}} {}",
+            escape(name),
+            stats.requests
+        );
+    }
+    header(
+        out,
+        &format!("{prefix}_errors_total"),
+        &format!("Failed {noun}"),
+        "counter",
+    );
+    for (name, stats) in stats {
+        let _ = writeln!(
+            out,
+            "{prefix}_errors_total{{{label}=\"{}\"}} {}",
+            escape(name),
+            stats.errors
+        );
+    }
+    let histogram = format!("{prefix}_duration_seconds");
+    header(
+        out,
+        &histogram,
+        &format!("Time taken to handle {noun}"),
+        "histogram",
+    );
+    for (name, stats) in stats {
+        let labels = format!("{label}=\"{}\"", escape(name));
+        write_histogram(out, &histogram, Some(&labels), &stats.latency);
+    }
+}
+
+fn write_histogram(out: &mut String, name: &str, labels: Option<&str>, histogram: &Histogram) {
+    let prefix = labels
+        .map(|labels| format!("{labels},"))
+        .unwrap_or_default();
+    for (bound, count) in &histogram.buckets {
+        let _ = writeln!(out, "{name}_bucket{{{prefix}le=\"{bound}\"}} {count}");
+    }
+    let _ = writeln!(
+        out,
+        "{name}_bucket{{{prefix}le=\"+Inf\"}} {}",
+        histogram.count
+    );
+    let labels = labels
+        .map(|labels| format!("{{{labels}}}"))
+        .unwrap_or_default();
+    let _ = writeln!(out, "{name}_sum{labels} {}", histogram.sum);
+    let _ = writeln!(out, "{name}_count{labels} {}", histogram.count);
+}
+
+/// Escape a Prometheus label value.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Length of a value serialized as compact JSON, without allocating it.
+fn serialized_size(value: &Value) -> usize {
+    struct Counter(usize);
+
+    impl io::Write for Counter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0 += buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut counter = Counter(0);
+    let _ = serde_json::to_writer(&mut counter, value);
+    counter.0
+}
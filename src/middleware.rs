@@ -0,0 +1,214 @@
+//! MCP Middleware
+//!
+//! This module defines the `Middleware` trait, which wraps the dispatch of every
+//! JSON-RPC message handled by `McpProtocolEngine`. Middleware sees the method,
+//! params and `McpContext` of each request and can rewrite the request, return
+//! its own response without calling the handler, or transform the response.
+//!
+//! # Ordering
+//!
+//! Middleware runs in registration order: the first registered is the outermost
+//! layer and sees the request first and the response last. The innermost layer
+//! calls the engine's normal dispatch, so middleware applies to tools, resources,
+//! prompts, `initialize`, `ping`, notifications and unknown methods alike.
+//!
+//! # Example
+//!
+//! ```rust
+//! use solidmcp::middleware::{McpRequest, Middleware, Next};
+//! use anyhow::Result;
+//! use async_trait::async_trait;
+//! use serde_json::Value;
+//! use std::time::Instant;
+//!
+//! struct Timing;
+//!
+//! #[async_trait]
+//! impl Middleware for Timing {
+//!     async fn handle(&self, request: McpRequest, next: Next<'_>) -> Result<Value> {
+//!         let method = request.method.clone();
+//!         let started = Instant::now();
+//!         let response = next.run(request).await;
+//!         tracing::info!("{} took {:?}", method, started.elapsed());
+//!         response
+//!     }
+//! }
+//! ```
+
+use {
+    crate::{
+        handler::McpContext, protocol_impl::McpProtocolHandlerImpl, session_store::SessionData,
+        shared::McpProtocolEngine,
+    },
+    anyhow::Result,
+    async_trait::async_trait,
+    serde_json::{json, Value},
+    std::sync::Arc,
+};
+
+/// A layer wrapped around the dispatch of every MCP message.
+///
+/// Implementations call `next.run(request)` to continue down the chain, or
+/// return early to short-circuit it. Returning `Err` with an
+/// [`McpError`](crate::McpError) produces a JSON-RPC error response with the
+/// matching code; other errors are reported like handler errors.
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    /// Handle a request, usually by delegating to `next`.
+    ///
+    /// # Parameters
+    ///
+    /// - `request`: The method, params and context of the message
+    /// - `next`: The rest of the chain, ending in the engine's dispatch
+    ///
+    /// # Returns
+    ///
+    /// The complete JSON-RPC response message
+    async fn handle(&self, request: McpRequest, next: Next<'_>) -> Result<Value>;
+}
+
+/// A JSON-RPC message as seen by middleware.
+///
+/// Changes to `method`, `params` or `context` are what the rest of the chain
+/// and the handler receive.
+///
+/// # Fields
+///
+/// - `method`: The JSON-RPC method, e.g. `tools/call`
+/// - `params`: The `params` object, if the message had one
+/// - `context`: Session, client and transport details of the request
+pub struct McpRequest {
+    /// The JSON-RPC method name
+    pub method: String,
+    /// The request params, `None` if the message had none
+    pub params: Option<Value>,
+    /// Context passed on to the handler
+    pub context: McpContext,
+    message: Value,
+}
+
+impl McpRequest {
+    pub(crate) fn new(message: Value, context: McpContext) -> Self {
+        Self {
+            method: message["method"].as_str().unwrap_or("").to_string(),
+            params: message.get("params").cloned(),
+            context,
+            message,
+        }
+    }
+
+    /// Get the JSON-RPC request ID, `None` for notifications.
+    pub fn id(&self) -> Option<&Value> {
+        self.context.request_id.as_ref()
+    }
+
+    /// Get the tool name of a `tools/call` or the prompt name of a `prompts/get`.
+    pub fn name(&self) -> Option<&str> {
+        self.params.as_ref()?.get("name")?.as_str()
+    }
+
+    /// Build a successful JSON-RPC response to this request.
+    ///
+    /// Useful for middleware that answers a request itself, such as a cache.
+    ///
+    /// # Parameters
+    ///
+    /// - `result`: The `result` member of the response
+    ///
+    /// # Returns
+    ///
+    /// A JSON-RPC response carrying this request's ID
+    pub fn respond(&self, result: Value) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": self.id(),
+            "result": result
+        })
+    }
+
+    /// Build a JSON-RPC error response to this request.
+    ///
+    /// # Parameters
+    ///
+    /// - `code`: The JSON-RPC error code
+    /// - `message`: Short description of the error
+    /// - `data`: Optional structured details, e.g. when to retry
+    ///
+    /// # Returns
+    ///
+    /// A JSON-RPC error response carrying this request's ID
+    pub fn respond_error(&self, code: i32, message: &str, data: Option<Value>) -> Value {
+        let mut error = json!({ "code": code, "message": message });
+        if let Some(data) = data {
+            error["data"] = data;
+        }
+        json!({
+            "jsonrpc": "2.0",
+            "id": self.id(),
+            "error": error
+        })
+    }
+
+    /// Rebuild the JSON-RPC message with any changes made by middleware.
+    ///
+    /// Untouched fields are left exactly as received, so malformed messages
+    /// still reach protocol validation unchanged.
+    pub(crate) fn into_parts(self) -> (Value, McpContext) {
+        let mut message = self.message;
+        if let Some(object) = message.as_object_mut() {
+            if object.get("method").and_then(Value::as_str).unwrap_or("") != self.method {
+                object.insert("method".to_string(), Value::String(self.method));
+            }
+            if object.get("params") != self.params.as_ref() {
+                match self.params {
+                    Some(params) => object.insert("params".to_string(), params),
+                    None => object.remove("params"),
+                };
+            }
+        }
+        (message, self.context)
+    }
+}
+
+/// The remainder of the middleware chain.
+///
+/// Consumed by [`run`](Self::run), so each layer continues the chain at most once.
+pub struct Next<'a> {
+    pub(crate) engine: &'a McpProtocolEngine,
+    pub(crate) session: &'a mut McpProtocolHandlerImpl,
+    pub(crate) middleware: &'a [Arc<dyn Middleware>],
+}
+
+impl Next<'_> {
+    /// Application data kept with the current session.
+    ///
+    /// Changes are saved with the rest of the session state, so a durable
+    /// `SessionStore` keeps them across restarts. Tools see the same data
+    /// through `NotificationCtx::session_data`.
+    pub fn session_data(&self) -> &SessionData {
+        &self.session.data
+    }
+
+    /// Pass the request to the next layer, or to the engine's dispatch after the last one.
+    ///
+    /// # Returns
+    ///
+    /// The JSON-RPC response produced by the rest of the chain
+    pub async fn run(self, request: McpRequest) -> Result<Value> {
+        match self.middleware.split_first() {
+            Some((layer, rest)) => {
+                layer
+                    .handle(
+                        request,
+                        Next {
+                            engine: self.engine,
+                            session: self.session,
+                            middleware: rest,
+                        },
+                    )
+                    .await
+            }
+            None => self.engine.dispatch(request, self.session).await,
+        }
+    }
+}
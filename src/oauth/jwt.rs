@@ -0,0 +1,189 @@
+//! JWT access token validation.
+
+use {
+    crate::{
+        auth::{bearer_token, AuthError, Authenticator, Principal},
+        handler::ConnectionInfo,
+    },
+    anyhow::{Context, Result},
+    async_trait::async_trait,
+    jsonwebtoken::{jwk::JwkSet, Algorithm, DecodingKey, Validation},
+    serde_json::Value,
+    std::path::Path,
+    tracing::debug,
+};
+
+/// A JSON Web Key Set holding the public keys tokens are verified against.
+#[derive(Debug, Clone)]
+pub struct Jwks {
+    keys: JwkSet,
+}
+
+impl Jwks {
+    /// Parse a JWKS document.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let keys = serde_json::from_str(json).context("Invalid JWKS document")?;
+        Ok(Self { keys })
+    }
+
+    /// Load a JWKS document from a file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Could not read JWKS file {}", path.display()))?;
+        Self::from_json(&json)
+    }
+
+    /// Fetch a JWKS document, usually the `jwks_uri` of the authorization server.
+    ///
+    /// The keys are fetched once; build a new authenticator to pick up rotated keys.
+    pub async fn from_url(url: &str) -> Result<Self> {
+        let keys = reqwest::get(url)
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .with_context(|| format!("Could not fetch JWKS from {url}"))?
+            .json()
+            .await
+            .with_context(|| format!("Invalid JWKS document at {url}"))?;
+        Ok(Self { keys })
+    }
+
+    /// Pick the key a token was signed with: by `kid`, or the only key if the token has none.
+    fn key_for(&self, kid: Option<&str>) -> Option<&jsonwebtoken::jwk::Jwk> {
+        match kid {
+            Some(kid) => self.keys.find(kid),
+            None if self.keys.keys.len() == 1 => self.keys.keys.first(),
+            None => None,
+        }
+    }
+}
+
+/// Authenticates requests carrying a JWT access token in `Authorization: Bearer`.
+///
+/// A token is accepted when its signature verifies against the JWKS, it was
+/// issued by `issuer` for `audience`, and it has not expired. The principal's
+/// ID is the `sub` claim (or `client_id` for client-credential tokens), its
+/// scopes come from the space-separated `scope` claim (or a `scp` array) and
+/// its claims are the full token payload.
+///
+/// Only asymmetric signature algorithms are accepted.
+#[derive(Debug, Clone)]
+pub struct JwtAuthenticator {
+    jwks: Jwks,
+    issuer: String,
+    audience: String,
+    leeway: u64,
+    resource_metadata: Option<String>,
+}
+
+impl JwtAuthenticator {
+    const ALGORITHMS: [Algorithm; 8] = [
+        Algorithm::RS256,
+        Algorithm::RS384,
+        Algorithm::RS512,
+        Algorithm::PS256,
+        Algorithm::PS384,
+        Algorithm::PS512,
+        Algorithm::ES256,
+        Algorithm::ES384,
+    ];
+
+    /// Create an authenticator accepting tokens from `issuer` for `audience`.
+    ///
+    /// # Parameters
+    ///
+    /// - `jwks`: Public keys of the authorization server
+    /// - `issuer`: Expected `iss` claim
+    /// - `audience`: Expected `aud` claim, normally the canonical URI of this server
+    pub fn new(jwks: Jwks, issuer: impl Into<String>, audience: impl Into<String>) -> Self {
+        Self {
+            jwks,
+            issuer: issuer.into(),
+            audience: audience.into(),
+            leeway: 60,
+            resource_metadata: None,
+        }
+    }
+
+    /// Set the clock skew tolerated for `exp` and `nbf`, in seconds (default 60).
+    pub fn with_leeway(mut self, seconds: u64) -> Self {
+        self.leeway = seconds;
+        self
+    }
+
+    /// Point clients at the protected resource metadata in every 401 challenge.
+    pub fn with_resource_metadata(mut self, url: impl Into<String>) -> Self {
+        self.resource_metadata = Some(url.into());
+        self
+    }
+
+    /// Validate a token and build the principal it was issued to.
+    pub fn validate(&self, token: &str) -> Result<Principal, AuthError> {
+        let invalid = |message: String| AuthError::InvalidCredentials(message);
+
+        let header = jsonwebtoken::decode_header(token)
+            .map_err(|e| invalid(format!("malformed token: {e}")))?;
+        if !Self::ALGORITHMS.contains(&header.alg) {
+            return Err(invalid(format!("unsupported algorithm {:?}", header.alg)));
+        }
+        let jwk = self
+            .jwks
+            .key_for(header.kid.as_deref())
+            .ok_or_else(|| invalid("no matching signing key".to_string()))?;
+        if let Some(key_alg) = jwk.common.key_algorithm {
+            if key_alg.to_string() != format!("{:?}", header.alg) {
+                return Err(invalid("algorithm does not match signing key".to_string()));
+            }
+        }
+        let key = DecodingKey::from_jwk(jwk)
+            .map_err(|e| invalid(format!("unusable signing key: {e}")))?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.leeway = self.leeway;
+        validation.set_issuer(&[&self.issuer]);
+        validation.set_audience(&[&self.audience]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud"]);
+
+        let claims = jsonwebtoken::decode::<Value>(token, &key, &validation)
+            .map_err(|e| invalid(e.to_string()))?
+            .claims;
+        let id = claims["sub"]
+            .as_str()
+            .or_else(|| claims["client_id"].as_str())
+            .ok_or_else(|| invalid("token has no subject".to_string()))?
+            .to_string();
+        let scopes = match (&claims["scope"], &claims["scp"]) {
+            (Value::String(scope), _) | (_, Value::String(scope)) => {
+                scope.split_whitespace().map(str::to_string).collect()
+            }
+            (_, Value::Array(scopes)) => scopes
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect(),
+            _ => Vec::new(),
+        };
+        Ok(Principal { id, scopes, claims })
+    }
+}
+
+#[async_trait]
+impl Authenticator for JwtAuthenticator {
+    async fn authenticate(&self, connection: &ConnectionInfo) -> Result<Principal, AuthError> {
+        let token = bearer_token(&connection.headers).ok_or(AuthError::MissingCredentials)?;
+        let principal = self.validate(token)?;
+        debug!("Validated access token for {}", principal.id);
+        Ok(principal)
+    }
+
+    fn challenge(&self, error: &AuthError) -> String {
+        let mut challenge = r#"Bearer realm="mcp""#.to_string();
+        if let Some(url) = &self.resource_metadata {
+            challenge.push_str(&format!(r#", resource_metadata="{url}""#));
+        }
+        if let AuthError::InvalidCredentials(_) = error {
+            challenge.push_str(r#", error="invalid_token""#);
+        }
+        challenge
+    }
+}
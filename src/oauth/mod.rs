@@ -0,0 +1,100 @@
+//! MCP OAuth Resource Server
+//!
+//! This module lets a server act as an OAuth 2.1 protected resource, as the MCP
+//! authorization spec requires for remote deployments:
+//!
+//! - `ProtectedResourceMetadata` is served at
+//!   `/.well-known/oauth-protected-resource` (RFC 9728) so clients can discover
+//!   which authorization server issues tokens for this server.
+//! - `JwtAuthenticator` validates JWT access tokens locally: the signature
+//!   against a JWKS, then the `iss`, `aud` and `exp` claims. The token's scopes
+//!   become the scopes of the `Principal`. It needs the `oauth` feature.
+//!
+//! Tools can then require scopes with `McpServerBuilder::with_tool_scopes`.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use solidmcp::oauth::{Jwks, JwtAuthenticator, ProtectedResourceMetadata};
+//!
+//! let jwks = Jwks::from_url("https://auth.example.com/.well-known/jwks.json").await?;
+//! let authenticator = JwtAuthenticator::new(jwks, "https://auth.example.com", "https://mcp.example.com/mcp")
+//!     .with_resource_metadata("https://mcp.example.com/.well-known/oauth-protected-resource");
+//! let metadata = ProtectedResourceMetadata::new("https://mcp.example.com/mcp")
+//!     .with_authorization_server("https://auth.example.com")
+//!     .with_scopes_supported(["files:read", "files:write"]);
+//! ```
+
+#[cfg(feature = "oauth")]
+mod jwt;
+
+#[cfg(feature = "oauth")]
+pub use jwt::{Jwks, JwtAuthenticator};
+
+use serde::{Deserialize, Serialize};
+
+/// Path at which the protected resource metadata is served.
+pub const PROTECTED_RESOURCE_METADATA_PATH: &str = "/.well-known/oauth-protected-resource";
+
+/// OAuth 2.0 Protected Resource Metadata (RFC 9728).
+///
+/// # Fields
+///
+/// - `resource`: The canonical URI of this MCP server, used as token audience
+/// - `authorization_servers`: Issuers clients can obtain tokens from
+/// - `scopes_supported`: Scopes this server understands
+/// - `bearer_methods_supported`: How tokens are presented, always `header`
+/// - `resource_name`: Human-readable name of the server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProtectedResourceMetadata {
+    /// Canonical URI of the protected resource
+    pub resource: String,
+    /// Issuer URLs of the authorization servers
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authorization_servers: Vec<String>,
+    /// Scopes clients can request
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes_supported: Vec<String>,
+    /// Ways a bearer token can be presented
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bearer_methods_supported: Vec<String>,
+    /// Human-readable name of the resource
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_name: Option<String>,
+}
+
+impl ProtectedResourceMetadata {
+    /// Create metadata for the resource at `resource`.
+    pub fn new(resource: impl Into<String>) -> Self {
+        Self {
+            resource: resource.into(),
+            authorization_servers: Vec::new(),
+            scopes_supported: Vec::new(),
+            bearer_methods_supported: vec!["header".to_string()],
+            resource_name: None,
+        }
+    }
+
+    /// Add an authorization server (issuer URL) clients can obtain tokens from.
+    pub fn with_authorization_server(mut self, issuer: impl Into<String>) -> Self {
+        self.authorization_servers.push(issuer.into());
+        self
+    }
+
+    /// Advertise the scopes this server understands.
+    pub fn with_scopes_supported<I, S>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.scopes_supported
+            .extend(scopes.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set a human-readable name for the server.
+    pub fn with_resource_name(mut self, name: impl Into<String>) -> Self {
+        self.resource_name = Some(name.into());
+        self
+    }
+}
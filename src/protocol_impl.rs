@@ -4,9 +4,11 @@
 
 use {
     super::protocol::McpProtocol,
+    super::session_store::{SessionData, SessionExtensions},
     super::tools::McpTools,
     anyhow::Result,
     serde_json::{json, Value},
+    std::collections::BTreeSet,
     thiserror::Error,
     tracing::{debug, error, info},
 };
@@ -21,15 +23,56 @@ pub enum McpError {
     NotInitialized,
     #[error("Invalid params: {0}")]
     InvalidParams(String),
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+    #[error("Busy: {0}")]
+    Busy(String),
     #[error("Internal error: {0}")]
     Internal(String),
 }
 
+impl McpError {
+    /// The JSON-RPC error code for this error
+    pub fn code(&self) -> i32 {
+        match self {
+            McpError::UnknownMethod(_) | McpError::UnknownTool(_) => -32601,
+            McpError::NotInitialized => -32002,
+            McpError::InvalidParams(_) => -32602,
+            McpError::Forbidden(_) => -32003,
+            McpError::Busy(_) => -32004,
+            McpError::Internal(_) => -32603,
+        }
+    }
+}
+
+/// Log levels a client may pass to `logging/setLevel`, least severe first.
+pub const LOG_LEVELS: [&str; 8] = [
+    "debug",
+    "info",
+    "notice",
+    "warning",
+    "error",
+    "critical",
+    "alert",
+    "emergency",
+];
+
 pub struct McpProtocolHandlerImpl {
     protocol: McpProtocol,
     pub initialized: bool,
     pub client_info: Option<Value>,
+    pub client_capabilities: Option<Value>,
     pub protocol_version: Option<String>,
+    /// ID of the principal that initialized the session; only it may use the session
+    pub principal_id: Option<String>,
+    /// Minimum level of log messages the client asked for via `logging/setLevel`
+    pub log_level: Option<String>,
+    /// Resource URIs the client subscribed to via `resources/subscribe`
+    pub subscriptions: BTreeSet<String>,
+    /// Application data kept with the session
+    pub data: SessionData,
+    /// Typed in-memory values kept with the session, cleared when it is dropped
+    pub extensions: SessionExtensions,
 }
 
 impl McpProtocolHandlerImpl {
@@ -39,7 +82,13 @@ impl McpProtocolHandlerImpl {
             protocol: McpProtocol::new(),
             initialized: false,
             client_info: None,
+            client_capabilities: None,
             protocol_version: None,
+            principal_id: None,
+            log_level: None,
+            subscriptions: BTreeSet::new(),
+            data: SessionData::new(),
+            extensions: SessionExtensions::new(),
         }
     }
 
@@ -48,7 +97,13 @@ impl McpProtocolHandlerImpl {
             protocol: McpProtocol::new(),
             initialized: true,
             client_info,
+            client_capabilities: None,
             protocol_version,
+            principal_id: None,
+            log_level: None,
+            subscriptions: BTreeSet::new(),
+            data: SessionData::new(),
+            extensions: SessionExtensions::new(),
         }
     }
 }
@@ -97,6 +152,9 @@ impl McpProtocolHandlerImpl {
             "notifications/cancel" => self.handle_cancel(params).await,
             "notifications/initialized" => self.handle_initialized_notification().await,
             "notifications/message" => self.handle_logging_notification(params).await,
+            "logging/setLevel" => self.handle_set_log_level(params).await,
+            "resources/subscribe" => self.handle_resource_subscription(params, true).await,
+            "resources/unsubscribe" => self.handle_resource_subscription(params, false).await,
             _ => {
                 error!("[PROTOCOL] Unknown method: {:?} (id={:?})", method, id);
                 Err(McpError::UnknownMethod(method.to_string()).into())
@@ -126,6 +184,8 @@ impl McpProtocolHandlerImpl {
                         }
                         McpError::NotInitialized => (-32002, "Not initialized"),
                         McpError::InvalidParams(msg) => (-32602, msg.as_str()),
+                        McpError::Forbidden(msg) => (-32003, msg.as_str()),
+                        McpError::Busy(msg) => (-32004, msg.as_str()),
                         McpError::Internal(msg) => (-32603, msg.as_str()),
                     }
                 } else {
@@ -175,6 +235,7 @@ impl McpProtocolHandlerImpl {
             // Reset state for clean re-initialization
             self.initialized = false;
             self.client_info = None;
+            self.client_capabilities = None;
             self.protocol_version = None;
             info!("🔄 [INIT] State reset for re-initialization");
         }
@@ -187,6 +248,9 @@ impl McpProtocolHandlerImpl {
             info!("📋 [INIT] No client info provided");
         }
 
+        // Store client capabilities if provided
+        self.client_capabilities = params.get("capabilities").cloned();
+
         // Check protocol version compatibility
         if let Some(protocol_version) = params.get("protocolVersion") {
             let client_version = protocol_version.as_str().unwrap_or("");
@@ -341,6 +405,38 @@ impl McpProtocolHandlerImpl {
         Ok(json!({}))
     }
 
+    /// Handle logging/setLevel by remembering the level for this session
+    async fn handle_set_log_level(&mut self, params: Value) -> Result<Value> {
+        if !self.initialized {
+            return Err(McpError::NotInitialized.into());
+        }
+        let level = params["level"].as_str().ok_or_else(|| {
+            McpError::InvalidParams("Missing required 'level' field".to_string())
+        })?;
+        if !LOG_LEVELS.contains(&level) {
+            return Err(McpError::InvalidParams(format!("Unknown log level: {level}")).into());
+        }
+        debug!("Client set log level to {}", level);
+        self.log_level = Some(level.to_string());
+        Ok(json!({}))
+    }
+
+    /// Handle resources/subscribe and resources/unsubscribe
+    async fn handle_resource_subscription(&mut self, params: Value, subscribe: bool) -> Result<Value> {
+        if !self.initialized {
+            return Err(McpError::NotInitialized.into());
+        }
+        let uri = params["uri"].as_str().ok_or_else(|| {
+            McpError::InvalidParams("Missing required 'uri' field".to_string())
+        })?;
+        if subscribe {
+            self.subscriptions.insert(uri.to_string());
+        } else {
+            self.subscriptions.remove(uri);
+        }
+        Ok(json!({}))
+    }
+
     /// Handle logging notification
     async fn handle_logging_notification(&mut self, params: Value) -> Result<Value> {
         let level = params
@@ -366,3 +462,10 @@ impl Default for McpProtocolHandlerImpl {
         Self::new()
     }
 }
+
+impl Drop for McpProtocolHandlerImpl {
+    fn drop(&mut self) {
+        // Tools may still hold clones of the map; its values go with the session
+        self.extensions.clear();
+    }
+}
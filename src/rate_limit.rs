@@ -0,0 +1,342 @@
+//! MCP Rate Limiting
+//!
+//! This module provides `RateLimiter`, a [`Middleware`] that throttles requests
+//! with token buckets and optionally enforces daily quotas on tool calls.
+//!
+//! Limits are keyed by session, by principal or by tool name:
+//!
+//! - `limit` applies to every request (notifications are never limited)
+//! - `limit_tool` applies only to `tools/call` requests for one tool
+//! - `daily_quota` caps the number of tool calls per key and UTC day
+//!
+//! Requests over a limit are answered with a JSON-RPC error (code -32029)
+//! whose `data.retryAfter` says how many seconds to wait before retrying.
+//!
+//! # Example
+//!
+//! ```rust
+//! use solidmcp::rate_limit::{LimitKey, RateLimit, RateLimiter};
+//!
+//! let limiter = RateLimiter::new()
+//!     .limit(LimitKey::Session, RateLimit::per_minute(120))
+//!     .limit_tool("render_video", LimitKey::Principal, RateLimit::per_hour(10))
+//!     .daily_quota(LimitKey::Principal, 1_000);
+//!
+//! // Read and reset usage counters from billing code
+//! let usage = limiter.quota_usage();
+//! ```
+
+use {
+    crate::{
+        handler::McpContext,
+        middleware::{McpRequest, Middleware, Next},
+    },
+    anyhow::Result,
+    async_trait::async_trait,
+    serde_json::{json, Value},
+    std::{
+        collections::HashMap,
+        sync::{Arc, Mutex, PoisonError},
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    },
+    tokio::time::Instant,
+    tracing::debug,
+};
+
+/// JSON-RPC error code of requests rejected by a rate limit or quota.
+pub const RATE_LIMITED: i32 = -32029;
+
+/// Buckets are pruned once there are more than this many.
+const MAX_IDLE_BUCKETS: usize = 1024;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// What a limit or quota is counted against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LimitKey {
+    /// Each session has its own allowance
+    Session,
+    /// Each authenticated principal has its own allowance, shared by all its
+    /// sessions. Unauthenticated requests are counted by session instead.
+    Principal,
+    /// One allowance shared by every caller
+    Global,
+}
+
+impl LimitKey {
+    fn resolve(self, context: &McpContext) -> String {
+        let session = || format!("session:{}", context.session_id.as_deref().unwrap_or(""));
+        match self {
+            LimitKey::Session => session(),
+            LimitKey::Principal => context
+                .principal
+                .as_ref()
+                .map(|principal| format!("principal:{}", principal.id))
+                .unwrap_or_else(session),
+            LimitKey::Global => "global".to_string(),
+        }
+    }
+}
+
+/// A token bucket: up to `burst` requests at once, refilled at `requests` per `period`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    requests: u32,
+    period: Duration,
+    burst: u32,
+}
+
+impl RateLimit {
+    /// Allow `requests` per `period`, all of which may arrive at once.
+    pub fn new(requests: u32, period: Duration) -> Self {
+        Self {
+            requests: requests.max(1),
+            period,
+            burst: requests.max(1),
+        }
+    }
+
+    /// Allow `requests` per second.
+    pub fn per_second(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(1))
+    }
+
+    /// Allow `requests` per minute.
+    pub fn per_minute(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(60))
+    }
+
+    /// Allow `requests` per hour.
+    pub fn per_hour(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(3600))
+    }
+
+    /// Limit how many requests may arrive at once, independent of the rate.
+    pub fn with_burst(mut self, burst: u32) -> Self {
+        self.burst = burst.max(1);
+        self
+    }
+
+    fn tokens_per_second(&self) -> f64 {
+        f64::from(self.requests) / self.period.as_secs_f64()
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    /// Refill the bucket, returning how long until it holds a whole token.
+    fn refill(&mut self, limit: &RateLimit, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * limit.tokens_per_second()).min(f64::from(limit.burst));
+        self.updated = now;
+        if self.tokens >= 1.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((1.0 - self.tokens) / limit.tokens_per_second())
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Rule {
+    tool: Option<String>,
+    key: LimitKey,
+    limit: RateLimit,
+}
+
+#[derive(Debug, Default)]
+struct UsageState {
+    day: u64,
+    calls: HashMap<String, u64>,
+}
+
+/// Shared daily usage counters of a [`RateLimiter`]'s quota.
+///
+/// Counters are keyed like the quota: `session:<id>`, `principal:<id>` or
+/// `global`. They start from zero at every UTC midnight.
+#[derive(Debug, Clone, Default)]
+pub struct QuotaUsage {
+    state: Arc<Mutex<UsageState>>,
+}
+
+impl QuotaUsage {
+    fn lock(&self) -> std::sync::MutexGuard<'_, UsageState> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let today = today();
+        if state.day != today {
+            state.day = today;
+            state.calls.clear();
+        }
+        state
+    }
+
+    /// Get the number of tool calls counted against `key` today.
+    pub fn usage(&self, key: &str) -> u64 {
+        self.lock().calls.get(key).copied().unwrap_or(0)
+    }
+
+    /// Get today's usage of every key that made a tool call.
+    pub fn snapshot(&self) -> HashMap<String, u64> {
+        self.lock().calls.clone()
+    }
+
+    /// Reset the usage of one key, returning what it was.
+    pub fn reset(&self, key: &str) -> u64 {
+        self.lock().calls.remove(key).unwrap_or(0)
+    }
+
+    /// Reset the usage of every key.
+    pub fn reset_all(&self) {
+        self.lock().calls.clear();
+    }
+}
+
+fn unix_seconds() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+fn today() -> u64 {
+    unix_seconds() / SECONDS_PER_DAY
+}
+
+/// Middleware enforcing token-bucket rate limits and daily tool call quotas.
+///
+/// A request must pass every limit that applies to it; tokens are only taken
+/// when it does, so a rejected request does not count against any limit.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    rules: Vec<Rule>,
+    buckets: Mutex<HashMap<(usize, String), Bucket>>,
+    quota: Option<(LimitKey, u64)>,
+    usage: QuotaUsage,
+}
+
+impl RateLimiter {
+    /// Create a limiter with no limits yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit every request, counted per `key`.
+    pub fn limit(mut self, key: LimitKey, limit: RateLimit) -> Self {
+        self.rules.push(Rule {
+            tool: None,
+            key,
+            limit,
+        });
+        self
+    }
+
+    /// Limit calls to one tool, counted per `key`.
+    pub fn limit_tool(mut self, tool: impl Into<String>, key: LimitKey, limit: RateLimit) -> Self {
+        self.rules.push(Rule {
+            tool: Some(tool.into()),
+            key,
+            limit,
+        });
+        self
+    }
+
+    /// Allow at most `calls` tool calls per `key` and UTC day.
+    pub fn daily_quota(mut self, key: LimitKey, calls: u64) -> Self {
+        self.quota = Some((key, calls));
+        self
+    }
+
+    /// Get a handle to the quota's usage counters, e.g. for billing.
+    pub fn quota_usage(&self) -> QuotaUsage {
+        self.usage.clone()
+    }
+
+    /// Take a token from every bucket that applies, or say how long to wait.
+    pub(crate) fn acquire(&self, request: &McpRequest) -> Result<(), (String, Duration)> {
+        let tool = (request.method == "tools/call")
+            .then(|| request.name())
+            .flatten();
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let mut keys = Vec::new();
+        let mut wait = Duration::ZERO;
+        for (index, rule) in self.rules.iter().enumerate() {
+            if rule.tool.is_some() && rule.tool.as_deref() != tool {
+                continue;
+            }
+            let key = (index, rule.key.resolve(&request.context));
+            let bucket = buckets.entry(key.clone()).or_insert(Bucket {
+                tokens: f64::from(rule.limit.burst),
+                updated: now,
+            });
+            wait = wait.max(bucket.refill(&rule.limit, now));
+            keys.push(key);
+        }
+        if !wait.is_zero() {
+            return Err(("Rate limit exceeded".to_string(), wait));
+        }
+
+        if let (Some(_), Some((key, calls))) = (tool, self.quota) {
+            let mut usage = self.usage.lock();
+            let used = usage
+                .calls
+                .entry(key.resolve(&request.context))
+                .or_insert(0);
+            if *used >= calls {
+                let until_midnight = SECONDS_PER_DAY - unix_seconds() % SECONDS_PER_DAY;
+                return Err((
+                    "Daily quota exceeded".to_string(),
+                    Duration::from_secs(until_midnight),
+                ));
+            }
+            *used += 1;
+        }
+
+        for key in keys {
+            if let Some(bucket) = buckets.get_mut(&key) {
+                bucket.tokens -= 1.0;
+            }
+        }
+        if buckets.len() > MAX_IDLE_BUCKETS {
+            // Full buckets carry no state worth keeping
+            let rules = &self.rules;
+            buckets.retain(|(index, _), bucket| {
+                let limit = &rules[*index].limit;
+                bucket.refill(limit, now);
+                bucket.tokens < f64::from(limit.burst)
+            });
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Middleware for RateLimiter {
+    async fn handle(&self, request: McpRequest, next: Next<'_>) -> Result<Value> {
+        if request.id().is_none() {
+            return next.run(request).await;
+        }
+        match self.acquire(&request) {
+            Ok(()) => next.run(request).await,
+            Err((message, wait)) => {
+                let retry_after = wait.as_secs_f64().ceil() as u64;
+                debug!(
+                    "{} for {} (retry after {}s)",
+                    message, request.method, retry_after
+                );
+                Ok(request.respond_error(
+                    RATE_LIMITED,
+                    &message,
+                    Some(json!({ "retryAfter": retry_after })),
+                ))
+            }
+        }
+    }
+}
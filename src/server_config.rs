@@ -0,0 +1,58 @@
+//! Server Configuration
+//!
+//! Where [`McpServer::serve`](crate::McpServer::serve) listens and whether it
+//! serves over TLS.
+//!
+//! # Example
+//!
+//! ```rust
+//! use solidmcp::server_config::ServerConfig;
+//! use solidmcp::tls::TlsConfig;
+//!
+//! // All IPv6 (and, on most systems, IPv4) interfaces
+//! let config = ServerConfig::new("[::]:8443".parse::<std::net::SocketAddr>()?)
+//!     .with_tls(TlsConfig::new("tls.crt", "tls.key"));
+//! let handle = server.serve(config).await?;
+//! ```
+
+use {crate::tls::TlsConfig, std::net::SocketAddr};
+
+/// Address and transport security of a server.
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    addr: SocketAddr,
+    tls: Option<TlsConfig>,
+}
+
+impl ServerConfig {
+    /// Listen on `addr` without TLS.
+    ///
+    /// # Parameters
+    /// - `addr`: The address to listen on, e.g. `0.0.0.0:3000` for every IPv4
+    ///   interface; port 0 lets the OS pick a free port
+    pub fn new(addr: impl Into<SocketAddr>) -> Self {
+        Self {
+            addr: addr.into(),
+            tls: None,
+        }
+    }
+
+    /// Serve HTTPS and secure WebSockets instead of plain HTTP.
+    ///
+    /// # Parameters
+    /// - `tls`: Certificate, key and client verification settings
+    pub fn with_tls(mut self, tls: TlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Get the address to listen on.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Get the TLS settings, if the server uses TLS.
+    pub fn tls(&self) -> Option<&TlsConfig> {
+        self.tls.as_ref()
+    }
+}
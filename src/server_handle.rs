@@ -0,0 +1,153 @@
+//! Running Server Handle
+//!
+//! [`McpServer::bind`](crate::McpServer::bind) starts serving in the
+//! background and returns a [`ServerHandle`]. The handle reports the address
+//! the server listens on, which lets tests bind port 0, and shuts the server
+//! down gracefully:
+//!
+//! 1. `/health/ready` starts failing (`ServerPhase::ShuttingDown`)
+//! 2. The listener stops accepting connections
+//! 3. WebSockets get a close frame once their current message is answered
+//! 4. In-flight requests may finish until the deadline; the rest are cancelled
+//!    with a `REQUEST_CANCELLED` error
+//! 5. The session-end hooks run with `SessionEndReason::Shutdown`
+//!
+//! # Example
+//!
+//! ```rust
+//! let handle = server.bind(([127, 0, 0, 1], 0)).await?;
+//! println!("Listening on {}", handle.local_addr());
+//!
+//! tokio::signal::ctrl_c().await?;
+//! handle.shutdown().await?;
+//! ```
+
+use {
+    crate::{health::ServerPhase, shared::McpProtocolEngine, tls::ReloadableTls},
+    anyhow::{anyhow, Result},
+    std::{net::SocketAddr, sync::Arc, time::Duration},
+    tokio::{sync::oneshot, task::JoinHandle, time::Instant},
+    tracing::{info, warn},
+};
+
+/// How long [`ServerHandle::shutdown`] lets in-flight requests finish.
+pub const DEFAULT_SHUTDOWN_DEADLINE: Duration = Duration::from_secs(30);
+
+/// Handle to a server running in the background.
+///
+/// Dropping the handle leaves the server running until the runtime shuts
+/// down; only [`shutdown`](Self::shutdown) stops it.
+pub struct ServerHandle {
+    local_addr: SocketAddr,
+    engine: Arc<McpProtocolEngine>,
+    tls: Option<Arc<ReloadableTls>>,
+    stop: oneshot::Sender<()>,
+    server: JoinHandle<()>,
+}
+
+impl ServerHandle {
+    pub(crate) fn new(
+        local_addr: SocketAddr,
+        engine: Arc<McpProtocolEngine>,
+        tls: Option<Arc<ReloadableTls>>,
+        stop: oneshot::Sender<()>,
+        server: JoinHandle<()>,
+    ) -> Self {
+        Self {
+            local_addr,
+            engine,
+            tls,
+            stop,
+            server,
+        }
+    }
+
+    /// Get the address the server listens on, with the port the OS picked
+    /// when binding port 0.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Read the TLS certificate, key and client CA files again.
+    ///
+    /// New connections use the new certificates; established connections keep
+    /// theirs. If the files are invalid the current certificates stay in use.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server does not use TLS or the files are invalid
+    pub fn reload_tls(&self) -> Result<()> {
+        self.tls
+            .as_ref()
+            .ok_or_else(|| anyhow!("Server on {} does not use TLS", self.local_addr))?
+            .reload()
+    }
+
+    /// Shut the server down, letting in-flight requests finish for up to
+    /// [`DEFAULT_SHUTDOWN_DEADLINE`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server task panicked
+    pub async fn shutdown(self) -> Result<()> {
+        self.shutdown_with_deadline(DEFAULT_SHUTDOWN_DEADLINE).await
+    }
+
+    /// Shut the server down, letting in-flight requests finish for up to `deadline`.
+    ///
+    /// # Parameters
+    ///
+    /// - `deadline`: How long to wait for in-flight requests before cancelling them
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server task panicked
+    pub async fn shutdown_with_deadline(self, deadline: Duration) -> Result<()> {
+        let until = Instant::now() + deadline;
+        info!("🛑 Shutting down MCP Server on {}", self.local_addr);
+        self.engine.readiness().set_phase(ServerPhase::ShuttingDown);
+        // The server may already have stopped, in which case nobody listens
+        let _ = self.stop.send(());
+
+        let cancelled = self.engine.drain(until).await;
+        if cancelled > 0 {
+            warn!(
+                "Cancelled {} requests still in flight at the shutdown deadline",
+                cancelled
+            );
+        }
+
+        // Connections that are still open past the deadline are dropped
+        let mut server = self.server;
+        match tokio::time::timeout_at(until, &mut server).await {
+            Ok(joined) => joined.map_err(|e| anyhow!("Server task failed: {}", e))?,
+            Err(_) => {
+                warn!("Dropping connections still open at the shutdown deadline");
+                server.abort();
+            }
+        }
+        info!("MCP Server on {} stopped", self.local_addr);
+        Ok(())
+    }
+
+    /// Wait until the server stops, which only happens if it fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server task panicked
+    pub async fn wait(self) -> Result<()> {
+        self.server
+            .await
+            .map_err(|e| anyhow!("Server task failed: {}", e))
+    }
+}
+
+/// Wait until [`ServerHandle::shutdown`] asks the server to stop.
+///
+/// Never returns if the handle was dropped instead, so dropping it leaves the
+/// server running.
+pub(crate) async fn stop_requested(stopped: oneshot::Receiver<()>) {
+    if stopped.await.is_err() {
+        std::future::pending::<()>().await;
+    }
+}
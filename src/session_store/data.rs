@@ -0,0 +1,104 @@
+//! JSON data kept with a session.
+
+use {
+    serde_json::{Map, Value},
+    std::{
+        fmt,
+        sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    },
+};
+
+/// Application data kept with a session as a JSON object.
+///
+/// Unlike [`SessionExtensions`](super::SessionExtensions), the data is saved
+/// with the rest of the session state, so a durable `SessionStore` keeps it
+/// across restarts and shares it between processes. Tools reach it through
+/// `NotificationCtx::session_data`, middleware through `Next::session_data`.
+/// Clones share the same map.
+///
+/// Changes are saved when the message that made them has been handled.
+///
+/// # Examples
+/// ```rust,ignore
+/// let data = notif.session_data();
+/// let visits = data.get("visits").and_then(|v| v.as_u64()).unwrap_or(0);
+/// data.insert("visits", json!(visits + 1));
+/// ```
+#[derive(Clone, Default)]
+pub struct SessionData {
+    values: Arc<RwLock<Map<String, Value>>>,
+}
+
+impl SessionData {
+    /// Create an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get a copy of the value stored under `key`.
+    pub fn get(&self, key: &str) -> Option<Value> {
+        self.read().get(key).cloned()
+    }
+
+    /// Store a value, replacing any previous value under `key`.
+    ///
+    /// # Returns
+    /// The previous value, if any
+    pub fn insert(&self, key: impl Into<String>, value: Value) -> Option<Value> {
+        self.write().insert(key.into(), value)
+    }
+
+    /// Remove the value stored under `key`.
+    ///
+    /// # Returns
+    /// The removed value, if any
+    pub fn remove(&self, key: &str) -> Option<Value> {
+        self.write().remove(key)
+    }
+
+    /// Change the map in place, e.g. to increment a counter without racing
+    /// other calls of the same session.
+    ///
+    /// # Returns
+    /// What `f` returned
+    pub fn update<R>(&self, f: impl FnOnce(&mut Map<String, Value>) -> R) -> R {
+        f(&mut self.write())
+    }
+
+    /// Get a copy of the whole map.
+    pub fn to_map(&self) -> Map<String, Value> {
+        self.read().clone()
+    }
+
+    /// Get the number of values stored.
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Check whether no values are stored.
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, Map<String, Value>> {
+        self.values.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Map<String, Value>> {
+        self.values.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl From<Map<String, Value>> for SessionData {
+    fn from(values: Map<String, Value>) -> Self {
+        Self {
+            values: Arc::new(RwLock::new(values)),
+        }
+    }
+}
+
+impl fmt::Debug for SessionData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.read().iter()).finish()
+    }
+}
@@ -0,0 +1,127 @@
+//! Typed per-session state.
+
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt,
+    sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+type AnyValue = Box<dyn Any + Send + Sync>;
+
+/// Typed values kept with a session, at most one per type.
+///
+/// Tools reach the map of their own session through `NotificationCtx::session`
+/// (middleware and handlers through `McpContext::session`), which makes it the
+/// place for per-client state such as a selected workspace, a login or the
+/// progress of a multi-step wizard. Clones share the same map.
+///
+/// The map lives as long as the session's entry in the session store. Its
+/// values are dropped when the session ends, expires, is evicted or is
+/// initialized again. Values are kept in memory only; use
+/// `Next::session_data` for data a durable store should persist.
+///
+/// # Examples
+/// ```rust
+/// #[derive(Clone)]
+/// struct Workspace(PathBuf);
+///
+/// // In one tool
+/// notif.session().insert(Workspace(input.path));
+///
+/// // In a later call from the same client
+/// let Some(Workspace(path)) = notif.session().get::<Workspace>() else {
+///     anyhow::bail!("select a workspace first");
+/// };
+/// ```
+#[derive(Clone, Default)]
+pub struct SessionExtensions {
+    values: Arc<RwLock<HashMap<TypeId, AnyValue>>>,
+}
+
+impl SessionExtensions {
+    /// Create an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get a copy of the value of type `T`, if one was inserted.
+    pub fn get<T: Clone + Send + Sync + 'static>(&self) -> Option<T> {
+        self.read()
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref::<T>())
+            .cloned()
+    }
+
+    /// Check whether a value of type `T` was inserted.
+    pub fn contains<T: Send + Sync + 'static>(&self) -> bool {
+        self.read().contains_key(&TypeId::of::<T>())
+    }
+
+    /// Store a value, replacing any previous value of the same type.
+    ///
+    /// # Returns
+    /// The previous value of type `T`, if any
+    pub fn insert<T: Send + Sync + 'static>(&self, value: T) -> Option<T> {
+        self.write()
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|previous| previous.downcast::<T>().ok())
+            .map(|previous| *previous)
+    }
+
+    /// Remove the value of type `T`.
+    ///
+    /// # Returns
+    /// The removed value, if any
+    pub fn remove<T: Send + Sync + 'static>(&self) -> Option<T> {
+        self.write()
+            .remove(&TypeId::of::<T>())
+            .and_then(|value| value.downcast::<T>().ok())
+            .map(|value| *value)
+    }
+
+    /// Change the value of type `T` in place, e.g. to advance a wizard without
+    /// racing other calls of the same session.
+    ///
+    /// # Returns
+    /// What `f` returned, or `None` if there is no value of type `T`
+    pub fn update<T: Send + Sync + 'static, R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        self.write()
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_mut::<T>())
+            .map(f)
+    }
+
+    /// Drop every value.
+    pub fn clear(&self) {
+        // Take the values out first so their destructors run outside the lock
+        let values = std::mem::take(&mut *self.write());
+        drop(values);
+    }
+
+    /// Get the number of values stored.
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Check whether no values are stored.
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<TypeId, AnyValue>> {
+        self.values.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, HashMap<TypeId, AnyValue>> {
+        self.values.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl fmt::Debug for SessionExtensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SessionExtensions")
+            .field("len", &self.len())
+            .finish()
+    }
+}
@@ -0,0 +1,415 @@
+//! Durable session store keeping one JSON file per session.
+
+use {
+    super::{EvictionHook, EvictionReason, SessionHandle, SessionSnapshot, SessionStore},
+    crate::protocol_impl::McpProtocolHandlerImpl,
+    anyhow::{bail, Context, Result},
+    async_trait::async_trait,
+    serde::{Deserialize, Serialize},
+    std::{
+        collections::HashMap,
+        io::ErrorKind,
+        path::{Path, PathBuf},
+        sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock},
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    },
+    tracing::{debug, warn},
+};
+
+const EXTENSION: &str = "json";
+/// Lock file that processes sharing the directory hold while changing it.
+const LOCK_FILE: &str = ".lock";
+
+/// Contents of a session file.
+#[derive(Serialize, Deserialize)]
+struct Record {
+    /// Changes on every save, so readers can tell whether their copy is current
+    revision: String,
+    #[serde(flatten)]
+    snapshot: SessionSnapshot,
+}
+
+/// A session loaded into this process.
+struct Cached {
+    session: SessionHandle,
+    /// Revision of the file when it was last read or written here
+    revision: Option<String>,
+}
+
+/// Session store persisting each session to a JSON file in a directory.
+///
+/// Sessions survive restarts, and several processes on the same host can
+/// share the directory: each message rereads the session's file and picks up
+/// changes saved by another process since this one last read or wrote it. Writes go
+/// to a temporary file that is renamed into place, so readers never see a
+/// partial file.
+///
+/// Saves and removals hold an advisory lock on the directory's `.lock` file
+/// and check the session's revision first. If another process saved or
+/// removed the session since this one read it, the save fails rather than
+/// overwrite those changes. Only initialized sessions are written.
+///
+/// Sessions expire after `idle_ttl` without messages (a day by default).
+/// Eviction hooks only run in the process that removed the file.
+pub struct FileSessionStore {
+    dir: PathBuf,
+    idle_ttl: Option<Duration>,
+    cache: Mutex<HashMap<String, Cached>>,
+    last_sweep: Mutex<SystemTime>,
+    hooks: RwLock<Vec<EvictionHook>>,
+}
+
+impl FileSessionStore {
+    /// Open a store in `dir`, creating the directory if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("creating session directory {}", dir.display()))?;
+        Ok(Self {
+            dir,
+            idle_ttl: Some(Duration::from_secs(24 * 3600)),
+            cache: Mutex::new(HashMap::new()),
+            last_sweep: Mutex::new(SystemTime::now()),
+            hooks: RwLock::new(Vec::new()),
+        })
+    }
+
+    /// Expire sessions that received no message for `ttl`.
+    pub fn with_idle_ttl(mut self, ttl: Duration) -> Self {
+        self.idle_ttl = Some(ttl);
+        self
+    }
+
+    /// Keep sessions until they are removed explicitly.
+    pub fn without_idle_ttl(mut self) -> Self {
+        self.idle_ttl = None;
+        self
+    }
+
+    /// Get the directory the session files are kept in.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Remove every expired session file now.
+    ///
+    /// Expired sessions are also swept as messages arrive, at most once a minute.
+    ///
+    /// # Returns
+    ///
+    /// The number of sessions removed
+    pub async fn sweep(&self) -> Result<usize> {
+        *self
+            .last_sweep
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = SystemTime::now();
+        let Some(ttl) = self.idle_ttl else {
+            return Ok(0);
+        };
+        let mut removed = 0;
+        let mut entries = tokio::fs::read_dir(&self.dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let Some(session_id) = session_id_of(&path) else {
+                continue;
+            };
+            if self.in_use(&session_id) {
+                continue;
+            }
+            let Ok(Some(record)) = read_record(&path).await else {
+                continue;
+            };
+            let idle = SystemTime::now()
+                .duration_since(UNIX_EPOCH + Duration::from_secs(record.snapshot.updated_at))
+                .unwrap_or_default();
+            if idle >= ttl && self.delete(&session_id, Some(record.revision)).await? {
+                removed += 1;
+                self.notify(&session_id, EvictionReason::Expired);
+            }
+        }
+        Ok(removed)
+    }
+
+    fn path(&self, session_id: &str) -> PathBuf {
+        self.dir
+            .join(format!("{}.{}", encode_id(session_id), EXTENSION))
+    }
+
+    fn cache(&self) -> MutexGuard<'_, HashMap<String, Cached>> {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// A session is in use while a message holds a clone of its handle.
+    fn in_use(&self, session_id: &str) -> bool {
+        self.cache()
+            .get(session_id)
+            .is_some_and(|cached| Arc::strong_count(&cached.session) > 1)
+    }
+
+    /// Delete a session's file, unless `revision` is given and the file has
+    /// been saved again since.
+    async fn delete(&self, session_id: &str, revision: Option<String>) -> Result<bool> {
+        let path = self.path(session_id);
+        let deleted = self
+            .locked(move || {
+                if revision.is_some() && revision_of(&path) != revision {
+                    return Ok(false);
+                }
+                match std::fs::remove_file(&path) {
+                    Ok(()) => Ok(true),
+                    Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+                    Err(e) => Err(e.into()),
+                }
+            })
+            .await?;
+        if deleted {
+            self.cache().remove(session_id);
+        }
+        Ok(deleted)
+    }
+
+    /// Run `change` while holding the directory's lock file.
+    async fn locked<T: Send + 'static>(
+        &self,
+        change: impl FnOnce() -> Result<T> + Send + 'static,
+    ) -> Result<T> {
+        let path = self.dir.join(LOCK_FILE);
+        tokio::task::spawn_blocking(move || {
+            let lock = std::fs::OpenOptions::new()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(&path)
+                .with_context(|| format!("opening lock file {}", path.display()))?;
+            lock.lock()
+                .with_context(|| format!("locking {}", path.display()))?;
+            // Unlocked when the file is closed
+            change()
+        })
+        .await?
+    }
+
+    /// Get the cached session, reloading it if its file was changed by
+    /// another process since this one last read or wrote it.
+    async fn load(&self, session_id: &str) -> Option<SessionHandle> {
+        let cached = self
+            .cache()
+            .get(session_id)
+            .map(|cached| cached.session.clone());
+        let Some(session) = cached else {
+            let record = self.read(session_id).await?;
+            let session = Arc::new(tokio::sync::Mutex::new(record.snapshot.restore()));
+            return Some(
+                self.cache()
+                    .entry(session_id.to_string())
+                    .or_insert(Cached {
+                        session,
+                        revision: Some(record.revision),
+                    })
+                    .session
+                    .clone(),
+            );
+        };
+
+        // Compare under the session's lock, so a message of this process that
+        // is still being handled saves its changes first
+        let mut state = session.lock().await;
+        let Some(record) = self.read(session_id).await else {
+            // Another process removed the session
+            self.cache().remove(session_id);
+            return None;
+        };
+        let seen = self
+            .cache()
+            .get(session_id)
+            .and_then(|cached| cached.revision.clone());
+        if seen.as_ref() != Some(&record.revision) {
+            // Another process saved the session; its in-memory values stay here
+            let extensions = std::mem::take(&mut state.extensions);
+            *state = record.snapshot.restore();
+            state.extensions = extensions;
+            if let Some(cached) = self.cache().get_mut(session_id) {
+                cached.revision = Some(record.revision);
+            }
+        }
+        drop(state);
+        Some(session)
+    }
+
+    /// Read a session's file, treating a file that cannot be read as missing.
+    async fn read(&self, session_id: &str) -> Option<Record> {
+        match read_record(&self.path(session_id)).await {
+            Ok(record) => record,
+            Err(e) => {
+                // The session starts over; saving it overwrites the bad file
+                warn!("Failed to load session {}: {}", session_id, e);
+                None
+            }
+        }
+    }
+
+    fn sweep_due(&self) -> bool {
+        let last_sweep = self
+            .last_sweep
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        last_sweep.elapsed().unwrap_or_default() >= Duration::from_secs(60)
+    }
+
+    fn notify(&self, session_id: &str, reason: EvictionReason) {
+        debug!("Session {} left the store: {:?}", session_id, reason);
+        let hooks = self.hooks.read().unwrap_or_else(PoisonError::into_inner);
+        for hook in hooks.iter() {
+            hook(session_id, reason);
+        }
+    }
+}
+
+#[async_trait]
+impl SessionStore for FileSessionStore {
+    async fn get(&self, session_id: &str) -> Option<SessionHandle> {
+        if self.sweep_due() {
+            if let Err(e) = self.sweep().await {
+                warn!(
+                    "Failed to sweep session directory {}: {}",
+                    self.dir.display(),
+                    e
+                );
+            }
+        }
+        self.load(session_id).await
+    }
+
+    async fn save(
+        &self,
+        session_id: &str,
+        session: &SessionHandle,
+        state: &McpProtocolHandlerImpl,
+    ) -> Result<()> {
+        if !state.initialized {
+            return Ok(());
+        }
+        let path = self.path(session_id);
+        let record = Record {
+            revision: uuid::Uuid::new_v4().to_string(),
+            snapshot: SessionSnapshot::capture(state),
+        };
+        let contents = serde_json::to_vec(&record)?;
+        let seen = self
+            .cache()
+            .get(session_id)
+            .and_then(|cached| cached.revision.clone());
+        let temp = path.with_extension(format!("{}.{}.tmp", EXTENSION, record.revision));
+        let id = session_id.to_string();
+        self.locked(move || {
+            if revision_of(&path) != seen {
+                bail!("session {} was changed by another process", id);
+            }
+            std::fs::write(&temp, contents)?;
+            if let Err(e) = std::fs::rename(&temp, &path) {
+                let _ = std::fs::remove_file(&temp);
+                return Err(e.into());
+            }
+            Ok(())
+        })
+        .await?;
+        self.cache()
+            .entry(session_id.to_string())
+            .or_insert_with(|| Cached {
+                session: session.clone(),
+                revision: None,
+            })
+            .revision = Some(record.revision);
+        Ok(())
+    }
+
+    async fn remove(&self, session_id: &str) -> bool {
+        match self.delete(session_id, None).await {
+            Ok(true) => {
+                self.notify(session_id, EvictionReason::Removed);
+                true
+            }
+            Ok(false) => false,
+            Err(e) => {
+                warn!("Failed to remove session {}: {}", session_id, e);
+                false
+            }
+        }
+    }
+
+    async fn session_count(&self) -> usize {
+        let Ok(mut entries) = tokio::fs::read_dir(&self.dir).await else {
+            return 0;
+        };
+        let mut count = 0;
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            if session_id_of(&entry.path()).is_some() {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    fn on_evict(&self, hook: EvictionHook) {
+        self.hooks
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(hook);
+    }
+}
+
+async fn read_record(path: &Path) -> Result<Option<Record>> {
+    match tokio::fs::read(path).await {
+        Ok(bytes) => {
+            Ok(Some(serde_json::from_slice(&bytes).with_context(|| {
+                format!("parsing session file {}", path.display())
+            })?))
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Get the revision of a session file, if it exists and can be parsed.
+fn revision_of(path: &Path) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    serde_json::from_slice::<Record>(&bytes)
+        .ok()
+        .map(|record| record.revision)
+}
+
+/// Turn a session ID into a file name, escaping anything but `[A-Za-z0-9_-]`.
+fn encode_id(session_id: &str) -> String {
+    let mut name = String::with_capacity(session_id.len());
+    for byte in session_id.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_' {
+            name.push(byte as char);
+        } else {
+            name.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    name
+}
+
+/// Recover the session ID from the path of a session file.
+fn session_id_of(path: &Path) -> Option<String> {
+    if path.extension()? != EXTENSION {
+        return None;
+    }
+    let name = path.file_stem()?.to_str()?;
+    let mut bytes = Vec::with_capacity(name.len());
+    let mut chars = name.bytes();
+    while let Some(byte) = chars.next() {
+        if byte == b'%' {
+            let hex = [chars.next()?, chars.next()?];
+            bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
@@ -0,0 +1,232 @@
+//! In-memory session store with idle expiry and LRU eviction.
+
+use {
+    super::{EvictionHook, EvictionReason, SessionHandle, SessionStore},
+    crate::protocol_impl::McpProtocolHandlerImpl,
+    anyhow::Result,
+    async_trait::async_trait,
+    std::{
+        collections::HashMap,
+        sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock},
+        time::Duration,
+    },
+    tokio::time::Instant,
+    tracing::debug,
+};
+
+/// Expired sessions are swept at most this often.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+
+struct Entry {
+    session: SessionHandle,
+    last_used: Instant,
+}
+
+impl Entry {
+    /// A session is in use while a message or its WebSocket holds a clone of
+    /// its handle.
+    fn in_use(&self) -> bool {
+        Arc::strong_count(&self.session) > 1
+    }
+}
+
+struct State {
+    sessions: HashMap<String, Entry>,
+    last_sweep: Instant,
+}
+
+/// Session store keeping every session in memory.
+///
+/// By default sessions expire after an hour without messages and at most
+/// 10,000 sessions are kept, evicting the least recently used beyond that.
+/// Sessions that are handling a message or held by an open WebSocket are
+/// never evicted.
+pub struct InMemorySessionStore {
+    state: Mutex<State>,
+    idle_ttl: Option<Duration>,
+    max_sessions: Option<usize>,
+    hooks: RwLock<Vec<EvictionHook>>,
+}
+
+impl Default for InMemorySessionStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemorySessionStore {
+    /// Create a store with the default limits.
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(State {
+                sessions: HashMap::new(),
+                last_sweep: Instant::now(),
+            }),
+            idle_ttl: Some(Duration::from_secs(3600)),
+            max_sessions: Some(10_000),
+            hooks: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Create a store that never evicts sessions on its own.
+    pub fn unbounded() -> Self {
+        Self {
+            idle_ttl: None,
+            max_sessions: None,
+            ..Self::new()
+        }
+    }
+
+    /// Expire sessions that received no message for `ttl`.
+    pub fn with_idle_ttl(mut self, ttl: Duration) -> Self {
+        self.idle_ttl = Some(ttl);
+        self
+    }
+
+    /// Keep at most `max` sessions, evicting the least recently used.
+    pub fn with_max_sessions(mut self, max: usize) -> Self {
+        self.max_sessions = Some(max.max(1));
+        self
+    }
+
+    /// Remove every expired session now.
+    ///
+    /// Expired sessions are also swept as messages arrive, so calling this is
+    /// only needed to release memory on an otherwise idle server.
+    ///
+    /// # Returns
+    ///
+    /// The number of sessions removed
+    pub fn sweep(&self) -> usize {
+        let expired = {
+            let mut state = self.lock();
+            self.take_expired(&mut state, Instant::now())
+        };
+        let count = expired.len();
+        self.notify(expired);
+        count
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn take_expired(&self, state: &mut State, now: Instant) -> Vec<(String, EvictionReason)> {
+        state.last_sweep = now;
+        let Some(ttl) = self.idle_ttl else {
+            return Vec::new();
+        };
+        let expired: Vec<String> = state
+            .sessions
+            .iter()
+            .filter(|(_, entry)| !entry.in_use() && now.duration_since(entry.last_used) >= ttl)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in &expired {
+            state.sessions.remove(id);
+        }
+        expired
+            .into_iter()
+            .map(|id| (id, EvictionReason::Expired))
+            .collect()
+    }
+
+    fn take_least_recently_used(&self, state: &mut State) -> Option<(String, EvictionReason)> {
+        let id = state
+            .sessions
+            .iter()
+            .filter(|(_, entry)| !entry.in_use())
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(id, _)| id.clone())?;
+        state.sessions.remove(&id);
+        Some((id, EvictionReason::CapacityExceeded))
+    }
+
+    /// Run the eviction hooks, outside the state lock.
+    fn notify(&self, evicted: Vec<(String, EvictionReason)>) {
+        if evicted.is_empty() {
+            return;
+        }
+        let hooks = self.hooks.read().unwrap_or_else(PoisonError::into_inner);
+        for (id, reason) in evicted {
+            debug!("Session {} left the store: {:?}", id, reason);
+            for hook in hooks.iter() {
+                hook(&id, reason);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl SessionStore for InMemorySessionStore {
+    async fn get(&self, session_id: &str) -> Option<SessionHandle> {
+        let now = Instant::now();
+        let mut evicted = Vec::new();
+        let session = {
+            let mut state = self.lock();
+            if now.duration_since(state.last_sweep) >= SWEEP_INTERVAL {
+                evicted = self.take_expired(&mut state, now);
+            }
+            state.sessions.get_mut(session_id).map(|entry| {
+                entry.last_used = now;
+                entry.session.clone()
+            })
+        };
+        self.notify(evicted);
+        session
+    }
+
+    async fn save(
+        &self,
+        session_id: &str,
+        session: &SessionHandle,
+        state: &McpProtocolHandlerImpl,
+    ) -> Result<()> {
+        if !state.initialized {
+            return Ok(());
+        }
+        let mut evicted = Vec::new();
+        {
+            let mut state = self.lock();
+            if state.sessions.contains_key(session_id) {
+                return Ok(());
+            }
+            if let Some(max) = self.max_sessions {
+                while state.sessions.len() >= max {
+                    match self.take_least_recently_used(&mut state) {
+                        Some(lru) => evicted.push(lru),
+                        None => break,
+                    }
+                }
+            }
+            state.sessions.insert(
+                session_id.to_string(),
+                Entry {
+                    session: session.clone(),
+                    last_used: Instant::now(),
+                },
+            );
+        }
+        self.notify(evicted);
+        Ok(())
+    }
+
+    async fn remove(&self, session_id: &str) -> bool {
+        let removed = self.lock().sessions.remove(session_id).is_some();
+        if removed {
+            self.notify(vec![(session_id.to_string(), EvictionReason::Removed)]);
+        }
+        removed
+    }
+
+    async fn session_count(&self) -> usize {
+        self.lock().sessions.len()
+    }
+
+    fn on_evict(&self, hook: EvictionHook) {
+        self.hooks
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(hook);
+    }
+}
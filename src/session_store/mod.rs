@@ -0,0 +1,209 @@
+//! MCP Session Store
+//!
+//! This module defines the `SessionStore` trait, which holds the per-session
+//! protocol state (`McpProtocolHandlerImpl`) of `McpProtocolEngine`, the
+//! default `InMemorySessionStore` and the durable `FileSessionStore`, as well
+//! as `SessionExtensions` and `SessionData`, the typed and JSON values tools
+//! keep with a session.
+//!
+//! Sessions leave a store when they are removed explicitly (the WebSocket
+//! transport removes its session when the connection closes), when they sit
+//! idle for longer than the store's TTL, or when the store is full and they
+//! are the least recently used. Only initialized sessions are stored, and a
+//! session is never evicted while it handles a message or while its WebSocket
+//! is open. Eviction hooks registered with [`SessionStore::on_evict`] are told
+//! which session left and why.
+//!
+//! # Example
+//!
+//! ```rust
+//! use solidmcp::session_store::InMemorySessionStore;
+//! use std::{sync::Arc, time::Duration};
+//!
+//! let store = InMemorySessionStore::new()
+//!     .with_idle_ttl(Duration::from_secs(15 * 60))
+//!     .with_max_sessions(5_000);
+//! store.on_evict(Arc::new(|session_id, reason| {
+//!     tracing::info!("session {} left the store: {:?}", session_id, reason);
+//! }));
+//! ```
+
+mod data;
+mod extensions;
+mod file;
+mod memory;
+
+pub use data::SessionData;
+pub use extensions::SessionExtensions;
+pub use file::FileSessionStore;
+pub use memory::InMemorySessionStore;
+
+use {
+    crate::protocol_impl::McpProtocolHandlerImpl,
+    anyhow::Result,
+    async_trait::async_trait,
+    serde::{Deserialize, Serialize},
+    serde_json::{Map, Value},
+    std::{
+        collections::BTreeSet,
+        sync::Arc,
+        time::{SystemTime, UNIX_EPOCH},
+    },
+    tokio::sync::Mutex,
+};
+
+/// Shared, lockable protocol state of one session.
+pub type SessionHandle = Arc<Mutex<McpProtocolHandlerImpl>>;
+
+/// Why a session left a store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EvictionReason {
+    /// The session was idle for longer than the store's TTL
+    Expired,
+    /// The store was full and the session was the least recently used
+    CapacityExceeded,
+    /// The session was removed explicitly, e.g. because its WebSocket closed
+    Removed,
+}
+
+/// Serializable form of a session's state, as written by durable stores.
+///
+/// # Fields
+/// - `initialized`: Whether the client completed `initialize`
+/// - `protocol_version`, `client_info`, `client_capabilities`: What the client sent in `initialize`
+/// - `principal_id`: ID of the principal that initialized the session
+/// - `log_level`: Level set with `logging/setLevel`
+/// - `subscriptions`: URIs subscribed to with `resources/subscribe`
+/// - `data`: Application data kept with the session
+/// - `updated_at`: When the snapshot was taken, in seconds since the Unix epoch
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    pub initialized: bool,
+    pub protocol_version: Option<String>,
+    pub client_info: Option<Value>,
+    pub client_capabilities: Option<Value>,
+    #[serde(default)]
+    pub principal_id: Option<String>,
+    pub log_level: Option<String>,
+    #[serde(default)]
+    pub subscriptions: BTreeSet<String>,
+    #[serde(default)]
+    pub data: Map<String, Value>,
+    #[serde(default)]
+    pub updated_at: u64,
+}
+
+impl SessionSnapshot {
+    /// Take a snapshot of a session's state.
+    pub fn capture(session: &McpProtocolHandlerImpl) -> Self {
+        Self {
+            initialized: session.initialized,
+            protocol_version: session.protocol_version.clone(),
+            client_info: session.client_info.clone(),
+            client_capabilities: session.client_capabilities.clone(),
+            principal_id: session.principal_id.clone(),
+            log_level: session.log_level.clone(),
+            subscriptions: session.subscriptions.clone(),
+            data: session.data.to_map(),
+            updated_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        }
+    }
+
+    /// Rebuild the session's state from the snapshot.
+    pub fn restore(self) -> McpProtocolHandlerImpl {
+        let mut session = McpProtocolHandlerImpl::new();
+        session.initialized = self.initialized;
+        session.protocol_version = self.protocol_version;
+        session.client_info = self.client_info;
+        session.client_capabilities = self.client_capabilities;
+        session.principal_id = self.principal_id;
+        session.log_level = self.log_level;
+        session.subscriptions = self.subscriptions;
+        session.data = self.data.into();
+        session
+    }
+}
+
+/// Why a session ended, as reported to `McpHandler::on_session_end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SessionEndReason {
+    /// The client's connection closed (WebSocket)
+    Closed,
+    /// The session was idle for longer than the store's TTL
+    Expired,
+    /// The session store was full and evicted the session
+    Evicted,
+    /// The client deleted the session (HTTP `DELETE /mcp`)
+    Deleted,
+    /// The client sent `initialize` again; a new session starts right after
+    Reinitialized,
+    /// An operator terminated the session through the admin API
+    Terminated,
+    /// The server shut down (`ServerHandle::shutdown`)
+    Shutdown,
+}
+
+/// Callback told about every session that leaves a store.
+pub type EvictionHook = Arc<dyn Fn(&str, EvictionReason) + Send + Sync>;
+
+/// Storage for the protocol state of every session.
+///
+/// The engine calls [`get_or_create`](Self::get_or_create) for each message,
+/// holds the session's lock while the message is handled and then calls
+/// [`save`](Self::save), so stores that persist state can write it back.
+///
+/// Stores only hold initialized sessions: a message naming an unknown session
+/// gets a fresh session that `save` adds once `initialize` succeeds. Made-up
+/// session IDs therefore never take up room or push real sessions out.
+#[async_trait]
+pub trait SessionStore: Send + Sync {
+    /// Get a session the store holds.
+    ///
+    /// Counts as a use of the session for idle expiry and LRU eviction.
+    async fn get(&self, session_id: &str) -> Option<SessionHandle>;
+
+    /// Get a session's state, or a fresh session the store does not hold yet.
+    ///
+    /// # Default Implementation
+    ///
+    /// Calls [`get`](Self::get) and creates an uninitialized session if it
+    /// returns `None`
+    async fn get_or_create(&self, session_id: &str) -> SessionHandle {
+        match self.get(session_id).await {
+            Some(session) => session,
+            None => Arc::new(Mutex::new(McpProtocolHandlerImpl::new())),
+        }
+    }
+
+    /// Keep the changes made to a session while handling a message.
+    ///
+    /// Adds `session` to the store if it is initialized and not held yet.
+    ///
+    /// # Parameters
+    ///
+    /// - `session_id`: The session's ID
+    /// - `session`: The handle returned by `get_or_create`
+    /// - `state`: The session's state, whose lock the caller holds
+    async fn save(
+        &self,
+        session_id: &str,
+        session: &SessionHandle,
+        state: &McpProtocolHandlerImpl,
+    ) -> Result<()>;
+
+    /// Remove a session, calling the eviction hooks with `EvictionReason::Removed`.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the store held the session
+    async fn remove(&self, session_id: &str) -> bool;
+
+    /// Get the number of sessions the store holds.
+    async fn session_count(&self) -> usize;
+
+    /// Register a hook called whenever a session leaves the store.
+    fn on_evict(&self, hook: EvictionHook);
+}
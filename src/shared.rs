@@ -13,7 +13,7 @@
 //! # Session Management
 //!
 //! - WebSocket connections maintain state per connection
-//! - HTTP connections use session cookies for state persistence
+//! - HTTP connections name their session with the `Mcp-Session-Id` header or cookie
 //! - Sessions can be re-initialized (important for reconnecting clients)
 //!
 //! # Example
@@ -38,13 +38,25 @@
 //! ```
 
 use {
-    super::protocol_impl::McpProtocolHandlerImpl,
+    super::content_types::EmbeddedResource,
+    super::handler::{ConnectionInfo, McpContext, SessionNotifier},
+    super::admin::AdminApi,
+    super::auth::{AccessDenied, Authenticator, Principal},
+    super::health::{HealthReport, Probe, Readiness, ServerPhase},
+    super::introspection::{InFlightRequest, Introspection, SessionInfo},
+    super::middleware::{McpRequest, Middleware, Next},
+    super::metrics::{Metrics, TransportKind, DEFAULT_METRICS_PATH},
+    super::oauth::ProtectedResourceMetadata,
+    super::protocol::McpProtocol,
+    super::protocol_impl::{McpError, McpProtocolHandlerImpl},
+    super::session_store::{EvictionReason, InMemorySessionStore, SessionEndReason, SessionStore},
+    super::trace_context::{self, TraceContext},
     anyhow::Result,
     serde_json::{json, Value},
-    std::collections::HashMap,
     std::sync::Arc,
-    tokio::sync::Mutex,
-    tracing::{debug, trace},
+    tokio::time::Instant,
+    tracing::{debug, trace, warn, Instrument, Span},
+    uuid::Uuid,
 };
 
 /// Core protocol engine for routing MCP messages and managing sessions.
@@ -56,17 +68,38 @@ use {
 /// # Thread Safety
 ///
 /// The engine is thread-safe and can be shared across multiple connections using
-/// `Arc`. Session handlers are protected by a mutex to ensure safe concurrent access.
+/// `Arc`. Each session's state has its own mutex, so messages of one session are
+/// handled in order while different sessions are served concurrently.
 ///
 /// # Fields
 ///
-/// - `session_handlers`: Thread-safe map of session IDs to protocol handler instances
+/// - `sessions`: Store holding the protocol state of each session
 /// - `handler`: Optional custom handler implementing the `McpHandler` trait
+/// - `notifier`: Push channels of sessions that can receive server notifications
+/// - `middleware`: Layers wrapped around the dispatch of every message
+/// - `authenticator`: Optional authentication applied by the HTTP and WebSocket transports
+/// - `resource_metadata`: OAuth protected resource metadata published over HTTP
 pub struct McpProtocolEngine {
-    // Maintain protocol handlers per session ID for proper client isolation
-    session_handlers: Arc<Mutex<HashMap<String, McpProtocolHandlerImpl>>>,
+    // Maintain protocol state per session ID for proper client isolation
+    sessions: Arc<dyn SessionStore>,
     // Handler implementation for MCP functionality
     handler: Option<Arc<dyn super::handler::McpHandler>>,
+    // Shared with the handler so it can push notifications to sessions
+    notifier: SessionNotifier,
+    // Layers wrapped around every dispatch, outermost first
+    middleware: Vec<Arc<dyn Middleware>>,
+    // Applied by the transports before a request reaches the engine
+    authenticator: Option<Arc<dyn Authenticator>>,
+    // Served at /.well-known/oauth-protected-resource
+    resource_metadata: Option<ProtectedResourceMetadata>,
+    // Counts and latencies of the messages handled, served at `metrics_path`
+    metrics: Metrics,
+    metrics_path: String,
+    // Phase of the server, deciding the answer of `/health/ready`
+    readiness: Readiness,
+    // Initialized sessions and in-flight messages, for the admin API
+    introspection: Introspection,
+    admin: Option<AdminApi>,
 }
 
 impl Default for McpProtocolEngine {
@@ -92,9 +125,22 @@ impl McpProtocolEngine {
     /// let engine = McpProtocolEngine::new();
     /// ```
     pub fn new() -> Self {
+        let sessions: Arc<dyn SessionStore> = Arc::new(InMemorySessionStore::new());
+        let metrics = Metrics::new();
+        let introspection = Introspection::default();
+        forget_sessions(&sessions, &metrics, &introspection);
         Self {
-            session_handlers: Arc::new(Mutex::new(HashMap::new())),
+            sessions,
             handler: None,
+            notifier: SessionNotifier::default(),
+            middleware: Vec::new(),
+            authenticator: None,
+            resource_metadata: None,
+            metrics,
+            metrics_path: DEFAULT_METRICS_PATH.to_string(),
+            readiness: Readiness::default(),
+            introspection,
+            admin: None,
         }
     }
 
@@ -122,11 +168,281 @@ impl McpProtocolEngine {
     /// ```
     pub fn with_handler(handler: Arc<dyn super::handler::McpHandler>) -> Self {
         debug!("Handler registered with MCP protocol engine");
+        let sessions = handler
+            .session_store()
+            .unwrap_or_else(|| Arc::new(InMemorySessionStore::new()));
+        report_evictions(&sessions, &handler);
+        let metrics = Metrics::new();
+        let introspection = Introspection::default();
+        forget_sessions(&sessions, &metrics, &introspection);
         Self {
-            session_handlers: Arc::new(Mutex::new(HashMap::new())),
+            sessions,
+            notifier: handler.session_notifier().unwrap_or_default(),
+            middleware: handler.middleware(),
+            authenticator: handler.authenticator(),
+            resource_metadata: handler.protected_resource_metadata(),
+            metrics,
+            metrics_path: handler
+                .metrics_path()
+                .unwrap_or_else(|| DEFAULT_METRICS_PATH.to_string()),
+            readiness: Readiness::default(),
+            introspection,
+            admin: handler.admin_api(),
             handler: Some(handler),
         }
     }
+
+    /// Get the notifier used to push notifications to sessions.
+    ///
+    /// Transports with a push channel subscribe each session to it. When the
+    /// engine was created with a handler that provides its own notifier, this is
+    /// the same notifier, so the handler's broadcasts reach these sessions.
+    ///
+    /// # Returns
+    ///
+    /// The engine's `SessionNotifier`
+    pub fn notifier(&self) -> &SessionNotifier {
+        &self.notifier
+    }
+
+    /// Get the authenticator the transports apply to incoming requests.
+    ///
+    /// # Returns
+    ///
+    /// The handler's authenticator, or `None` if requests are not authenticated
+    pub fn authenticator(&self) -> Option<&Arc<dyn Authenticator>> {
+        self.authenticator.as_ref()
+    }
+
+    /// Get the OAuth protected resource metadata published by the HTTP transport.
+    ///
+    /// # Returns
+    ///
+    /// The handler's metadata, or `None` if the server is not an OAuth protected resource
+    pub fn protected_resource_metadata(&self) -> Option<&ProtectedResourceMetadata> {
+        self.resource_metadata.as_ref()
+    }
+
+    /// Get the metrics recorded for the messages this engine handled.
+    ///
+    /// # Returns
+    ///
+    /// The engine's `Metrics`; call `snapshot()` for the current values
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    /// Get the path the Prometheus metrics endpoint is served at.
+    ///
+    /// # Returns
+    ///
+    /// The handler's metrics path, or `/metrics`
+    pub fn metrics_path(&self) -> &str {
+        &self.metrics_path
+    }
+
+    /// Get the handle to the server phase, which decides readiness.
+    ///
+    /// The phase starts as `ServerPhase::Starting`; [`warm_up`](Self::warm_up)
+    /// moves it to `Ready`. Set it to `ShuttingDown` to fail readiness
+    /// probes while the server drains.
+    ///
+    /// # Returns
+    ///
+    /// The engine's `Readiness`
+    pub fn readiness(&self) -> &Readiness {
+        &self.readiness
+    }
+
+    /// Run the handler's warm-up, then mark the server ready.
+    ///
+    /// # Returns
+    ///
+    /// The warm-up's error, in which case the server stays unready
+    pub async fn warm_up(&self) -> Result<()> {
+        if let Some(handler) = &self.handler {
+            handler.warm_up().await?;
+        }
+        self.readiness.mark_ready();
+        Ok(())
+    }
+
+    /// Run the health checks of a probe.
+    ///
+    /// Liveness passes when its checks pass; readiness also requires the
+    /// server to be in `ServerPhase::Ready`.
+    ///
+    /// # Returns
+    ///
+    /// The overall status, the server phase and the outcome of each check
+    pub async fn check_health(&self, probe: Probe) -> HealthReport {
+        let checks = match &self.handler {
+            Some(handler) => handler.check_health(probe).await,
+            None => Vec::new(),
+        };
+        HealthReport::new(probe, self.readiness.phase(), checks)
+    }
+
+    /// Get the configuration of the admin API `McpServer` serves.
+    ///
+    /// # Returns
+    ///
+    /// The handler's admin API, or `None` if it is not served
+    pub fn admin_api(&self) -> Option<&AdminApi> {
+        self.admin.as_ref()
+    }
+
+    /// Take a snapshot of the initialized sessions, oldest first.
+    ///
+    /// # Returns
+    ///
+    /// Transport, client, negotiated version, age and idle time of each session
+    pub fn sessions(&self) -> Vec<SessionInfo> {
+        self.introspection.sessions()
+    }
+
+    /// Take a snapshot of the messages being handled, oldest first.
+    ///
+    /// Messages waiting for their session's previous message are included.
+    ///
+    /// # Returns
+    ///
+    /// Session, method, tool and elapsed time of each message
+    pub fn in_flight_requests(&self) -> Vec<InFlightRequest> {
+        self.introspection.requests()
+    }
+
+    /// Cancel an in-flight message by the ID listed in [`in_flight_requests`](Self::in_flight_requests).
+    ///
+    /// The handler's work is dropped and the client receives a
+    /// `REQUEST_CANCELLED` error in place of the response.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the message was still in flight
+    pub fn cancel_request(&self, id: u64) -> bool {
+        self.introspection.cancel(id)
+    }
+
+    /// Terminate a session: cancel its in-flight messages, close its
+    /// WebSocket and end it with `SessionEndReason::Terminated`.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the session existed
+    pub async fn terminate_session(&self, session_id: &str) -> bool {
+        self.introspection.cancel_session(session_id);
+        let closed = self.introspection.close_websocket(session_id);
+        let ended = self
+            .end_session(session_id, SessionEndReason::Terminated)
+            .await;
+        closed || ended
+    }
+
+    pub(crate) fn introspection(&self) -> &Introspection {
+        &self.introspection
+    }
+
+    /// Get the store holding the protocol state of each session.
+    ///
+    /// # Returns
+    ///
+    /// The handler's session store, or the default `InMemorySessionStore`
+    pub fn session_store(&self) -> &Arc<dyn SessionStore> {
+        &self.sessions
+    }
+
+    /// End a session: remove it from the session store and tell the handler.
+    ///
+    /// Transports call this when a session ends, e.g. with
+    /// `SessionEndReason::Closed` when its WebSocket closes.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the store held the session
+    pub async fn end_session(&self, session_id: &str, reason: SessionEndReason) -> bool {
+        self.introspection.session_ended(session_id);
+        if !self.sessions.remove(session_id).await {
+            return false;
+        }
+        self.session_end_hook(session_id, reason).await;
+        true
+    }
+
+    /// End a session on behalf of a client, as `DELETE /mcp` does.
+    ///
+    /// Only the principal that initialized the session may end it.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the session existed and was initialized
+    ///
+    /// # Errors
+    ///
+    /// `AccessDenied::ForeignSession` if another principal initialized the session
+    pub(crate) async fn end_owned_session(
+        &self,
+        session_id: &str,
+        principal: Option<&Principal>,
+        reason: SessionEndReason,
+    ) -> Result<bool, AccessDenied> {
+        let owner = match self.sessions.get(session_id).await {
+            Some(session) => {
+                let session = session.lock().await;
+                session.initialized.then(|| session.principal_id.clone())
+            }
+            None => None,
+        };
+        match owner {
+            None => Ok(false),
+            Some(owner) if owner.as_deref() != principal.map(|p| p.id.as_str()) => {
+                Err(AccessDenied::ForeignSession(session_id.to_string()))
+            }
+            Some(_) => Ok(self.end_session(session_id, reason).await),
+        }
+    }
+
+    async fn session_end_hook(&self, session_id: &str, reason: SessionEndReason) {
+        if let Some(handler) = &self.handler {
+            if let Err(e) = handler.on_session_end(session_id, reason).await {
+                warn!("Session end hook failed for {}: {}", session_id, e);
+            }
+        }
+    }
+
+    /// Drain the engine for shutdown: fail readiness, close the WebSockets,
+    /// let in-flight messages finish until `deadline`, cancel the rest and end
+    /// the initialized sessions with `SessionEndReason::Shutdown`.
+    ///
+    /// HTTP sessions stay in the session store, so stores that persist them
+    /// can resume them after a restart.
+    ///
+    /// # Returns
+    ///
+    /// The number of messages cancelled at the deadline
+    pub async fn drain(&self, deadline: Instant) -> usize {
+        self.readiness.set_phase(ServerPhase::ShuttingDown);
+        self.introspection.close_websockets();
+        let cancelled = match tokio::time::timeout_at(deadline, self.introspection.drained()).await {
+            Ok(()) => 0,
+            Err(_) => self.introspection.cancel_all(),
+        };
+        for session in self.introspection.sessions() {
+            match session.transport {
+                // Unless its connection loop got there first
+                TransportKind::WebSocket => {
+                    self.end_session(&session.session_id, SessionEndReason::Shutdown)
+                        .await;
+                }
+                TransportKind::Http => {
+                    self.introspection.session_ended(&session.session_id);
+                    self.session_end_hook(&session.session_id, SessionEndReason::Shutdown)
+                        .await;
+                }
+            }
+        }
+        cancelled
+    }
 }
 
 impl McpProtocolEngine {
@@ -179,6 +495,93 @@ impl McpProtocolEngine {
         &self,
         message: Value,
         session_id: Option<String>, // Session ID for client isolation
+    ) -> Result<Value> {
+        self.handle_message_with_connection(message, session_id, None)
+            .await
+    }
+
+    /// Handle an MCP message with transport-level connection details.
+    ///
+    /// Behaves exactly like [`handle_message`](Self::handle_message), but also
+    /// makes the HTTP headers and remote address of the connection available to
+    /// handlers through [`McpContext::connection`](super::handler::McpContext::connection).
+    /// Transports call this variant; `handle_message` passes `None`.
+    ///
+    /// # Parameters
+    ///
+    /// - `message`: The JSON-RPC message to process
+    /// - `session_id`: Optional session identifier for maintaining state
+    /// - `connection`: Optional headers and remote address of the client connection
+    ///
+    /// # Returns
+    ///
+    /// A JSON-RPC response message
+    pub async fn handle_message_with_connection(
+        &self,
+        message: Value,
+        session_id: Option<String>,
+        connection: Option<ConnectionInfo>,
+    ) -> Result<Value> {
+        let session_key = session_id.as_deref().unwrap_or("default");
+        let meta = message.get("params").and_then(|p| p.get("_meta"));
+        let span = trace_context::request_span(&message, session_key);
+        let trace = TraceContext::for_request(&span, meta, connection.as_ref());
+        let request = self.introspection.begin(session_key, &message);
+        let handled = self
+            .handle_traced(message, session_id, connection, trace, span.clone())
+            .instrument(span.clone());
+        let response = tokio::select! {
+            response = handled => response,
+            () = request.cancelled() => Ok(request.cancelled_response()),
+        };
+        trace_context::record_outcome(&span, response.as_ref().ok());
+        response
+    }
+
+    /// Handle a message from an HTTP client that sent no session ID.
+    ///
+    /// The message runs in a throwaway session that counts as initialized with
+    /// the latest protocol version and is removed afterwards, so stateless
+    /// clients share no state with each other or with real sessions. The
+    /// session hooks do not run for it.
+    ///
+    /// # Parameters
+    ///
+    /// - `message`: The JSON-RPC message to process
+    /// - `connection`: Optional headers and remote address of the client connection
+    pub(crate) async fn handle_stateless_message(
+        &self,
+        message: Value,
+        connection: Option<ConnectionInfo>,
+    ) -> Result<Value> {
+        let session_id = format!("stateless-{}", Uuid::new_v4());
+        // Holding the handle keeps the store from evicting the session, which
+        // would report the end of a session that never started
+        let session = self.sessions.get_or_create(&session_id).await;
+        {
+            let version = McpProtocol::new().version().to_string();
+            let mut state = session.lock().await;
+            *state = McpProtocolHandlerImpl::with_initialized(None, Some(version));
+            state.principal_id = principal_id(connection.as_ref());
+            self.sessions.save(&session_id, &session, &state).await?;
+        }
+        let response = self
+            .handle_message_with_connection(message, Some(session_id.clone()), connection)
+            .await;
+        self.introspection.session_ended(&session_id);
+        drop(session);
+        self.sessions.remove(&session_id).await;
+        response
+    }
+
+    /// Handle a message inside its request span.
+    async fn handle_traced(
+        &self,
+        message: Value,
+        session_id: Option<String>,
+        connection: Option<ConnectionInfo>,
+        trace: TraceContext,
+        span: Span,
     ) -> Result<Value> {
         let method = message["method"].as_str().unwrap_or("");
         trace!(
@@ -187,30 +590,108 @@ impl McpProtocolEngine {
             session_id
         );
 
-        // Get or create protocol handler for this session
-        let mut sessions = self.session_handlers.lock().await;
+        // Get or create protocol handler for this session. Only the session's own
+        // lock is held while the message is handled, so other sessions are not blocked.
         let session_key = session_id
             .as_ref()
             .unwrap_or(&"default".to_string())
             .clone();
+        let session = self.sessions.get_or_create(&session_key).await;
+        let mut protocol_handler = session.lock().await;
 
-        let protocol_handler = sessions.entry(session_key.clone()).or_insert_with(|| {
-            trace!("Creating new protocol handler for session: {}", session_key);
-            McpProtocolHandlerImpl::new()
-        });
+        // A session belongs to the principal that initialized it
+        let principal_id = principal_id(connection.as_ref());
+        if protocol_handler.initialized && protocol_handler.principal_id != principal_id {
+            debug!("Refusing access to session {} by another principal", session_key);
+            return Err(AccessDenied::ForeignSession(session_key).into());
+        }
+
+        let params = message.get("params");
+        let mut context = McpContext {
+            session_id: session_id.clone(),
+            notification_sender: None, // TODO: Add notification support
+            protocol_version: protocol_handler.protocol_version.clone(),
+            client_info: protocol_handler.client_info.clone(),
+            client_capabilities: protocol_handler.client_capabilities.clone(),
+            request_id: message.get("id").cloned(),
+            meta: params.and_then(|p| p.get("_meta")).cloned(),
+            principal: connection.as_ref().and_then(|c| c.principal.clone()),
+            connection,
+            session: protocol_handler.extensions.clone(),
+            data: protocol_handler.data.clone(),
+            trace: Some(trace),
+            span: Some(span),
+        };
+
+        // During initialize the session state is about to be replaced, so expose
+        // what the client is sending now rather than any previous handshake.
+        let initializing = method == "initialize";
+        if initializing {
+            context.protocol_version = params
+                .and_then(|p| p.get("protocolVersion"))
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string());
+            context.client_info = params.and_then(|p| p.get("clientInfo")).cloned();
+            context.client_capabilities = params.and_then(|p| p.get("capabilities")).cloned();
+        }
+
+        let observation = self.metrics.start(&session_key, &message);
+        let request = McpRequest::new(message, context);
+        let id = request.id().cloned();
+        let next = Next {
+            engine: self,
+            session: &mut protocol_handler,
+            middleware: &self.middleware,
+        };
+        let response = match next.run(request).await {
+            // Middleware short-circuiting with an MCP error gets a proper error response
+            Err(e) if e.downcast_ref::<McpError>().is_some() => {
+                handler_error(id.as_ref(), e, "Middleware error")
+            }
+            response => response,
+        };
+        if initializing && protocol_handler.initialized {
+            protocol_handler.principal_id = principal_id;
+        }
+        if initializing && !protocol_handler.initialized {
+            // A failed re-initialize already ended the session; drop it so its
+            // end hooks cannot fire a second time when it expires
+            self.sessions.remove(&session_key).await;
+        }
+        if let Err(e) = self
+            .sessions
+            .save(&session_key, &session, &protocol_handler)
+            .await
+        {
+            warn!("Failed to save session {}: {}", session_key, e);
+        }
+        self.introspection
+            .session_active(&session_key, initializing, &protocol_handler);
+        self.metrics.finish(observation, response.as_ref().ok());
+        response
+    }
+
+    /// Route a message to the custom handler or the built-in protocol handler.
+    ///
+    /// This is the innermost step of the middleware chain.
+    pub(crate) async fn dispatch(
+        &self,
+        request: McpRequest,
+        protocol_handler: &mut McpProtocolHandlerImpl,
+    ) -> Result<Value> {
+        let session_key = request
+            .context
+            .session_id
+            .clone()
+            .unwrap_or_else(|| "default".to_string());
+        let method = request.method.clone();
+        let (message, mut context) = request.into_parts();
 
         // If we have a custom handler, delegate to it for supported methods
         if let Some(ref custom_handler) = self.handler {
             trace!("Delegating method '{}' to custom handler", method);
 
-            let context = super::handler::McpContext {
-                session_id: session_id.clone(),
-                notification_sender: None, // TODO: Add notification support
-                protocol_version: Some("2025-06-18".to_string()),
-                client_info: None,
-            };
-
-            match method {
+            match method.as_str() {
                 "initialize" => {
                     let params = message
                         .get("params")
@@ -230,25 +711,33 @@ impl McpProtocolEngine {
 
                         // Create a fresh protocol handler to ensure clean state
                         *protocol_handler = McpProtocolHandlerImpl::new();
+                        context.session = protocol_handler.extensions.clone();
+                        context.data = protocol_handler.data.clone();
 
                         debug!(
                             "Created fresh protocol handler for session {} re-initialization",
                             session_key
                         );
+                        if let Err(e) = custom_handler
+                            .on_session_end(&session_key, SessionEndReason::Reinitialized)
+                            .await
+                        {
+                            warn!("Session end hook failed for {}: {}", session_key, e);
+                        }
                     }
 
-                    match custom_handler.initialize(params, &context).await {
+                    let initialized = match custom_handler.initialize(params, &context).await {
+                        Ok(result) => custom_handler.on_session_start(&context).await.map(|_| result),
+                        Err(e) => Err(e),
+                    };
+                    match initialized {
                         Ok(result) => {
-                            // Mark session as initialized in the protocol handler
+                            // Mark session as initialized and remember what the client
+                            // declared so later requests on this session can see it
                             protocol_handler.initialized = true;
-                            if let Some(client_version) = message
-                                .get("params")
-                                .and_then(|p| p.get("protocolVersion"))
-                                .and_then(|v| v.as_str())
-                            {
-                                protocol_handler.protocol_version =
-                                    Some(client_version.to_string());
-                            }
+                            protocol_handler.protocol_version = context.protocol_version;
+                            protocol_handler.client_info = context.client_info;
+                            protocol_handler.client_capabilities = context.client_capabilities;
 
                             let response = serde_json::json!({
                                 "jsonrpc": "2.0",
@@ -258,7 +747,7 @@ impl McpProtocolEngine {
                             return Ok(response);
                         }
                         Err(e) => {
-                            return Err(anyhow::anyhow!("Initialize error: {}", e));
+                            return handler_error(message.get("id"), e, "Initialize error");
                         }
                     }
                 }
@@ -286,7 +775,7 @@ impl McpProtocolEngine {
                         return Ok(response);
                     }
                     Err(e) => {
-                        return Err(anyhow::anyhow!("Tools list error: {}", e));
+                        return handler_error(message.get("id"), e, "Tools list error");
                     }
                 },
                 "tools/call" => {
@@ -308,7 +797,7 @@ impl McpProtocolEngine {
                                 return Ok(response);
                             }
                             Err(e) => {
-                                return Err(anyhow::anyhow!("Tool call error: {}", e));
+                                return handler_error(message.get("id"), e, "Tool call error");
                             }
                         }
                     }
@@ -322,12 +811,21 @@ impl McpProtocolEngine {
                                     "uri": r.uri,
                                     "name": r.name,
                                 });
+                                if let Some(title) = r.title {
+                                    resource["title"] = serde_json::Value::String(title);
+                                }
                                 if let Some(desc) = r.description {
                                     resource["description"] = serde_json::Value::String(desc);
                                 }
                                 if let Some(mime) = r.mime_type {
                                     resource["mimeType"] = serde_json::Value::String(mime);
                                 }
+                                if let Some(size) = r.size {
+                                    resource["size"] = json!(size);
+                                }
+                                if let Some(annotations) = r.annotations {
+                                    resource["annotations"] = json!(annotations);
+                                }
                                 resource
                             })
                             .collect();
@@ -342,31 +840,31 @@ impl McpProtocolEngine {
                         return Ok(response);
                     }
                     Err(e) => {
-                        return Err(anyhow::anyhow!("Resources list error: {}", e));
+                        return handler_error(message.get("id"), e, "Resources list error");
                     }
                 },
                 "resources/read" => {
                     let params = message.get("params").unwrap_or(&serde_json::Value::Null);
                     if let Some(uri) = params.get("uri").and_then(|u| u.as_str()) {
-                        match custom_handler.read_resource(uri, &context).await {
-                            Ok(content) => {
+                        match custom_handler.read_resource_contents(uri, &context).await {
+                            Ok(contents) => {
+                                let contents: Vec<serde_json::Value> = contents
+                                    .into_iter()
+                                    .map(|content| {
+                                        json!(EmbeddedResource::from(content))
+                                    })
+                                    .collect();
                                 let response = serde_json::json!({
                                     "jsonrpc": "2.0",
                                     "id": message.get("id"),
                                     "result": {
-                                        "contents": [
-                                            {
-                                                "uri": content.uri,
-                                                "mimeType": content.mime_type,
-                                                "text": content.content,
-                                            }
-                                        ]
+                                        "contents": contents
                                     }
                                 });
                                 return Ok(response);
                             }
                             Err(e) => {
-                                return Err(anyhow::anyhow!("Resource read error: {}", e));
+                                return handler_error(message.get("id"), e, "Resource read error");
                             }
                         }
                     }
@@ -414,7 +912,7 @@ impl McpProtocolEngine {
                         return Ok(response);
                     }
                     Err(e) => {
-                        return Err(anyhow::anyhow!("Prompts list error: {}", e));
+                        return handler_error(message.get("id"), e, "Prompts list error");
                     }
                 },
                 "prompts/get" => {
@@ -429,25 +927,26 @@ impl McpProtocolEngine {
                                     .map(|m| {
                                         serde_json::json!({
                                             "role": m.role,
-                                            "content": {
-                                                "type": "text",
-                                                "text": m.content,
-                                            }
+                                            "content": m.content,
                                         })
                                     })
                                     .collect();
 
+                                let mut result = serde_json::json!({
+                                    "messages": messages
+                                });
+                                if let Some(desc) = content.description {
+                                    result["description"] = serde_json::Value::String(desc);
+                                }
                                 let response = serde_json::json!({
                                     "jsonrpc": "2.0",
                                     "id": message.get("id"),
-                                    "result": {
-                                        "messages": messages
-                                    }
+                                    "result": result
                                 });
                                 return Ok(response);
                             }
                             Err(e) => {
-                                return Err(anyhow::anyhow!("Prompt get error: {}", e));
+                                return handler_error(message.get("id"), e, "Prompt get error");
                             }
                         }
                     }
@@ -476,3 +975,72 @@ impl McpProtocolEngine {
         }))
     }
 }
+
+/// ID of the principal a connection authenticated as.
+fn principal_id(connection: Option<&ConnectionInfo>) -> Option<String> {
+    connection
+        .and_then(|c| c.principal.as_ref())
+        .map(|p| p.id.clone())
+}
+
+/// Stop counting and listing sessions once they leave the store, for any reason.
+fn forget_sessions(
+    sessions: &Arc<dyn SessionStore>,
+    metrics: &Metrics,
+    introspection: &Introspection,
+) {
+    let metrics = metrics.clone();
+    let introspection = introspection.clone();
+    sessions.on_evict(Arc::new(move |session_id, _reason| {
+        metrics.session_ended(session_id);
+        introspection.session_ended(session_id);
+    }));
+}
+
+/// Tell the handler about sessions the store expires or evicts on its own.
+///
+/// Explicit removals are reported by `end_session`, which knows the reason.
+/// The hook holds the handler weakly, since the handler may own the store.
+fn report_evictions(sessions: &Arc<dyn SessionStore>, handler: &Arc<dyn super::handler::McpHandler>) {
+    let handler = Arc::downgrade(handler);
+    sessions.on_evict(Arc::new(move |session_id, reason| {
+        let reason = match reason {
+            EvictionReason::Expired => SessionEndReason::Expired,
+            EvictionReason::CapacityExceeded => SessionEndReason::Evicted,
+            EvictionReason::Removed => return,
+        };
+        let (Some(handler), Ok(runtime)) = (handler.upgrade(), tokio::runtime::Handle::try_current()) else {
+            return;
+        };
+        let session_id = session_id.to_string();
+        runtime.spawn(async move {
+            if let Err(e) = handler.on_session_end(&session_id, reason).await {
+                warn!("Session end hook failed for {}: {}", session_id, e);
+            }
+        });
+    }));
+}
+
+/// Turn a handler error into the engine's result.
+///
+/// Errors carrying an [`McpError`] become a JSON-RPC error response with the
+/// matching code (e.g. `-32602` for invalid params), so every transport reports
+/// them the same way. [`AccessDenied`] is passed on unchanged, so the HTTP
+/// transport can answer it with `403 Forbidden`. Any other error is passed on
+/// to the transport, prefixed with `label`.
+fn handler_error(id: Option<&Value>, error: anyhow::Error, label: &str) -> Result<Value> {
+    if error.downcast_ref::<AccessDenied>().is_some() {
+        return Err(error);
+    }
+    if let Some(mcp_error) = error.downcast_ref::<McpError>() {
+        return Ok(json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": {
+                "code": mcp_error.code(),
+                "message": mcp_error.to_string()
+            }
+        }));
+    }
+    Err(anyhow::anyhow!("{}: {}", label, error))
+}
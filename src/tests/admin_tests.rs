@@ -0,0 +1,194 @@
+//! Admin API Tests
+//!
+//! Tests the engine's read-only view of sessions and in-flight requests,
+//! cancelling requests and terminating sessions, and the authenticated admin
+//! HTTP API serving them.
+
+#[cfg(test)]
+mod tests {
+    use crate::admin::{self, AdminApi};
+    use crate::auth::{ApiKeyAuthenticator, Principal};
+    use crate::framework::FrameworkHandler;
+    use crate::introspection::REQUEST_CANCELLED;
+    use crate::metrics::TransportKind;
+    use crate::session_store::SessionEndReason;
+    use crate::shared::McpProtocolEngine;
+    use crate::tests::support::{initialize, send};
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use warp::test::request;
+
+    #[derive(JsonSchema, Deserialize)]
+    struct Empty {}
+
+    #[derive(JsonSchema, Serialize)]
+    struct Done {}
+
+    /// An engine with a tool that never finishes, recording why sessions end.
+    fn engine(ended: Arc<Mutex<Vec<SessionEndReason>>>) -> Arc<McpProtocolEngine> {
+        let mut framework = FrameworkHandler::new(ended, "admin-server", "1.0.0");
+        framework.set_admin_api(
+            AdminApi::new(
+                ApiKeyAuthenticator::new()
+                    .with_key("sk-ops", Principal::new("oncall").with_scopes(["admin"]))
+                    .with_key("sk-dev", Principal::new("dev")),
+            )
+            .require_scope("admin"),
+        );
+        framework.add_session_end_hook(
+            |ended: Arc<Arc<Mutex<Vec<SessionEndReason>>>>, _id, reason| async move {
+                ended.lock().unwrap().push(reason);
+                Ok(())
+            },
+        );
+        framework.registry_handle().add_tool(
+            "hang",
+            "Never return",
+            |_input: Empty, _ctx, _notif| async {
+                std::future::pending::<()>().await;
+                Ok(Done {})
+            },
+        );
+        Arc::new(McpProtocolEngine::with_handler(Arc::new(framework)))
+    }
+
+    #[tokio::test]
+    async fn test_sessions_are_listed_with_transport_and_client() {
+        let engine = engine(Arc::default());
+        initialize(&engine, "http-1").await;
+        let _close = engine.introspection().websocket_opened("ws-1");
+        initialize(&engine, "ws-1").await;
+        // Sessions that never initialized are not listed
+        send(&engine, "stranger", "tools/list", json!({}))
+            .await
+            .unwrap();
+
+        let mut sessions = engine.sessions();
+        sessions.sort_by(|a, b| a.session_id.cmp(&b.session_id));
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].session_id, "http-1");
+        assert_eq!(sessions[0].transport, TransportKind::Http);
+        assert_eq!(sessions[0].protocol_version.as_deref(), Some("2025-06-18"));
+        assert_eq!(
+            sessions[0].client_info.as_ref().unwrap()["name"],
+            "test-client"
+        );
+        assert_eq!(sessions[1].transport, TransportKind::WebSocket);
+        assert!(sessions[1].idle_secs <= sessions[1].age_secs);
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_requests_can_be_cancelled() {
+        let engine = engine(Arc::default());
+        initialize(&engine, "a").await;
+        let call = tokio::spawn({
+            let engine = engine.clone();
+            async move {
+                send(
+                    &engine,
+                    "a",
+                    "tools/call",
+                    json!({ "name": "hang", "arguments": {} }),
+                )
+                .await
+                .unwrap()
+            }
+        });
+
+        let request = loop {
+            if let Some(request) = engine.in_flight_requests().pop() {
+                break request;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        };
+        assert_eq!(request.session_id, "a");
+        assert_eq!(request.method, "tools/call");
+        assert_eq!(request.tool.as_deref(), Some("hang"));
+        assert_eq!(request.request_id, Some(json!(1)));
+        assert_eq!(engine.sessions()[0].in_flight, 1);
+
+        assert!(engine.cancel_request(request.id));
+        let response = call.await.unwrap();
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["error"]["code"], REQUEST_CANCELLED);
+        assert!(engine.in_flight_requests().is_empty());
+        assert!(!engine.cancel_request(request.id));
+    }
+
+    #[tokio::test]
+    async fn test_terminating_a_session_closes_its_websocket() {
+        let ended = Arc::new(Mutex::new(Vec::new()));
+        let engine = engine(ended.clone());
+        let close = engine.introspection().websocket_opened("ws-1");
+        initialize(&engine, "ws-1").await;
+
+        assert!(engine.terminate_session("ws-1").await);
+        tokio::time::timeout(Duration::from_secs(1), close.notified())
+            .await
+            .expect("WebSocket was not told to close");
+        assert!(engine.sessions().is_empty());
+        assert_eq!(*ended.lock().unwrap(), [SessionEndReason::Terminated]);
+        // As the connection loop does once the close frame is sent
+        engine.introspection().websocket_closed("ws-1");
+        assert!(!engine.terminate_session("ws-1").await);
+    }
+
+    #[tokio::test]
+    async fn test_admin_api_requires_authentication_and_scope() {
+        let engine = engine(Arc::default());
+        initialize(&engine, "http-1").await;
+        let routes = admin::routes(engine.clone());
+
+        let anonymous = request().path("/admin/sessions").reply(&routes).await;
+        assert_eq!(anonymous.status(), 401);
+        let developer = request()
+            .path("/admin/sessions")
+            .header("authorization", "Bearer sk-dev")
+            .reply(&routes)
+            .await;
+        assert_eq!(developer.status(), 403);
+
+        let listed = request()
+            .path("/admin/sessions")
+            .header("authorization", "Bearer sk-ops")
+            .reply(&routes)
+            .await;
+        assert_eq!(listed.status(), 200);
+        let body: Value = serde_json::from_slice(listed.body()).unwrap();
+        assert_eq!(body["sessions"][0]["session_id"], "http-1");
+        assert_eq!(body["sessions"][0]["transport"], "http");
+
+        let requests = request()
+            .path("/admin/requests")
+            .header("authorization", "Bearer sk-ops")
+            .reply(&routes)
+            .await;
+        let body: Value = serde_json::from_slice(requests.body()).unwrap();
+        assert_eq!(body["requests"], json!([]));
+
+        for (path, status) in [
+            ("/admin/sessions/http-1", 204),
+            ("/admin/sessions/http-1", 404),
+            ("/admin/requests/99", 404),
+        ] {
+            let deleted = request()
+                .method("DELETE")
+                .path(path)
+                .header("authorization", "Bearer sk-ops")
+                .reply(&routes)
+                .await;
+            assert_eq!(deleted.status(), status, "{path}");
+        }
+        assert!(engine.sessions().is_empty());
+
+        let unknown = request()
+            .path("/administrator/sessions")
+            .header("authorization", "Bearer sk-ops")
+            .reply(&routes)
+            .await;
+        assert_eq!(unknown.status(), 404);
+    }
+}
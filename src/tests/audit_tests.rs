@@ -0,0 +1,314 @@
+//! Audit Log Tests
+//!
+//! Tests that `FrameworkHandler` records every tool call with its caller,
+//! redacted arguments, timing and outcome, including calls dropped before the
+//! tool returned, and that `JsonLinesAuditSink` writes the records in JSON
+//! Lines format from a bounded queue.
+
+#[cfg(test)]
+mod tests {
+    use crate::audit::{
+        AuditLog, AuditOutcome, AuditRecord, AuditSink, JsonLinesAuditSink, REDACTED,
+    };
+    use crate::framework::FrameworkHandler;
+    use crate::shared::McpProtocolEngine;
+    use crate::tests::support::send_with_id;
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+    use std::io::{self, Write};
+    use std::sync::{mpsc, Arc, Mutex};
+    use std::time::Duration;
+    use tokio::sync::Semaphore;
+
+    #[derive(JsonSchema, Deserialize)]
+    struct LoginInput {
+        user: String,
+        #[allow(dead_code)]
+        password: String,
+        #[allow(dead_code)]
+        options: Value,
+    }
+
+    #[derive(JsonSchema, Serialize)]
+    struct LoginOutput {
+        user: String,
+    }
+
+    #[derive(JsonSchema, Deserialize)]
+    struct Empty {}
+
+    fn engine(audit: AuditLog) -> McpProtocolEngine {
+        engine_with_started(audit, Arc::new(Semaphore::new(0)))
+    }
+
+    /// An engine whose `hang` tool adds a permit to `started` and never returns.
+    fn engine_with_started(audit: AuditLog, started: Arc<Semaphore>) -> McpProtocolEngine {
+        let mut framework = FrameworkHandler::new((), "audit-server", "1.0.0");
+        framework.set_audit_log(audit);
+        framework.registry_handle().add_tool(
+            "hang",
+            "Never return",
+            move |_input: Empty, _ctx, _notif| {
+                let started = started.clone();
+                async move {
+                    started.add_permits(1);
+                    std::future::pending::<()>().await;
+                    Ok(LoginOutput {
+                        user: String::new(),
+                    })
+                }
+            },
+        );
+        framework.registry_handle().add_tool(
+            "login",
+            "Log in",
+            |input: LoginInput, _ctx, _notif| async move {
+                if input.user.is_empty() {
+                    anyhow::bail!("unknown user");
+                }
+                Ok(LoginOutput { user: input.user })
+            },
+        );
+        McpProtocolEngine::with_handler(Arc::new(framework))
+    }
+
+    async fn login(engine: &McpProtocolEngine, id: u64, user: &str) {
+        let arguments = json!({
+            "user": user,
+            "password": "hunter2",
+            "options": { "token": "abc", "keys": [{ "password": "x", "id": 1 }] }
+        });
+        let _ = send_with_id(
+            engine,
+            "audited",
+            id,
+            "tools/call",
+            json!({ "name": "login", "arguments": arguments }),
+        )
+        .await;
+    }
+
+    fn collecting() -> (AuditLog, Arc<Mutex<Vec<AuditRecord>>>) {
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let sink = records.clone();
+        let audit = AuditLog::new(move |record| sink.lock().unwrap().push(record));
+        (audit, records)
+    }
+
+    #[test]
+    fn test_redaction_rules() {
+        let (audit, _) = collecting();
+        let audit = audit
+            .redact_field("password")
+            .redact_path("login", "options.token")
+            .redact_path("other", "user");
+        let arguments = json!({
+            "user": "ada",
+            "password": "hunter2",
+            "options": { "token": "abc", "keys": [{ "password": "x", "id": 1 }] }
+        });
+
+        assert_eq!(
+            audit.redact("login", &arguments),
+            json!({
+                "user": "ada",
+                "password": REDACTED,
+                "options": { "token": REDACTED, "keys": [{ "password": REDACTED, "id": 1 }] }
+            })
+        );
+        // Paths only apply to their tool
+        assert_eq!(
+            audit.redact("search", &arguments)["options"]["token"],
+            "abc"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tool_calls_are_recorded() {
+        let (audit, records) = collecting();
+        let engine = engine(audit.redact_field("password"));
+        let _ = send_with_id(
+            &engine,
+            "audited",
+            1,
+            "initialize",
+            json!({
+                "protocolVersion": "2025-06-18",
+                "clientInfo": { "name": "inspector", "version": "2.0" }
+            }),
+        )
+        .await;
+        login(&engine, 2, "ada").await;
+        login(&engine, 3, "").await;
+        let _ = send_with_id(
+            &engine,
+            "audited",
+            4,
+            "tools/call",
+            json!({ "name": "missing", "arguments": {} }),
+        )
+        .await;
+
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), 3);
+        let ok = &records[0];
+        assert_eq!(ok.tool, "login");
+        assert_eq!(ok.session_id.as_deref(), Some("audited"));
+        assert_eq!(ok.request_id, Some(json!(2)));
+        assert_eq!(ok.client_info.as_ref().unwrap()["name"], "inspector");
+        assert_eq!(ok.arguments["user"], "ada");
+        assert_eq!(ok.arguments["password"], REDACTED);
+        assert_eq!(ok.trace_id.as_ref().map(String::len), Some(32));
+        assert!(ok.timestamp.ends_with('Z'));
+        assert!(chrono::DateTime::parse_from_rfc3339(&ok.timestamp).is_ok());
+        assert_eq!(ok.outcome, AuditOutcome::Success);
+        assert_eq!(ok.error, None);
+
+        let failed = &records[1];
+        assert_eq!(failed.outcome, AuditOutcome::Error);
+        assert!(failed.error.as_deref().unwrap().contains("unknown user"));
+
+        let unknown = &records[2];
+        assert_eq!(unknown.tool, "missing");
+        assert_eq!(unknown.outcome, AuditOutcome::Error);
+    }
+
+    #[tokio::test]
+    async fn test_dropped_calls_are_recorded_as_cancelled() {
+        let (audit, records) = collecting();
+        let started = Arc::new(Semaphore::new(0));
+        let engine = Arc::new(engine_with_started(audit, started.clone()));
+        let hang = |session: &'static str| {
+            let engine = engine.clone();
+            let started = started.clone();
+            async move {
+                let call = tokio::spawn(async move {
+                    let params = json!({ "name": "hang", "arguments": {} });
+                    send_with_id(&engine, session, 1, "tools/call", params).await
+                });
+                started.acquire().await.unwrap().forget();
+                call
+            }
+        };
+
+        let call = hang("cancelled").await;
+        let request = engine.in_flight_requests().pop().unwrap();
+        assert!(engine.cancel_request(request.id));
+        call.await.unwrap().unwrap();
+
+        let call = hang("terminated").await;
+        engine.terminate_session("terminated").await;
+        call.await.unwrap().unwrap();
+
+        // A client that disconnects drops its request
+        let call = hang("disconnected").await;
+        call.abort();
+        assert!(call.await.unwrap_err().is_cancelled());
+
+        let records = records.lock().unwrap();
+        let sessions: Vec<_> = records
+            .iter()
+            .map(|record| (record.session_id.as_deref().unwrap(), record.outcome))
+            .collect();
+        assert_eq!(
+            sessions,
+            [
+                ("cancelled", AuditOutcome::Cancelled),
+                ("terminated", AuditOutcome::Cancelled),
+                ("disconnected", AuditOutcome::Cancelled),
+            ]
+        );
+        assert!(records
+            .iter()
+            .all(|record| record.tool == "hang" && record.error.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_json_lines_sink_appends_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let sink = JsonLinesAuditSink::open(&path).unwrap();
+        let engine = engine(AuditLog::new(sink.clone()));
+
+        login(&engine, 1, "ada").await;
+        login(&engine, 2, "grace").await;
+        sink.flush().unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let records: Vec<AuditRecord> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].arguments["user"], "ada");
+        assert_eq!(records[1].arguments["user"], "grace");
+        assert!(records[1].duration_ms >= 0.0);
+    }
+
+    /// A writer that stalls on its first write until released.
+    struct Stalled {
+        stall: Option<(mpsc::Sender<()>, mpsc::Receiver<()>)>,
+        written: Arc<Mutex<Vec<u8>>>,
+    }
+
+    impl Write for Stalled {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if let Some((entered, release)) = self.stall.take() {
+                entered.send(()).unwrap();
+                release.recv().unwrap();
+            }
+            self.written.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn record(tool: &str) -> AuditRecord {
+        AuditRecord {
+            timestamp: "2025-01-01T00:00:00.000Z".to_string(),
+            tool: tool.to_string(),
+            session_id: None,
+            request_id: None,
+            principal: None,
+            client_info: None,
+            remote_addr: None,
+            trace_id: None,
+            arguments: json!({}),
+            duration_ms: 1.0,
+            outcome: AuditOutcome::Success,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_full_queue_drops_records() {
+        let (entered, stalled) = mpsc::channel();
+        let (release, released) = mpsc::channel();
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let writer = Stalled {
+            stall: Some((entered, released)),
+            written: written.clone(),
+        };
+        let sink = JsonLinesAuditSink::from_writer(writer, 2).unwrap();
+
+        sink.record(record("first"));
+        stalled.recv_timeout(Duration::from_secs(5)).unwrap();
+        for tool in ["second", "third", "fourth", "fifth"] {
+            sink.record(record(tool));
+        }
+        assert_eq!(sink.dropped(), 2);
+
+        release.send(()).unwrap();
+        sink.flush().unwrap();
+        let written = String::from_utf8(written.lock().unwrap().clone()).unwrap();
+        let tools: Vec<String> = written
+            .lines()
+            .map(|line| serde_json::from_str::<AuditRecord>(line).unwrap().tool)
+            .collect();
+        assert_eq!(tools, ["first", "second", "third"]);
+    }
+}
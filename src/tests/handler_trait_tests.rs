@@ -120,6 +120,7 @@ mod tests {
             notification_sender: None,
             protocol_version: Some("2025-06-18".to_string()),
             client_info: None,
+            ..Default::default()
         };
 
         // Test initialization
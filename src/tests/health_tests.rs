@@ -0,0 +1,129 @@
+//! Health Check Tests
+//!
+//! Tests the liveness and readiness probes: user-defined checks run against
+//! the application context, readiness following the server phase through
+//! warm-up and shutdown, and the JSON answers of `/health/live` and
+//! `/health/ready`.
+
+#[cfg(test)]
+mod tests {
+    use crate::framework::FrameworkHandler;
+    use crate::health::{self, HealthStatus, Probe, ServerPhase};
+    use crate::shared::McpProtocolEngine;
+    use serde_json::Value;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use warp::test::request;
+
+    /// Application context with a database that can go down.
+    #[derive(Default)]
+    struct App {
+        database_up: AtomicBool,
+        warmed_up: AtomicBool,
+    }
+
+    fn engine(app: App) -> (Arc<McpProtocolEngine>, Arc<App>) {
+        let mut framework = FrameworkHandler::new(app, "health-server", "1.0.0");
+        framework.add_health_check("event_loop", Probe::Liveness, |_app: Arc<App>| async {
+            Ok(())
+        });
+        framework.add_health_check("database", Probe::Readiness, |app: Arc<App>| async move {
+            if !app.database_up.load(Ordering::SeqCst) {
+                anyhow::bail!("connection refused");
+            }
+            Ok(())
+        });
+        framework.add_warm_up(|app: Arc<App>| async move {
+            app.warmed_up.store(true, Ordering::SeqCst);
+            Ok(())
+        });
+        let app = framework.context().clone();
+        (
+            Arc::new(McpProtocolEngine::with_handler(Arc::new(framework))),
+            app,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_readiness_follows_warm_up_and_shutdown() {
+        let (engine, app) = engine(App {
+            database_up: AtomicBool::new(true),
+            ..Default::default()
+        });
+
+        let starting = engine.check_health(Probe::Readiness).await;
+        assert_eq!(starting.status, HealthStatus::Fail);
+        assert_eq!(starting.phase, ServerPhase::Starting);
+        assert!(starting
+            .checks
+            .iter()
+            .all(|check| check.status == HealthStatus::Pass));
+
+        engine.warm_up().await.unwrap();
+        assert!(app.warmed_up.load(Ordering::SeqCst));
+        let ready = engine.check_health(Probe::Readiness).await;
+        assert_eq!(ready.status, HealthStatus::Pass);
+        assert_eq!(ready.phase, ServerPhase::Ready);
+
+        engine.readiness().set_phase(ServerPhase::ShuttingDown);
+        assert_eq!(
+            engine.check_health(Probe::Readiness).await.status,
+            HealthStatus::Fail
+        );
+        // Draining servers are still alive
+        assert_eq!(
+            engine.check_health(Probe::Liveness).await.status,
+            HealthStatus::Pass
+        );
+        // Warm-up never brings a draining server back
+        engine.warm_up().await.unwrap();
+        assert_eq!(engine.readiness().phase(), ServerPhase::ShuttingDown);
+    }
+
+    #[tokio::test]
+    async fn test_probes_report_each_check() {
+        let (engine, _app) = engine(App::default());
+        engine.warm_up().await.unwrap();
+        let routes = health::routes(engine);
+
+        let ready = request().path("/health/ready").reply(&routes).await;
+        assert_eq!(ready.status(), 503);
+        let body: Value = serde_json::from_slice(ready.body()).unwrap();
+        assert_eq!(body["status"], "fail");
+        assert_eq!(body["phase"], "ready");
+        assert_eq!(body["checks"][0]["name"], "event_loop");
+        assert_eq!(body["checks"][0]["status"], "pass");
+        assert_eq!(body["checks"][1]["name"], "database");
+        assert_eq!(body["checks"][1]["status"], "fail");
+        assert_eq!(body["checks"][1]["error"], "connection refused");
+        assert!(body["checks"][1]["latency_ms"].as_f64().is_some());
+
+        // Liveness only runs the liveness checks
+        let live = request().path("/health/live").reply(&routes).await;
+        assert_eq!(live.status(), 200);
+        let body: Value = serde_json::from_slice(live.body()).unwrap();
+        assert_eq!(body["status"], "pass");
+        assert_eq!(body["checks"].as_array().unwrap().len(), 1);
+
+        let legacy = request().path("/health").reply(&routes).await;
+        assert_eq!(legacy.status(), 200);
+        assert_eq!(legacy.body(), "OK");
+
+        let unknown = request().path("/health/other").reply(&routes).await;
+        assert_eq!(unknown.status(), 404);
+    }
+
+    #[tokio::test]
+    async fn test_failed_warm_up_keeps_server_unready() {
+        let mut framework = FrameworkHandler::new((), "health-server", "1.0.0");
+        framework.add_warm_up(|_ctx: Arc<()>| async { anyhow::bail!("cache unavailable") });
+        let engine = McpProtocolEngine::with_handler(Arc::new(framework));
+
+        assert!(engine.warm_up().await.is_err());
+        assert_eq!(engine.readiness().phase(), ServerPhase::Starting);
+        assert_eq!(
+            engine.check_health(Probe::Readiness).await.status,
+            HealthStatus::Fail
+        );
+    }
+}
@@ -0,0 +1,389 @@
+//! Authentication tests for the MCP HTTP and WebSocket routes
+
+#[cfg(test)]
+mod tests {
+    use crate::auth::{ApiKeyAuthenticator, AuthError, Authenticator, Principal};
+    use crate::framework::{FrameworkHandler, McpServerBuilder};
+    use crate::handler::ConnectionInfo;
+    use crate::http::HttpMcpHandler;
+    use crate::shared::McpProtocolEngine;
+    use crate::websocket::create_ws_handler;
+    use async_trait::async_trait;
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use warp::test::request;
+
+    #[derive(JsonSchema, Deserialize)]
+    struct Empty {}
+
+    #[derive(JsonSchema, Serialize)]
+    struct WhoAmI {
+        id: Option<String>,
+        admin: bool,
+    }
+
+    fn engine(authenticator: impl Authenticator + 'static) -> Arc<McpProtocolEngine> {
+        let mut framework = FrameworkHandler::new((), "auth-server", "1.0.0");
+        framework.set_authenticator(authenticator);
+        framework.registry_handle().add_tool(
+            "whoami",
+            "Who am I",
+            |_input: Empty, _ctx, notif| async move {
+                Ok(WhoAmI {
+                    id: notif.principal().map(|p| p.id.clone()),
+                    admin: notif.principal().is_some_and(|p| p.has_scope("admin")),
+                })
+            },
+        );
+        Arc::new(McpProtocolEngine::with_handler(Arc::new(framework)))
+    }
+
+    fn api_keys() -> ApiKeyAuthenticator {
+        ApiKeyAuthenticator::new()
+            .with_key("sk-user", Principal::new("bob"))
+            .with_key("sk-admin", Principal::new("alice").with_scopes(["admin"]))
+    }
+
+    fn initialize() -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": { "protocolVersion": "2025-06-18" }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_missing_credentials_get_401_with_challenge() {
+        let routes = HttpMcpHandler::new(engine(api_keys())).route();
+
+        let resp = request()
+            .method("POST")
+            .path("/mcp")
+            .json(&initialize())
+            .reply(&routes)
+            .await;
+
+        assert_eq!(resp.status(), 401);
+        assert_eq!(resp.headers()["www-authenticate"], r#"Bearer realm="mcp""#);
+        let body: Value = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(body["error"]["message"], "Missing credentials");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_key_is_rejected_as_invalid_token() {
+        let routes = HttpMcpHandler::new(engine(api_keys())).route();
+
+        let resp = request()
+            .method("POST")
+            .path("/mcp")
+            .header("authorization", "Bearer sk-wrong")
+            .json(&initialize())
+            .reply(&routes)
+            .await;
+
+        assert_eq!(resp.status(), 401);
+        assert_eq!(
+            resp.headers()["www-authenticate"],
+            r#"Bearer realm="mcp", error="invalid_token""#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_principal_reaches_tools() {
+        let routes = HttpMcpHandler::new(engine(api_keys())).route();
+
+        for (header, value, id, admin) in [
+            ("authorization", "Bearer sk-admin", "alice", true),
+            ("x-api-key", "sk-user", "bob", false),
+        ] {
+            let resp = request()
+                .method("POST")
+                .path("/mcp")
+                .header(header, value)
+                .json(&initialize())
+                .reply(&routes)
+                .await;
+            assert_eq!(resp.status(), 200);
+
+            let resp = request()
+                .method("POST")
+                .path("/mcp")
+                .header(header, value)
+                .json(&json!({
+                    "jsonrpc": "2.0",
+                    "id": 2,
+                    "method": "tools/call",
+                    "params": { "name": "whoami", "arguments": {} }
+                }))
+                .reply(&routes)
+                .await;
+            assert_eq!(resp.status(), 200);
+            let body: Value = serde_json::from_slice(resp.body()).unwrap();
+            assert_eq!(body["result"]["data"]["id"], id);
+            assert_eq!(body["result"]["data"]["admin"], admin);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sessions_belong_to_their_principal() {
+        let routes = HttpMcpHandler::new(engine(api_keys())).route();
+        let init = request()
+            .method("POST")
+            .path("/mcp")
+            .header("authorization", "Bearer sk-user")
+            .json(&initialize())
+            .reply(&routes)
+            .await;
+        let session_id = init.headers()["mcp-session-id"].to_str().unwrap();
+        let whoami = json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": { "name": "whoami", "arguments": {} }
+        });
+
+        let hijacked = request()
+            .method("POST")
+            .path("/mcp")
+            .header("authorization", "Bearer sk-admin")
+            .header("mcp-session-id", session_id)
+            .json(&whoami)
+            .reply(&routes)
+            .await;
+        assert_eq!(hijacked.status(), 403);
+        let body: Value = serde_json::from_slice(hijacked.body()).unwrap();
+        assert_eq!(body["id"], 2);
+        assert_eq!(body["error"]["code"], -32003);
+
+        let deleted = request()
+            .method("DELETE")
+            .path("/mcp")
+            .header("authorization", "Bearer sk-admin")
+            .header("mcp-session-id", session_id)
+            .reply(&routes)
+            .await;
+        assert_eq!(deleted.status(), 403);
+
+        // The owner can still use and delete its session
+        let owned = request()
+            .method("POST")
+            .path("/mcp")
+            .header("authorization", "Bearer sk-user")
+            .header("mcp-session-id", session_id)
+            .json(&whoami)
+            .reply(&routes)
+            .await;
+        let body: Value = serde_json::from_slice(owned.body()).unwrap();
+        assert_eq!(body["result"]["data"]["id"], "bob");
+        let deleted = request()
+            .method("DELETE")
+            .path("/mcp")
+            .header("authorization", "Bearer sk-user")
+            .header("mcp-session-id", session_id)
+            .reply(&routes)
+            .await;
+        assert_eq!(deleted.status(), 204);
+    }
+
+    #[tokio::test]
+    async fn test_custom_authenticator_and_challenge() {
+        struct TenantHeader;
+
+        #[async_trait]
+        impl Authenticator for TenantHeader {
+            async fn authenticate(
+                &self,
+                connection: &ConnectionInfo,
+            ) -> Result<Principal, AuthError> {
+                let tenant = connection
+                    .headers
+                    .get("x-tenant")
+                    .and_then(|v| v.to_str().ok())
+                    .ok_or(AuthError::MissingCredentials)?;
+                Ok(Principal::new(tenant))
+            }
+
+            fn challenge(&self, _error: &AuthError) -> String {
+                r#"Tenant realm="internal""#.to_string()
+            }
+        }
+
+        let routes = HttpMcpHandler::new(engine(TenantHeader)).route();
+
+        let rejected = request()
+            .method("POST")
+            .path("/mcp")
+            .json(&initialize())
+            .reply(&routes)
+            .await;
+        assert_eq!(rejected.status(), 401);
+        assert_eq!(
+            rejected.headers()["www-authenticate"],
+            r#"Tenant realm="internal""#
+        );
+
+        let accepted = request()
+            .method("POST")
+            .path("/mcp")
+            .header("x-tenant", "acme")
+            .json(&initialize())
+            .reply(&routes)
+            .await;
+        assert_eq!(accepted.status(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_websocket_upgrade_requires_credentials() {
+        let routes = create_ws_handler(engine(api_keys()));
+
+        let resp = request()
+            .method("GET")
+            .path("/mcp")
+            .header("connection", "upgrade")
+            .header("upgrade", "websocket")
+            .header("sec-websocket-version", "13")
+            .header("sec-websocket-key", "dGhlIHNhbXBsZSBub25jZQ==")
+            .reply(&routes)
+            .await;
+        assert_eq!(resp.status(), 401);
+        assert!(resp.headers().contains_key("www-authenticate"));
+
+        let connected = warp::test::ws()
+            .path("/mcp")
+            .header("authorization", "Bearer sk-user")
+            .handshake(routes)
+            .await;
+        assert!(connected.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_options_preflight_stays_open() {
+        let routes = HttpMcpHandler::new(engine(api_keys())).route();
+
+        let resp = request()
+            .method("OPTIONS")
+            .path("/mcp")
+            .reply(&routes)
+            .await;
+        assert_ne!(resp.status(), 401);
+    }
+
+    #[tokio::test]
+    async fn test_missing_scopes_get_403_with_insufficient_scope() {
+        let mut framework = FrameworkHandler::new((), "auth-server", "1.0.0");
+        framework.set_authenticator(api_keys());
+        let registry = framework.registry_handle();
+        registry.add_tool(
+            "whoami",
+            "Who am I",
+            |_input: Empty, _ctx, notif| async move {
+                Ok(WhoAmI {
+                    id: notif.principal().map(|p| p.id.clone()),
+                    admin: true,
+                })
+            },
+        );
+        let routes = HttpMcpHandler::new(Arc::new(McpProtocolEngine::with_handler(Arc::new(
+            framework,
+        ))))
+        .route();
+        let call = |key: &'static str| {
+            request()
+                .method("POST")
+                .path("/mcp")
+                .header("x-api-key", key)
+                .json(&json!({
+                    "jsonrpc": "2.0",
+                    "id": 2,
+                    "method": "tools/call",
+                    "params": { "name": "whoami", "arguments": {} }
+                }))
+        };
+
+        assert_eq!(call("sk-user").reply(&routes).await.status(), 200);
+
+        // Scopes required at runtime apply to the next call
+        registry.require_tool_scopes("whoami", ["admin"]).unwrap();
+        let resp = call("sk-user").reply(&routes).await;
+        assert_eq!(resp.status(), 403);
+        assert_eq!(
+            resp.headers()["www-authenticate"],
+            r#"Bearer realm="mcp", error="insufficient_scope", scope="admin""#
+        );
+        let body: Value = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(body["id"], 2);
+        assert_eq!(body["error"]["code"], -32003);
+        assert_eq!(
+            body["error"]["message"],
+            "Forbidden: tool 'whoami' requires scope admin"
+        );
+
+        let resp = call("sk-admin").reply(&routes).await;
+        assert_eq!(resp.status(), 200);
+        let body: Value = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(body["result"]["data"]["id"], "alice");
+    }
+
+    #[test]
+    fn test_registry_handle_manages_tool_scopes() {
+        let framework = FrameworkHandler::new((), "auth-server", "1.0.0");
+        let registry = framework.registry_handle();
+
+        // Scopes can be set before the tool exists and survive its removal
+        registry
+            .require_tool_scopes("deploy", ["deploy", "admin"])
+            .unwrap();
+        registry.require_tool_scopes("deploy", ["admin"]).unwrap();
+        registry.add_tool(
+            "deploy",
+            "Deploy",
+            |_input: Empty, _ctx, _notif| async move {
+                Ok(WhoAmI {
+                    id: None,
+                    admin: false,
+                })
+            },
+        );
+        assert!(registry.remove_tool("deploy"));
+        assert_eq!(registry.tool_scopes("deploy"), ["deploy", "admin"]);
+
+        let error = registry
+            .require_tool_scopes("deploy", ["ops", "bad scope"])
+            .unwrap_err();
+        assert_eq!(error.to_string(), r#"Invalid scopes: "bad scope""#);
+        assert_eq!(registry.tool_scopes("deploy"), ["deploy", "admin"]);
+
+        assert!(registry.clear_tool_scopes("deploy"));
+        assert!(registry.tool_scopes("deploy").is_empty());
+        assert!(!registry.clear_tool_scopes("deploy"));
+    }
+
+    #[tokio::test]
+    async fn test_build_rejects_invalid_tool_scopes() {
+        let error = McpServerBuilder::new((), "auth-server", "1.0.0")
+            .with_tool(
+                "deploy",
+                "Deploy",
+                |_input: Empty, _ctx, _notif| async move {
+                    Ok(WhoAmI {
+                        id: None,
+                        admin: false,
+                    })
+                },
+            )
+            .with_tool_scopes("deploy", ["deploy:prod"])
+            .with_tool_scopes("delpoy", ["deploy:prod"])
+            .with_tool_scopes("deploy", ["deploy \"prod\""])
+            .build()
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            r#"Scopes required for unknown tools: delpoy; Invalid scopes: "deploy \"prod\"""#
+        );
+    }
+}
@@ -1,5 +1,8 @@
 //! HTTP-specific tests for MCP server
 
+pub mod auth_tests;
 pub mod handler_tests;
+#[cfg(feature = "oauth")]
+pub mod oauth_tests;
 pub mod protocol_tests;
 pub mod session_tests;
@@ -0,0 +1,326 @@
+//! OAuth protected resource tests: metadata discovery, JWT validation and tool scopes
+
+#[cfg(test)]
+mod tests {
+    use crate::auth::AuthError;
+    use crate::framework::FrameworkHandler;
+    use crate::http::HttpMcpHandler;
+    use crate::oauth::{Jwks, JwtAuthenticator, ProtectedResourceMetadata};
+    use crate::shared::McpProtocolEngine;
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+    use jsonwebtoken::{Algorithm, EncodingKey, Header};
+    use ring::rand::SystemRandom;
+    use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING};
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use std::time::{SystemTime, UNIX_EPOCH};
+    use warp::test::request;
+
+    const ISSUER: &str = "https://auth.example.com";
+    const AUDIENCE: &str = "https://files.example.com/mcp";
+    const METADATA_URL: &str = "https://files.example.com/.well-known/oauth-protected-resource";
+
+    /// A locally generated P-256 signing key and its public JWK.
+    struct SigningKey {
+        kid: &'static str,
+        pkcs8: Vec<u8>,
+        jwk: Value,
+    }
+
+    impl SigningKey {
+        fn generate(kid: &'static str) -> Self {
+            let rng = SystemRandom::new();
+            let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng)
+                .unwrap()
+                .as_ref()
+                .to_vec();
+            let pair =
+                EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &pkcs8, &rng).unwrap();
+            // Uncompressed point: 0x04 || x || y
+            let point = pair.public_key().as_ref();
+            let jwk = json!({
+                "kty": "EC",
+                "crv": "P-256",
+                "kid": kid,
+                "alg": "ES256",
+                "use": "sig",
+                "x": URL_SAFE_NO_PAD.encode(&point[1..33]),
+                "y": URL_SAFE_NO_PAD.encode(&point[33..]),
+            });
+            Self { kid, pkcs8, jwk }
+        }
+
+        fn sign(&self, claims: Value) -> String {
+            let mut header = Header::new(Algorithm::ES256);
+            header.kid = Some(self.kid.to_string());
+            jsonwebtoken::encode(&header, &claims, &EncodingKey::from_ec_der(&self.pkcs8)).unwrap()
+        }
+    }
+
+    fn jwks(keys: &[&SigningKey]) -> Jwks {
+        let keys: Vec<&Value> = keys.iter().map(|key| &key.jwk).collect();
+        Jwks::from_json(&json!({ "keys": keys }).to_string()).unwrap()
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    fn claims(scope: &str) -> Value {
+        json!({
+            "iss": ISSUER,
+            "aud": AUDIENCE,
+            "sub": "alice",
+            "scope": scope,
+            "exp": now() + 300,
+        })
+    }
+
+    fn authenticator(key: &SigningKey) -> JwtAuthenticator {
+        JwtAuthenticator::new(jwks(&[key]), ISSUER, AUDIENCE).with_resource_metadata(METADATA_URL)
+    }
+
+    #[derive(JsonSchema, Deserialize)]
+    struct Empty {}
+
+    #[derive(JsonSchema, Serialize)]
+    struct WhoAmI {
+        id: Option<String>,
+        scopes: Vec<String>,
+    }
+
+    fn engine(key: &SigningKey) -> Arc<McpProtocolEngine> {
+        let mut framework = FrameworkHandler::new((), "files-server", "1.0.0");
+        framework.set_protected_resource_metadata(
+            ProtectedResourceMetadata::new(AUDIENCE)
+                .with_authorization_server(ISSUER)
+                .with_scopes_supported(["files:read", "files:write"]),
+        );
+        framework.set_authenticator(authenticator(key));
+        framework.require_tool_scopes("delete_file", ["files:write"]);
+        let registry = framework.registry_handle();
+        for name in ["whoami", "delete_file"] {
+            registry.add_tool(name, "Test tool", |_input: Empty, _ctx, notif| async move {
+                Ok(WhoAmI {
+                    id: notif.principal().map(|p| p.id.clone()),
+                    scopes: notif
+                        .principal()
+                        .map(|p| p.scopes.clone())
+                        .unwrap_or_default(),
+                })
+            });
+        }
+        Arc::new(McpProtocolEngine::with_handler(Arc::new(framework)))
+    }
+
+    async fn post(engine: &Arc<McpProtocolEngine>, token: &str, message: Value) -> (u16, Value) {
+        let resp = request()
+            .method("POST")
+            .path("/mcp")
+            .header("authorization", format!("Bearer {token}"))
+            .json(&message)
+            .reply(&HttpMcpHandler::new(engine.clone()).route())
+            .await;
+        (
+            resp.status().as_u16(),
+            serde_json::from_slice(resp.body()).unwrap(),
+        )
+    }
+
+    fn call(name: &str) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": { "name": name, "arguments": {} }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_metadata_is_published() {
+        let key = SigningKey::generate("k1");
+        let routes = HttpMcpHandler::new(engine(&key)).route();
+
+        for path in [
+            "/.well-known/oauth-protected-resource",
+            "/.well-known/oauth-protected-resource/mcp",
+        ] {
+            let resp = request().method("GET").path(path).reply(&routes).await;
+            assert_eq!(resp.status(), 200);
+            let body: Value = serde_json::from_slice(resp.body()).unwrap();
+            assert_eq!(
+                body,
+                json!({
+                    "resource": AUDIENCE,
+                    "authorization_servers": [ISSUER],
+                    "scopes_supported": ["files:read", "files:write"],
+                    "bearer_methods_supported": ["header"],
+                })
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metadata_is_absent_without_oauth() {
+        let engine = Arc::new(McpProtocolEngine::with_handler(Arc::new(
+            FrameworkHandler::new((), "plain-server", "1.0.0"),
+        )));
+        let resp = request()
+            .method("GET")
+            .path("/.well-known/oauth-protected-resource")
+            .reply(&HttpMcpHandler::new(engine).route())
+            .await;
+        assert_eq!(resp.status(), 404);
+    }
+
+    #[tokio::test]
+    async fn test_challenge_points_to_metadata() {
+        let key = SigningKey::generate("k1");
+        let routes = HttpMcpHandler::new(engine(&key)).route();
+
+        let resp = request()
+            .method("POST")
+            .path("/mcp")
+            .json(&call("whoami"))
+            .reply(&routes)
+            .await;
+        assert_eq!(resp.status(), 401);
+        assert_eq!(
+            resp.headers()["www-authenticate"],
+            format!(r#"Bearer realm="mcp", resource_metadata="{METADATA_URL}""#).as_str()
+        );
+
+        let resp = request()
+            .method("POST")
+            .path("/mcp")
+            .header("authorization", "Bearer not-a-jwt")
+            .json(&call("whoami"))
+            .reply(&routes)
+            .await;
+        assert_eq!(resp.status(), 401);
+        assert_eq!(
+            resp.headers()["www-authenticate"],
+            format!(
+                r#"Bearer realm="mcp", resource_metadata="{METADATA_URL}", error="invalid_token""#
+            )
+            .as_str()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_valid_token_yields_principal_with_scopes() {
+        let key = SigningKey::generate("k1");
+        let engine = engine(&key);
+        let token = key.sign(claims("files:read files:write"));
+
+        let (status, _) = post(
+            &engine,
+            &token,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "initialize",
+                "params": { "protocolVersion": "2025-06-18" }
+            }),
+        )
+        .await;
+        assert_eq!(status, 200);
+
+        let (status, body) = post(&engine, &token, call("whoami")).await;
+        assert_eq!(status, 200);
+        assert_eq!(body["result"]["data"]["id"], "alice");
+        assert_eq!(
+            body["result"]["data"]["scopes"],
+            json!(["files:read", "files:write"])
+        );
+    }
+
+    #[test]
+    fn test_token_checks() {
+        let key = SigningKey::generate("k1");
+        let other = SigningKey::generate("k1");
+        let auth = authenticator(&key);
+
+        let principal = auth.validate(&key.sign(claims("files:read"))).unwrap();
+        assert_eq!(principal.id, "alice");
+        assert!(principal.has_scope("files:read"));
+        assert_eq!(principal.claims["iss"], ISSUER);
+
+        let mut scp = claims("");
+        scp["scope"] = Value::Null;
+        scp["scp"] = json!(["files:read"]);
+        assert!(auth
+            .validate(&key.sign(scp))
+            .unwrap()
+            .has_scope("files:read"));
+
+        let mut expired = claims("files:read");
+        expired["exp"] = json!(now() - 3600);
+        let mut wrong_audience = claims("files:read");
+        wrong_audience["aud"] = json!("https://other.example.com/mcp");
+        let mut wrong_issuer = claims("files:read");
+        wrong_issuer["iss"] = json!("https://evil.example.com");
+        let mut no_expiry = claims("files:read");
+        no_expiry.as_object_mut().unwrap().remove("exp");
+
+        for token in [
+            key.sign(expired),
+            key.sign(wrong_audience),
+            key.sign(wrong_issuer),
+            key.sign(no_expiry),
+            other.sign(claims("files:read")),
+            SigningKey::generate("k2").sign(claims("files:read")),
+        ] {
+            assert!(matches!(
+                auth.validate(&token),
+                Err(AuthError::InvalidCredentials(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tool_scopes_are_enforced() {
+        let key = SigningKey::generate("k1");
+        let engine = engine(&key);
+
+        let reader = key.sign(claims("files:read"));
+        let resp = request()
+            .method("POST")
+            .path("/mcp")
+            .header("authorization", format!("Bearer {reader}"))
+            .json(&call("delete_file"))
+            .reply(&HttpMcpHandler::new(engine.clone()).route())
+            .await;
+        assert_eq!(resp.status(), 403);
+        assert_eq!(
+            resp.headers()["www-authenticate"],
+            r#"Bearer realm="mcp", error="insufficient_scope", scope="files:write""#
+        );
+        let body: Value = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(body["error"]["code"], -32003);
+        assert_eq!(
+            body["error"]["message"],
+            "Forbidden: tool 'delete_file' requires scope files:write"
+        );
+
+        let writer = key.sign(claims("files:write"));
+        let (_, body) = post(&engine, &writer, call("delete_file")).await;
+        assert_eq!(body["result"]["data"]["id"], "alice");
+    }
+
+    #[test]
+    fn test_jwks_loads_from_file() {
+        let key = SigningKey::generate("k1");
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), json!({ "keys": [key.jwk] }).to_string()).unwrap();
+
+        let auth = JwtAuthenticator::new(Jwks::from_file(file.path()).unwrap(), ISSUER, AUDIENCE);
+        assert_eq!(auth.validate(&key.sign(claims(""))).unwrap().id, "alice");
+        assert!(Jwks::from_file(file.path().with_extension("missing")).is_err());
+    }
+}
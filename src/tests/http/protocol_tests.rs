@@ -79,10 +79,11 @@ mod tests {
         let http_handler = HttpMcpHandler::new(shared_handler);
         let routes = http_handler.route();
 
-        // Call tools/list without initialization
+        // Call tools/list in a session that was never initialized
         let resp = request()
             .method("POST")
             .path("/mcp")
+            .header("mcp-session-id", "never-initialized")
             .json(&json!({
                 "jsonrpc": "2.0",
                 "id": 1,
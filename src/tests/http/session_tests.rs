@@ -2,7 +2,16 @@
 
 #[cfg(test)]
 mod tests {
+    use crate::framework::FrameworkHandler;
     use crate::http::session::{extract_session_id_from_cookie, generate_session_id};
+    use crate::http::HttpMcpHandler;
+    use crate::session_store::SessionEndReason;
+    use crate::shared::McpProtocolEngine;
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+    use std::sync::{Arc, Mutex};
+    use warp::test::request;
 
     #[test]
     fn test_session_id_generation() {
@@ -50,4 +59,151 @@ mod tests {
         let session_id = extract_session_id_from_cookie(&cookie);
         assert_eq!(session_id, Some("found".to_string()));
     }
+
+    #[derive(JsonSchema, Deserialize)]
+    struct Note {
+        text: Option<String>,
+    }
+
+    #[derive(JsonSchema, Serialize)]
+    struct Noted {
+        previous: Option<String>,
+    }
+
+    /// An engine with a `note` tool keeping a string in the session.
+    fn engine(ended: Arc<Mutex<Vec<SessionEndReason>>>) -> Arc<McpProtocolEngine> {
+        let mut framework = FrameworkHandler::new(ended, "session-server", "1.0.0");
+        framework.add_session_end_hook(
+            |ended: Arc<Arc<Mutex<Vec<SessionEndReason>>>>, _id, reason| async move {
+                ended.lock().unwrap().push(reason);
+                Ok(())
+            },
+        );
+        framework.registry_handle().add_tool(
+            "note",
+            "Replace the session's note",
+            |input: Note, _ctx, notif| async move {
+                let session = notif.session();
+                let previous = session.get::<String>();
+                if let Some(text) = input.text {
+                    session.insert(text);
+                }
+                Ok(Noted { previous })
+            },
+        );
+        Arc::new(McpProtocolEngine::with_handler(Arc::new(framework)))
+    }
+
+    async fn post(
+        engine: &Arc<McpProtocolEngine>,
+        session: Option<&str>,
+        method: &str,
+        params: Value,
+    ) -> warp::http::Response<warp::hyper::body::Bytes> {
+        let mut builder = request().method("POST").path("/mcp").json(&json!({
+            "jsonrpc": "2.0", "id": 1, "method": method, "params": params
+        }));
+        if let Some(session) = session {
+            builder = builder.header("mcp-session-id", session);
+        }
+        builder
+            .reply(&HttpMcpHandler::new(engine.clone()).route())
+            .await
+    }
+
+    async fn initialize(engine: &Arc<McpProtocolEngine>) -> String {
+        let response = post(
+            engine,
+            None,
+            "initialize",
+            json!({ "protocolVersion": "2025-06-18" }),
+        )
+        .await;
+        let session_id = response.headers()["mcp-session-id"].to_str().unwrap();
+        let cookie = response.headers()["set-cookie"].to_str().unwrap();
+        assert!(cookie.starts_with(&format!("mcp_session={session_id};")));
+        session_id.to_string()
+    }
+
+    async fn note(engine: &Arc<McpProtocolEngine>, session: &str, text: Option<&str>) -> Value {
+        let response = post(
+            engine,
+            Some(session),
+            "tools/call",
+            json!({ "name": "note", "arguments": { "text": text } }),
+        )
+        .await;
+        let body: Value = serde_json::from_slice(response.body()).unwrap();
+        body["result"]["data"]["previous"].clone()
+    }
+
+    #[tokio::test]
+    async fn test_http_clients_get_isolated_sessions() {
+        let ended = Arc::new(Mutex::new(Vec::new()));
+        let engine = engine(ended.clone());
+        let first = initialize(&engine).await;
+        let second = initialize(&engine).await;
+        assert_ne!(first, second);
+        assert_eq!(engine.sessions().len(), 2);
+
+        // Neither client sees the other's session state
+        assert_eq!(note(&engine, &first, Some("first")).await, Value::Null);
+        assert_eq!(note(&engine, &second, Some("second")).await, Value::Null);
+        assert_eq!(note(&engine, &first, None).await, "first");
+        assert_eq!(note(&engine, &second, None).await, "second");
+
+        // Deleting one session leaves the other alone
+        let deleted = request()
+            .method("DELETE")
+            .path("/mcp")
+            .header("mcp-session-id", &first)
+            .reply(&HttpMcpHandler::new(engine.clone()).route())
+            .await;
+        assert_eq!(deleted.status(), 204);
+        assert_eq!(note(&engine, &second, None).await, "second");
+        assert_eq!(*ended.lock().unwrap(), [SessionEndReason::Deleted]);
+        assert_eq!(engine.sessions().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_reinitializing_replaces_the_named_session() {
+        let ended = Arc::new(Mutex::new(Vec::new()));
+        let engine = engine(ended.clone());
+        let first = initialize(&engine).await;
+        note(&engine, &first, Some("kept")).await;
+
+        let response = post(
+            &engine,
+            Some(&first),
+            "initialize",
+            json!({ "protocolVersion": "2025-06-18" }),
+        )
+        .await;
+        let second = response.headers()["mcp-session-id"].to_str().unwrap();
+        assert_ne!(second, first);
+        assert_eq!(note(&engine, second, None).await, Value::Null);
+        assert_eq!(*ended.lock().unwrap(), [SessionEndReason::Reinitialized]);
+        assert_eq!(engine.sessions().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_requests_without_session_are_stateless() {
+        let engine = engine(Arc::default());
+        initialize(&engine).await;
+
+        // Each stateless request starts from an empty session
+        let response = post(
+            &engine,
+            None,
+            "tools/call",
+            json!({ "name": "note", "arguments": { "text": "lost" } }),
+        )
+        .await;
+        let body: Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(body["result"]["data"]["previous"], Value::Null);
+        let response = post(&engine, None, "tools/list", json!({})).await;
+        let body: Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(body["result"]["tools"][0]["name"], "note");
+        assert_eq!(engine.sessions().len(), 1);
+    }
 }
@@ -0,0 +1,155 @@
+//! Metrics Tests
+//!
+//! Tests the per-method and per-tool statistics, error counts, session and
+//! WebSocket gauges recorded by the protocol engine, and the Prometheus
+//! rendering served by the metrics endpoint.
+
+#[cfg(test)]
+mod tests {
+    use crate::framework::FrameworkHandler;
+    use crate::metrics::{self, TransportKind};
+    use crate::session_store::SessionEndReason;
+    use crate::shared::McpProtocolEngine;
+    use crate::tests::support::{initialize, send};
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+    use std::sync::Arc;
+    use warp::test::request;
+
+    #[derive(JsonSchema, Deserialize)]
+    struct EchoInput {
+        text: String,
+    }
+
+    #[derive(JsonSchema, Serialize)]
+    struct EchoOutput {
+        text: String,
+    }
+
+    fn engine() -> McpProtocolEngine {
+        let mut framework = FrameworkHandler::new((), "metrics-server", "1.0.0");
+        framework.set_metrics_path("/internal/metrics");
+        framework.registry_handle().add_tool(
+            "echo",
+            "Echo",
+            |input: EchoInput, _ctx, _notif| async move {
+                if input.text.is_empty() {
+                    anyhow::bail!("nothing to echo");
+                }
+                Ok(EchoOutput { text: input.text })
+            },
+        );
+        McpProtocolEngine::with_handler(Arc::new(framework))
+    }
+
+    #[tokio::test]
+    async fn test_methods_and_tools_are_counted() {
+        let engine = engine();
+        initialize(&engine, "a").await;
+        let _ = send(&engine, "a", "tools/list", json!({})).await;
+        for text in ["hi", "there", ""] {
+            let arguments = json!({ "name": "echo", "arguments": { "text": text } });
+            let _ = send(&engine, "a", "tools/call", arguments).await;
+        }
+        let _ = send(
+            &engine,
+            "a",
+            "tools/call",
+            json!({ "name": "nope", "arguments": {} }),
+        )
+        .await;
+        let _ = send(&engine, "a", "made/up", json!({})).await;
+
+        let snapshot = engine.metrics().snapshot();
+        assert_eq!(snapshot.methods["initialize"].requests, 1);
+        assert_eq!(snapshot.methods["tools/list"].requests, 1);
+        assert_eq!(snapshot.methods["tools/list"].latency.count, 1);
+        assert_eq!(snapshot.methods["tools/call"].requests, 4);
+        assert!(!snapshot.methods.contains_key("made/up"));
+        assert_eq!(snapshot.methods["unknown"].requests, 1);
+
+        let echo = &snapshot.tools["echo"];
+        assert_eq!((echo.requests, echo.errors), (3, 1));
+        assert_eq!(snapshot.tools["nope"].errors, 1);
+        assert_eq!(snapshot.errors.get(&-32601), Some(&1));
+        // Failing tools reach the transport as internal errors
+        assert_eq!(snapshot.errors.get(&-32603), Some(&2));
+        assert_eq!(snapshot.request_size.count, 7);
+        assert!(snapshot.response_size.sum > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_sessions_are_counted_by_transport() {
+        let engine = engine();
+        initialize(&engine, "http-1").await;
+        engine.metrics().websocket_opened("ws-1");
+        initialize(&engine, "ws-1").await;
+        engine.metrics().notification_queue("ws-1", 3);
+
+        let snapshot = engine.metrics().snapshot();
+        assert_eq!(snapshot.active_sessions[&TransportKind::Http], 1);
+        assert_eq!(snapshot.active_sessions[&TransportKind::WebSocket], 1);
+        assert_eq!(snapshot.websocket_connections, 1);
+        assert_eq!(snapshot.notification_queues["ws-1"], 3);
+
+        engine.metrics().websocket_closed("ws-1");
+        engine.end_session("ws-1", SessionEndReason::Closed).await;
+        let snapshot = engine.metrics().snapshot();
+        assert_eq!(
+            snapshot.active_sessions.get(&TransportKind::WebSocket),
+            None
+        );
+        assert_eq!(snapshot.websocket_connections, 0);
+        assert!(snapshot.notification_queues.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_metrics_are_served_in_prometheus_format() {
+        let engine = engine();
+        initialize(&engine, "a").await;
+        let _ = send(
+            &engine,
+            "a",
+            "tools/call",
+            json!({ "name": "echo", "arguments": { "text": "x" } }),
+        )
+        .await;
+        let routes = metrics::route(engine.metrics().clone(), engine.metrics_path());
+
+        let response = request()
+            .method("GET")
+            .path("/internal/metrics")
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), 200);
+        assert!(response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain; version=0.0.4"));
+        let body = String::from_utf8(response.body().to_vec()).unwrap();
+        for line in [
+            "# TYPE solidmcp_requests_total counter",
+            "solidmcp_requests_total{method=\"tools/call\"} 1",
+            "solidmcp_tool_calls_total{tool=\"echo\"} 1",
+            "solidmcp_tool_calls_errors_total{tool=\"echo\"} 0",
+            "solidmcp_tool_calls_duration_seconds_bucket{tool=\"echo\",le=\"+Inf\"} 1",
+            "solidmcp_active_sessions{transport=\"http\"} 1",
+            "solidmcp_active_sessions{transport=\"websocket\"} 0",
+            "solidmcp_websocket_connections 0",
+            "solidmcp_request_size_bytes_count 2",
+        ] {
+            assert!(
+                body.lines().any(|l| l == line),
+                "missing {line:?} in:\n{body}"
+            );
+        }
+
+        let other = request()
+            .method("GET")
+            .path("/metrics")
+            .reply(&routes)
+            .await;
+        assert_eq!(other.status(), 404);
+    }
+}
@@ -0,0 +1,226 @@
+//! Middleware Tests
+//!
+//! Tests the middleware chain wrapped around message dispatch: ordering,
+//! short-circuiting, request rewriting and response transformation.
+
+#[cfg(test)]
+mod tests {
+    use crate::framework::FrameworkHandler;
+    use crate::middleware::{McpRequest, Middleware, Next};
+    use crate::protocol_impl::McpError;
+    use crate::shared::McpProtocolEngine;
+    use crate::tests::support::{initialize, send};
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(JsonSchema, Deserialize)]
+    struct EchoInput {
+        text: String,
+    }
+
+    #[derive(JsonSchema, Serialize)]
+    struct EchoOutput {
+        text: String,
+    }
+
+    /// Records the order in which layers see requests and responses.
+    struct Recorder {
+        label: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Middleware for Recorder {
+        async fn handle(&self, request: McpRequest, next: Next<'_>) -> Result<Value> {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{} > {}", self.label, request.method));
+            let response = next.run(request).await;
+            self.log.lock().unwrap().push(format!("{} <", self.label));
+            response
+        }
+    }
+
+    /// Rejects calls to tools whose name starts with `admin_`.
+    struct DenyAdmin;
+
+    #[async_trait]
+    impl Middleware for DenyAdmin {
+        async fn handle(&self, request: McpRequest, next: Next<'_>) -> Result<Value> {
+            if request.method == "tools/call"
+                && request
+                    .name()
+                    .is_some_and(|name| name.starts_with("admin_"))
+            {
+                return Err(McpError::InvalidParams("admin tools are disabled".to_string()).into());
+            }
+            next.run(request).await
+        }
+    }
+
+    /// Upper-cases echo input and redacts echo output.
+    struct Rewrite;
+
+    #[async_trait]
+    impl Middleware for Rewrite {
+        async fn handle(&self, mut request: McpRequest, next: Next<'_>) -> Result<Value> {
+            if let Some(text) = request
+                .params
+                .as_mut()
+                .and_then(|params| params.pointer_mut("/arguments/text"))
+            {
+                *text = json!(text.as_str().unwrap_or("").to_uppercase());
+            }
+            let mut response = next.run(request).await?;
+            if let Some(data) = response.pointer_mut("/result/data") {
+                data["redacted"] = json!(true);
+            }
+            Ok(response)
+        }
+    }
+
+    /// Answers a method no handler knows about.
+    struct CustomMethod;
+
+    #[async_trait]
+    impl Middleware for CustomMethod {
+        async fn handle(&self, request: McpRequest, next: Next<'_>) -> Result<Value> {
+            if request.method == "custom/whoami" {
+                return Ok(request.respond(json!({ "session": request.context.session_id })));
+            }
+            next.run(request).await
+        }
+    }
+
+    fn framework() -> FrameworkHandler<()> {
+        let framework = FrameworkHandler::new((), "middleware-server", "1.0.0");
+        let registry = framework.registry_handle();
+        for name in ["echo", "admin_echo"] {
+            registry.add_tool(name, "Echo", |input: EchoInput, _ctx, _notif| async move {
+                Ok(EchoOutput { text: input.text })
+            });
+        }
+        framework
+    }
+
+    async fn initialized(framework: FrameworkHandler<()>) -> McpProtocolEngine {
+        let engine = McpProtocolEngine::with_handler(Arc::new(framework));
+        initialize(&engine, "middleware").await;
+        engine
+    }
+
+    #[tokio::test]
+    async fn test_layers_run_in_registration_order_for_every_method() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut framework = framework();
+        framework.add_middleware(Recorder {
+            label: "outer",
+            log: log.clone(),
+        });
+        framework.add_middleware(Recorder {
+            label: "inner",
+            log: log.clone(),
+        });
+        let engine = initialized(framework).await;
+
+        send(&engine, "middleware", "tools/list", json!({}))
+            .await
+            .unwrap();
+        send(&engine, "middleware", "resources/list", json!({}))
+            .await
+            .unwrap();
+        send(&engine, "middleware", "prompts/list", json!({}))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "outer > initialize",
+                "inner > initialize",
+                "inner <",
+                "outer <",
+                "outer > tools/list",
+                "inner > tools/list",
+                "inner <",
+                "outer <",
+                "outer > resources/list",
+                "inner > resources/list",
+                "inner <",
+                "outer <",
+                "outer > prompts/list",
+                "inner > prompts/list",
+                "inner <",
+                "outer <",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_short_circuit_with_mcp_error() {
+        let mut framework = framework();
+        framework.add_middleware(DenyAdmin);
+        let engine = initialized(framework).await;
+
+        let denied = send(
+            &engine,
+            "middleware",
+            "tools/call",
+            json!({ "name": "admin_echo", "arguments": { "text": "x" } }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(denied["id"], 1);
+        assert_eq!(denied["error"]["code"], -32602);
+        assert_eq!(
+            denied["error"]["message"],
+            "Invalid params: admin tools are disabled"
+        );
+
+        let allowed = send(
+            &engine,
+            "middleware",
+            "tools/call",
+            json!({ "name": "echo", "arguments": { "text": "x" } }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(allowed["result"]["data"]["text"], "x");
+    }
+
+    #[tokio::test]
+    async fn test_request_and_response_can_be_rewritten() {
+        let mut framework = framework();
+        framework.add_middleware(Rewrite);
+        let engine = initialized(framework).await;
+
+        let response = send(
+            &engine,
+            "middleware",
+            "tools/call",
+            json!({ "name": "echo", "arguments": { "text": "quiet" } }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response["result"]["data"]["text"], "QUIET");
+        assert_eq!(response["result"]["data"]["redacted"], true);
+    }
+
+    #[tokio::test]
+    async fn test_custom_method_answered_by_middleware() {
+        let mut framework = framework();
+        framework.add_middleware(CustomMethod);
+        let engine = initialized(framework).await;
+
+        let response = send(&engine, "middleware", "custom/whoami", json!({}))
+            .await
+            .unwrap();
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"]["session"], "middleware");
+    }
+}
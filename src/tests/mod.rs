@@ -2,19 +2,89 @@
 //!
 //! Tests for Model Context Protocol server functionality.
 
+pub mod admin_tests;
+pub mod audit_tests;
 pub mod capability_negotiation_tests;
 pub mod dependency_integration_tests;
 pub mod edge_case_tests;
 pub mod error_handling_tests;
 pub mod handler_trait_tests;
+pub mod health_tests;
 pub mod http;
 pub mod jsonrpc_compliance_tests;
+pub mod metrics_tests;
+pub mod middleware_tests;
 pub mod notifications_tests;
+pub mod prompt_content_tests;
 pub mod protocol_parsing_tests;
 pub mod protocol_tests;
+pub mod provider_routing_tests;
+pub mod rate_limit_tests;
+pub mod request_context_tests;
+pub mod resource_content_tests;
+pub mod runtime_registry_tests;
+pub mod session_extensions_tests;
+pub mod session_lifecycle_tests;
 pub mod session_management_tests;
+pub mod session_store_tests;
+pub mod shutdown_tests;
+pub mod single_resource_tests;
+pub mod tls_tests;
+pub mod tool_concurrency_tests;
+pub mod tool_visibility_tests;
 pub mod tools_tests;
+pub mod trace_context_tests;
 pub mod transport_integration_tests;
+pub mod typed_prompt_tests;
+
+/// Helpers shared by the tests that drive a `McpProtocolEngine` directly.
+#[cfg(test)]
+pub(crate) mod support {
+    use crate::shared::McpProtocolEngine;
+    use anyhow::Result;
+    use serde_json::{json, Value};
+
+    /// Send a JSON-RPC request with id 1 on behalf of `session`.
+    pub(crate) async fn send(
+        engine: &McpProtocolEngine,
+        session: &str,
+        method: &str,
+        params: Value,
+    ) -> Result<Value> {
+        send_with_id(engine, session, 1, method, params).await
+    }
+
+    /// Send a JSON-RPC request with the given id on behalf of `session`.
+    pub(crate) async fn send_with_id(
+        engine: &McpProtocolEngine,
+        session: &str,
+        id: u64,
+        method: &str,
+        params: Value,
+    ) -> Result<Value> {
+        engine
+            .handle_message(
+                json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }),
+                Some(session.to_string()),
+            )
+            .await
+    }
+
+    /// Initialize `session` as the client `test-client` 1.0.
+    pub(crate) async fn initialize(engine: &McpProtocolEngine, session: &str) -> Value {
+        send(
+            engine,
+            session,
+            "initialize",
+            json!({
+                "protocolVersion": "2025-06-18",
+                "clientInfo": { "name": "test-client", "version": "1.0" }
+            }),
+        )
+        .await
+        .unwrap()
+    }
+}
 
 #[cfg(test)]
 mod integration_tests {
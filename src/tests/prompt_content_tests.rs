@@ -0,0 +1,117 @@
+//! Prompt Content Tests
+//!
+//! Tests that `prompts/get` serializes typed roles, rich message content and the
+//! optional prompt description.
+
+#[cfg(test)]
+mod tests {
+    use crate::content_types::McpContent;
+    use crate::framework::{FrameworkHandler, PromptProvider};
+    use crate::handler::{PromptContent, PromptInfo, PromptMessage, ResourceContent};
+    use crate::shared::McpProtocolEngine;
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+
+    struct ScreenshotPromptProvider;
+
+    #[async_trait]
+    impl PromptProvider<()> for ScreenshotPromptProvider {
+        async fn list_prompts(&self, _context: Arc<()>) -> Result<Vec<PromptInfo>> {
+            Ok(vec![PromptInfo {
+                name: "explain_screen".to_string(),
+                description: None,
+                arguments: vec![],
+            }])
+        }
+
+        async fn get_prompt(
+            &self,
+            name: &str,
+            _arguments: Option<Value>,
+            _context: Arc<()>,
+        ) -> Result<PromptContent> {
+            if name != "explain_screen" {
+                return Err(anyhow::anyhow!("Prompt not found: {}", name));
+            }
+            let source = ResourceContent::text(
+                "file:///src/main.rs",
+                Some("text/x-rust".to_string()),
+                "fn main() {}",
+            );
+            Ok(PromptContent {
+                description: Some("Explain a screenshot alongside its source".to_string()),
+                messages: vec![
+                    PromptMessage::user("What does this screen show?"),
+                    PromptMessage::user(McpContent::image_from_bytes(b"GIF89a\x01\x00\x01\x00")),
+                    PromptMessage::user(McpContent::embedded(source.into())),
+                    PromptMessage::assistant("Let me look at both."),
+                ],
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prompt_get_serializes_rich_messages() {
+        let mut framework = FrameworkHandler::new((), "prompt-server", "1.0.0");
+        framework
+            .registry_mut()
+            .register_prompt_provider(Box::new(ScreenshotPromptProvider));
+        let engine = McpProtocolEngine::with_handler(Arc::new(framework));
+        let session = Some("prompts".to_string());
+
+        engine
+            .handle_message(
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 0,
+                    "method": "initialize",
+                    "params": { "protocolVersion": "2025-06-18" }
+                }),
+                session.clone(),
+            )
+            .await
+            .unwrap();
+
+        let response = engine
+            .handle_message(
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "prompts/get",
+                    "params": { "name": "explain_screen" }
+                }),
+                session,
+            )
+            .await
+            .unwrap();
+
+        let result = &response["result"];
+        assert_eq!(
+            result["description"],
+            "Explain a screenshot alongside its source"
+        );
+
+        let messages = result["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 4);
+        assert_eq!(
+            messages[0],
+            json!({ "role": "user", "content": { "type": "text", "text": "What does this screen show?" } })
+        );
+        assert_eq!(messages[1]["content"]["type"], "image");
+        assert_eq!(messages[1]["content"]["mimeType"], "image/gif");
+        assert_eq!(
+            messages[2]["content"],
+            json!({
+                "type": "resource",
+                "resource": {
+                    "uri": "file:///src/main.rs",
+                    "mimeType": "text/x-rust",
+                    "text": "fn main() {}"
+                }
+            })
+        );
+        assert_eq!(messages[3]["role"], "assistant");
+    }
+}
@@ -0,0 +1,219 @@
+//! Provider Routing Tests
+//!
+//! Tests that resource reads and prompt requests are dispatched to the provider
+//! that declared ownership, that its errors reach the client, and that
+//! conflicting declarations are rejected by `build()`.
+
+#[cfg(test)]
+mod tests {
+    use crate::framework::{
+        FrameworkHandler, McpServerBuilder, PromptProvider, ResourceProvider, ResourceRoute,
+    };
+    use crate::handler::{PromptContent, PromptInfo, PromptMessage, ResourceContent, ResourceInfo};
+    use crate::shared::McpProtocolEngine;
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+
+    /// Serves every URI it is asked for, tagged with its own label
+    struct LabelledProvider {
+        label: &'static str,
+        routes: Vec<ResourceRoute>,
+    }
+
+    #[async_trait]
+    impl ResourceProvider<()> for LabelledProvider {
+        async fn list_resources(&self, _context: Arc<()>) -> Result<Vec<ResourceInfo>> {
+            Ok(vec![])
+        }
+
+        async fn read_resource(&self, uri: &str, _context: Arc<()>) -> Result<ResourceContent> {
+            if uri.ends_with("/broken") {
+                return Err(anyhow::anyhow!("{}: disk failure", self.label));
+            }
+            Ok(ResourceContent::text(uri, None, self.label))
+        }
+
+        fn routes(&self) -> Vec<ResourceRoute> {
+            self.routes.clone()
+        }
+    }
+
+    fn provider(label: &'static str, routes: Vec<ResourceRoute>) -> Box<LabelledProvider> {
+        Box::new(LabelledProvider { label, routes })
+    }
+
+    struct NamedPromptProvider {
+        names: Vec<&'static str>,
+    }
+
+    #[async_trait]
+    impl PromptProvider<()> for NamedPromptProvider {
+        async fn list_prompts(&self, _context: Arc<()>) -> Result<Vec<PromptInfo>> {
+            Ok(vec![])
+        }
+
+        async fn get_prompt(
+            &self,
+            name: &str,
+            _arguments: Option<Value>,
+            _context: Arc<()>,
+        ) -> Result<PromptContent> {
+            if name == "broken" {
+                return Err(anyhow::anyhow!("template store unavailable"));
+            }
+            Ok(PromptContent {
+                description: None,
+                messages: vec![PromptMessage::user(name)],
+            })
+        }
+
+        fn prompt_names(&self) -> Vec<String> {
+            self.names.iter().map(|n| n.to_string()).collect()
+        }
+    }
+
+    async fn request(engine: &McpProtocolEngine, method: &str, params: Value) -> Result<Value> {
+        let session = Some("routing".to_string());
+        engine
+            .handle_message(
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 0,
+                    "method": "initialize",
+                    "params": { "protocolVersion": "2025-06-18" }
+                }),
+                session.clone(),
+            )
+            .await
+            .unwrap();
+        engine
+            .handle_message(
+                json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }),
+                session,
+            )
+            .await
+    }
+
+    fn routed_engine() -> McpProtocolEngine {
+        let mut framework = FrameworkHandler::new((), "routing-server", "1.0.0");
+        let mut registry = framework.registry_mut();
+        // Registered first and accepts everything, so it would win without routing
+        registry.register_resource_provider(provider("fallback", vec![]));
+        registry.register_resource_provider(provider("scheme", vec![ResourceRoute::scheme("db")]));
+        registry.register_resource_provider(provider(
+            "prefix",
+            vec![ResourceRoute::prefix("db://logs/")],
+        ));
+        registry.register_resource_provider(provider(
+            "template",
+            vec![ResourceRoute::template("db://users/{id}")],
+        ));
+        registry.register_resource_provider(provider(
+            "exact",
+            vec![ResourceRoute::exact("db://users/admin")],
+        ));
+        registry.register_prompt_provider(Box::new(NamedPromptProvider { names: vec![] }));
+        registry.register_prompt_provider(Box::new(NamedPromptProvider {
+            names: vec!["summary", "broken"],
+        }));
+        drop(registry);
+        McpProtocolEngine::with_handler(Arc::new(framework))
+    }
+
+    async fn read_label(engine: &McpProtocolEngine, uri: &str) -> Value {
+        request(engine, "resources/read", json!({ "uri": uri }))
+            .await
+            .unwrap()["result"]["contents"][0]["text"]
+            .clone()
+    }
+
+    #[tokio::test]
+    async fn test_reads_go_to_most_specific_owner() {
+        let engine = routed_engine();
+        assert_eq!(read_label(&engine, "db://users/admin").await, "exact");
+        assert_eq!(read_label(&engine, "db://users/42").await, "template");
+        assert_eq!(read_label(&engine, "db://logs/today").await, "prefix");
+        assert_eq!(read_label(&engine, "db://orders").await, "scheme");
+        assert_eq!(read_label(&engine, "file:///tmp/x").await, "fallback");
+    }
+
+    #[tokio::test]
+    async fn test_owner_errors_reach_the_client() {
+        let engine = routed_engine();
+        let error = request(
+            &engine,
+            "resources/read",
+            json!({ "uri": "db://logs/broken" }),
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            error.to_string().contains("prefix: disk failure"),
+            "{}",
+            error
+        );
+
+        let error = request(&engine, "prompts/get", json!({ "name": "broken" }))
+            .await
+            .unwrap_err();
+        assert!(
+            error.to_string().contains("template store unavailable"),
+            "{}",
+            error
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prompts_go_to_owner() {
+        let engine = routed_engine();
+        let response = request(&engine, "prompts/get", json!({ "name": "summary" }))
+            .await
+            .unwrap();
+        assert_eq!(
+            response["result"]["messages"][0]["content"]["text"],
+            "summary"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build_rejects_conflicting_routes() {
+        let result = McpServerBuilder::new((), "conflict-server", "1.0.0")
+            .with_resource_provider(provider("a", vec![ResourceRoute::template("note://{id}")]))
+            .with_resource_provider(provider(
+                "b",
+                vec![ResourceRoute::template("note://{name}")],
+            ))
+            .with_prompt_provider(Box::new(NamedPromptProvider {
+                names: vec!["daily"],
+            }))
+            .with_prompt_provider(Box::new(NamedPromptProvider {
+                names: vec!["daily"],
+            }))
+            .build()
+            .await;
+
+        let message = result.err().unwrap().to_string();
+        assert!(
+            message.contains("resource route 'note://{name}'"),
+            "{}",
+            message
+        );
+        assert!(message.contains("prompt 'daily'"), "{}", message);
+    }
+
+    #[tokio::test]
+    async fn test_build_accepts_overlapping_routes() {
+        let result = McpServerBuilder::new((), "overlap-server", "1.0.0")
+            .with_resource_provider(provider("a", vec![ResourceRoute::scheme("note")]))
+            .with_resource_provider(provider(
+                "b",
+                vec![ResourceRoute::prefix("note://archive/")],
+            ))
+            .with_static_resource("note://readme", "Readme", "text/plain", "hello")
+            .build()
+            .await;
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,198 @@
+//! Rate Limit Tests
+//!
+//! Tests the token-bucket limits and daily quotas of `RateLimiter`.
+
+#[cfg(test)]
+mod tests {
+    use crate::auth::Principal;
+    use crate::framework::FrameworkHandler;
+    use crate::handler::McpContext;
+    use crate::middleware::McpRequest;
+    use crate::rate_limit::{LimitKey, RateLimit, RateLimiter, RATE_LIMITED};
+    use crate::shared::McpProtocolEngine;
+    use anyhow::Result;
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[derive(JsonSchema, Deserialize)]
+    struct Empty {}
+
+    #[derive(JsonSchema, Serialize)]
+    struct Done {
+        ok: bool,
+    }
+
+    async fn engine(limiter: RateLimiter) -> McpProtocolEngine {
+        let mut framework = FrameworkHandler::new((), "rate-limit-server", "1.0.0");
+        for name in ["render", "search"] {
+            framework.registry_handle().add_tool(
+                name,
+                "Test tool",
+                |_input: Empty, _ctx, _notif| async move { Ok(Done { ok: true }) },
+            );
+        }
+        framework.add_middleware(limiter);
+        let engine = McpProtocolEngine::with_handler(Arc::new(framework));
+        for session in ["a", "b"] {
+            engine
+                .handle_message(
+                    json!({
+                        "jsonrpc": "2.0",
+                        "id": 0,
+                        "method": "initialize",
+                        "params": { "protocolVersion": "2025-06-18" }
+                    }),
+                    Some(session.to_string()),
+                )
+                .await
+                .unwrap();
+        }
+        engine
+    }
+
+    async fn call(engine: &McpProtocolEngine, session: &str, tool: &str) -> Result<Value> {
+        engine
+            .handle_message(
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 7,
+                    "method": "tools/call",
+                    "params": { "name": tool, "arguments": {} }
+                }),
+                Some(session.to_string()),
+            )
+            .await
+    }
+
+    fn is_limited(response: &Value) -> bool {
+        response["error"]["code"] == RATE_LIMITED
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_session_limit_refills_over_time() {
+        let engine =
+            engine(RateLimiter::new().limit(LimitKey::Session, RateLimit::per_second(3))).await;
+
+        // `initialize` took the first token of each session
+        assert!(!is_limited(&call(&engine, "a", "search").await.unwrap()));
+        assert!(!is_limited(&call(&engine, "a", "search").await.unwrap()));
+        let limited = call(&engine, "a", "search").await.unwrap();
+        assert_eq!(limited["id"], 7);
+        assert_eq!(limited["error"]["message"], "Rate limit exceeded");
+        assert_eq!(limited["error"]["data"]["retryAfter"], 1);
+
+        // Other sessions have their own bucket
+        assert!(!is_limited(&call(&engine, "b", "search").await.unwrap()));
+
+        tokio::time::advance(Duration::from_millis(400)).await;
+        assert!(!is_limited(&call(&engine, "a", "search").await.unwrap()));
+        assert!(is_limited(&call(&engine, "a", "search").await.unwrap()));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_tool_limit_only_applies_to_that_tool() {
+        let engine = engine(RateLimiter::new().limit_tool(
+            "render",
+            LimitKey::Global,
+            RateLimit::per_minute(1),
+        ))
+        .await;
+
+        assert!(!is_limited(&call(&engine, "a", "render").await.unwrap()));
+        let limited = call(&engine, "b", "render").await.unwrap();
+        assert!(is_limited(&limited));
+        assert_eq!(limited["error"]["data"]["retryAfter"], 60);
+
+        for _ in 0..5 {
+            assert!(!is_limited(&call(&engine, "b", "search").await.unwrap()));
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rejected_request_takes_no_tokens() {
+        let engine = engine(
+            RateLimiter::new()
+                .limit(LimitKey::Session, RateLimit::per_minute(3))
+                .limit_tool("render", LimitKey::Session, RateLimit::per_minute(1)),
+        )
+        .await;
+
+        assert!(!is_limited(&call(&engine, "a", "render").await.unwrap()));
+        assert!(is_limited(&call(&engine, "a", "render").await.unwrap()));
+        // The rejected render did not use up the session's last token
+        assert!(!is_limited(&call(&engine, "a", "search").await.unwrap()));
+        assert!(is_limited(&call(&engine, "a", "search").await.unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_principal_limit_is_shared_across_sessions() {
+        let limiter = RateLimiter::new().limit(LimitKey::Principal, RateLimit::per_minute(1));
+        let request = |session: &str, principal: Option<&str>| {
+            let mut context = McpContext {
+                session_id: Some(session.to_string()),
+                ..Default::default()
+            };
+            context.principal = principal.map(Principal::new);
+            McpRequest::new(
+                json!({ "jsonrpc": "2.0", "id": 1, "method": "ping" }),
+                context,
+            )
+        };
+
+        assert!(limiter.acquire(&request("a", Some("alice"))).is_ok());
+        assert!(limiter.acquire(&request("b", Some("alice"))).is_err());
+        // Unauthenticated requests fall back to their session
+        assert!(limiter.acquire(&request("c", None)).is_ok());
+        assert!(limiter.acquire(&request("d", None)).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_daily_quota_usage_can_be_read_and_reset() {
+        let limiter = RateLimiter::new().daily_quota(LimitKey::Session, 2);
+        let usage = limiter.quota_usage();
+        let engine = engine(limiter).await;
+
+        assert!(!is_limited(&call(&engine, "a", "search").await.unwrap()));
+        assert!(!is_limited(&call(&engine, "a", "render").await.unwrap()));
+        let exceeded = call(&engine, "a", "search").await.unwrap();
+        assert_eq!(exceeded["error"]["message"], "Daily quota exceeded");
+        assert!(exceeded["error"]["data"]["retryAfter"].as_u64().unwrap() <= 86_400);
+
+        // Only tool calls count against the quota
+        let listed = engine
+            .handle_message(
+                json!({ "jsonrpc": "2.0", "id": 8, "method": "tools/list", "params": {} }),
+                Some("a".to_string()),
+            )
+            .await
+            .unwrap();
+        assert!(listed["result"]["tools"].is_array());
+
+        assert_eq!(usage.usage("session:a"), 2);
+        assert_eq!(usage.snapshot().len(), 1);
+        assert_eq!(usage.reset("session:a"), 2);
+        assert!(!is_limited(&call(&engine, "a", "search").await.unwrap()));
+        usage.reset_all();
+        assert_eq!(usage.usage("session:a"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_notifications_are_never_limited() {
+        let engine =
+            engine(RateLimiter::new().limit(LimitKey::Global, RateLimit::per_minute(1))).await;
+
+        for _ in 0..3 {
+            let response = engine
+                .handle_message(
+                    json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+                    Some("a".to_string()),
+                )
+                .await
+                .unwrap();
+            assert!(!is_limited(&response));
+        }
+    }
+}
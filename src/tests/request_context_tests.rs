@@ -0,0 +1,211 @@
+//! Request Context Tests
+//!
+//! Tests that `McpContext` carries the session handshake data and per-request
+//! details into every handler call.
+
+#[cfg(test)]
+mod tests {
+    use crate::framework::{FrameworkHandler, NotificationCtx};
+    use crate::handler::{ConnectionInfo, McpContext, McpHandler, ToolDefinition};
+    use crate::http::HttpMcpHandler;
+    use crate::shared::McpProtocolEngine;
+    use crate::McpResponse;
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use schemars::JsonSchema;
+    use serde::Deserialize;
+    use serde_json::{json, Value};
+    use std::sync::{Arc, Mutex};
+
+    /// Handler that records the context of the last tool call
+    #[derive(Default)]
+    struct RecordingHandler {
+        last_context: Mutex<Option<McpContext>>,
+    }
+
+    #[async_trait]
+    impl McpHandler for RecordingHandler {
+        async fn list_tools(&self, _context: &McpContext) -> Result<Vec<ToolDefinition>> {
+            Ok(vec![])
+        }
+
+        async fn call_tool(
+            &self,
+            _name: &str,
+            _arguments: Value,
+            context: &McpContext,
+        ) -> Result<Value> {
+            *self.last_context.lock().unwrap() = Some(context.clone());
+            Ok(json!({}))
+        }
+    }
+
+    fn initialize_message() -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-03-26",
+                "capabilities": { "roots": { "listChanged": true } },
+                "clientInfo": { "name": "context-client", "version": "2.1.0" }
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_context_carries_initialize_data_and_request_details() {
+        let handler = Arc::new(RecordingHandler::default());
+        let engine = McpProtocolEngine::with_handler(handler.clone());
+        let session = Some("ctx-session".to_string());
+
+        engine
+            .handle_message(initialize_message(), session.clone())
+            .await
+            .unwrap();
+
+        let mut headers = warp::http::HeaderMap::new();
+        headers.insert("x-tenant", "acme".parse().unwrap());
+        let connection = ConnectionInfo {
+            remote_addr: Some("10.0.0.7:4242".parse().unwrap()),
+            headers,
+            ..Default::default()
+        };
+
+        engine
+            .handle_message_with_connection(
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": "call-7",
+                    "method": "tools/call",
+                    "params": {
+                        "name": "anything",
+                        "arguments": {},
+                        "_meta": { "progressToken": "tok-1", "traceId": "abc" }
+                    }
+                }),
+                session,
+                Some(connection),
+            )
+            .await
+            .unwrap();
+
+        let context = handler.last_context.lock().unwrap().clone().unwrap();
+        assert_eq!(context.session_id.as_deref(), Some("ctx-session"));
+        assert_eq!(context.protocol_version.as_deref(), Some("2025-03-26"));
+        assert_eq!(
+            context.client_info.as_ref().unwrap()["name"],
+            "context-client"
+        );
+        assert_eq!(
+            context.client_capabilities.as_ref().unwrap()["roots"]["listChanged"],
+            true
+        );
+        assert_eq!(context.request_id, Some(json!("call-7")));
+        assert_eq!(context.meta.as_ref().unwrap()["traceId"], "abc");
+        assert_eq!(context.progress_token(), Some(&json!("tok-1")));
+
+        let connection = context.connection.unwrap();
+        assert_eq!(connection.remote_addr.unwrap().to_string(), "10.0.0.7:4242");
+        assert_eq!(connection.headers.get("x-tenant").unwrap(), "acme");
+    }
+
+    #[tokio::test]
+    async fn test_context_is_isolated_per_session() {
+        let handler = Arc::new(RecordingHandler::default());
+        let engine = McpProtocolEngine::with_handler(handler.clone());
+
+        engine
+            .handle_message(initialize_message(), Some("initialized".to_string()))
+            .await
+            .unwrap();
+
+        engine
+            .handle_message(
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 2,
+                    "method": "tools/call",
+                    "params": { "name": "anything", "arguments": {} }
+                }),
+                Some("fresh".to_string()),
+            )
+            .await
+            .unwrap();
+
+        let context = handler.last_context.lock().unwrap().clone().unwrap();
+        assert_eq!(context.session_id.as_deref(), Some("fresh"));
+        assert!(context.client_info.is_none());
+        assert!(context.protocol_version.is_none());
+        assert!(context.meta.is_none());
+        assert!(context.connection.is_none());
+    }
+
+    #[derive(Deserialize, JsonSchema)]
+    struct Empty {}
+
+    #[tokio::test]
+    async fn test_framework_tool_reads_context_over_http() {
+        let mut framework = FrameworkHandler::new((), "ctx-server", "1.0.0");
+        framework.registry_mut().register_tool(
+            "whoami",
+            "Report the caller",
+            |_input: Empty, _ctx: Arc<()>, notif: NotificationCtx| async move {
+                let client = notif
+                    .client_info()
+                    .and_then(|info| info.get("name"))
+                    .cloned()
+                    .unwrap_or(Value::Null);
+                let user_agent = notif
+                    .connection()
+                    .and_then(|c| c.headers.get("user-agent"))
+                    .and_then(|v| v.to_str().ok())
+                    .map(|v| v.to_string());
+                Ok(McpResponse::text("ok").with_data(json!({
+                    "client": client,
+                    "request_id": notif.request_id(),
+                    "version": notif.protocol_version(),
+                    "user_agent": user_agent,
+                    "remote": notif.connection().and_then(|c| c.remote_addr).map(|a| a.to_string()),
+                })))
+            },
+        );
+
+        let engine = Arc::new(McpProtocolEngine::with_handler(Arc::new(framework)));
+        let routes = HttpMcpHandler::new(engine).route();
+
+        let init = warp::test::request()
+            .method("POST")
+            .path("/mcp")
+            .header("content-type", "application/json")
+            .json(&initialize_message())
+            .reply(&routes)
+            .await;
+        assert_eq!(init.status(), 200);
+        let session_id = init.headers()["mcp-session-id"].clone();
+
+        let resp = warp::test::request()
+            .method("POST")
+            .path("/mcp")
+            .header("content-type", "application/json")
+            .header("mcp-session-id", session_id)
+            .header("user-agent", "context-test/1.0")
+            .remote_addr("192.168.1.20:5555".parse().unwrap())
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "id": 42,
+                "method": "tools/call",
+                "params": { "name": "whoami", "arguments": {} }
+            }))
+            .reply(&routes)
+            .await;
+
+        let body: Value = serde_json::from_slice(resp.body()).unwrap();
+        let data = &body["result"]["data"];
+        assert_eq!(data["client"], "context-client");
+        assert_eq!(data["request_id"], 42);
+        assert_eq!(data["version"], "2025-03-26");
+        assert_eq!(data["user_agent"], "context-test/1.0");
+        assert_eq!(data["remote"], "192.168.1.20:5555");
+    }
+}
@@ -0,0 +1,150 @@
+//! Resource Content Tests
+//!
+//! Tests for binary and multi-part `resources/read` responses and the extended
+//! `resources/list` metadata.
+
+#[cfg(test)]
+mod tests {
+    use crate::content_types::{Annotations, Role};
+    use crate::framework::{FrameworkHandler, ResourceProvider};
+    use crate::handler::{ResourceContent, ResourceInfo};
+    use crate::shared::McpProtocolEngine;
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+
+    const PNG_BYTES: &[u8] = b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR";
+
+    /// Provider serving a binary file and a directory with several entries
+    struct ArchiveProvider;
+
+    #[async_trait]
+    impl ResourceProvider<()> for ArchiveProvider {
+        async fn list_resources(&self, _context: Arc<()>) -> Result<Vec<ResourceInfo>> {
+            Ok(vec![ResourceInfo {
+                uri: "file:///logo.png".to_string(),
+                name: "logo.png".to_string(),
+                title: Some("Company Logo".to_string()),
+                mime_type: Some("image/png".to_string()),
+                size: Some(PNG_BYTES.len() as u64),
+                annotations: Some(Annotations {
+                    audience: Some(vec![Role::User]),
+                    priority: Some(0.8),
+                    last_modified: None,
+                }),
+                ..Default::default()
+            }])
+        }
+
+        async fn read_resource(&self, uri: &str, _context: Arc<()>) -> Result<ResourceContent> {
+            match uri {
+                "file:///logo.png" => Ok(ResourceContent::blob(
+                    uri,
+                    Some("image/png".to_string()),
+                    PNG_BYTES,
+                )),
+                _ => Err(anyhow::anyhow!("Resource not found: {}", uri)),
+            }
+        }
+
+        async fn read_resource_contents(
+            &self,
+            uri: &str,
+            context: Arc<()>,
+        ) -> Result<Vec<ResourceContent>> {
+            match uri {
+                "file:///docs/" => Ok(vec![
+                    ResourceContent::text(
+                        "file:///docs/a.md",
+                        Some("text/markdown".to_string()),
+                        "# A",
+                    ),
+                    ResourceContent::text(
+                        "file:///docs/b.md",
+                        Some("text/markdown".to_string()),
+                        "# B",
+                    ),
+                ]),
+                _ => Ok(vec![self.read_resource(uri, context).await?]),
+            }
+        }
+    }
+
+    async fn engine() -> McpProtocolEngine {
+        let mut framework = FrameworkHandler::new((), "resource-server", "1.0.0");
+        framework
+            .registry_mut()
+            .register_resource_provider(Box::new(ArchiveProvider));
+        let engine = McpProtocolEngine::with_handler(Arc::new(framework));
+        engine
+            .handle_message(
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 0,
+                    "method": "initialize",
+                    "params": { "protocolVersion": "2025-06-18" }
+                }),
+                Some("resources".to_string()),
+            )
+            .await
+            .unwrap();
+        engine
+    }
+
+    async fn request(engine: &McpProtocolEngine, method: &str, params: Value) -> Value {
+        engine
+            .handle_message(
+                json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }),
+                Some("resources".to_string()),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_read_binary_resource_as_blob() {
+        let engine = engine().await;
+        let response = request(
+            &engine,
+            "resources/read",
+            json!({ "uri": "file:///logo.png" }),
+        )
+        .await;
+
+        let contents = response["result"]["contents"].as_array().unwrap();
+        assert_eq!(contents.len(), 1);
+        assert_eq!(contents[0]["uri"], "file:///logo.png");
+        assert_eq!(contents[0]["mimeType"], "image/png");
+        assert_eq!(contents[0]["blob"], "iVBORw0KGgoAAAANSUhEUg==");
+        assert!(contents[0].get("text").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_read_returns_multiple_contents() {
+        let engine = engine().await;
+        let response = request(&engine, "resources/read", json!({ "uri": "file:///docs/" })).await;
+
+        let contents = response["result"]["contents"].as_array().unwrap();
+        assert_eq!(contents.len(), 2);
+        assert_eq!(contents[0]["uri"], "file:///docs/a.md");
+        assert_eq!(contents[0]["text"], "# A");
+        assert_eq!(contents[1]["uri"], "file:///docs/b.md");
+        assert_eq!(contents[1]["text"], "# B");
+    }
+
+    #[tokio::test]
+    async fn test_list_includes_title_size_and_annotations() {
+        let engine = engine().await;
+        let response = request(&engine, "resources/list", json!({})).await;
+
+        let resource = &response["result"]["resources"][0];
+        assert_eq!(resource["title"], "Company Logo");
+        assert_eq!(resource["size"], PNG_BYTES.len());
+        assert_eq!(
+            resource["annotations"],
+            json!({ "audience": ["user"], "priority": 0.8 })
+        );
+        assert!(resource.get("description").is_none());
+    }
+}
@@ -0,0 +1,275 @@
+//! Runtime Registry Tests
+//!
+//! Tests adding and removing tools, resources and prompts on a running handler
+//! and the `list_changed` notifications sent for each change.
+
+#[cfg(test)]
+mod tests {
+    use crate::framework::FrameworkHandler;
+    use crate::handler::{McpNotification, PromptContent, PromptMessage};
+    use crate::shared::McpProtocolEngine;
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+    use std::{sync::Arc, time::Duration};
+    use tokio::sync::oneshot;
+
+    #[derive(JsonSchema, Deserialize)]
+    struct EchoInput {
+        text: String,
+    }
+
+    #[derive(JsonSchema, Serialize)]
+    struct EchoOutput {
+        text: String,
+    }
+
+    #[derive(JsonSchema, Deserialize)]
+    struct NoArgs {}
+
+    fn setup() -> (FrameworkHandler<()>, Option<String>) {
+        let framework = FrameworkHandler::new((), "runtime-registry-server", "1.0.0");
+        (framework, Some("runtime-registry".to_string()))
+    }
+
+    async fn initialize(engine: &McpProtocolEngine, session: &Option<String>) -> Value {
+        engine
+            .handle_message(
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 0,
+                    "method": "initialize",
+                    "params": { "protocolVersion": "2025-06-18" }
+                }),
+                session.clone(),
+            )
+            .await
+            .unwrap()
+    }
+
+    async fn request(
+        engine: &McpProtocolEngine,
+        session: &Option<String>,
+        method: &str,
+        params: Value,
+    ) -> Value {
+        engine
+            .handle_message(
+                json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }),
+                session.clone(),
+            )
+            .await
+            .unwrap()
+    }
+
+    fn tool_names(response: &Value) -> Vec<String> {
+        response["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|tool| tool["name"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_list_changed_capabilities_are_advertised() {
+        let (framework, session) = setup();
+        let engine = McpProtocolEngine::with_handler(Arc::new(framework));
+        let response = initialize(&engine, &session).await;
+
+        let capabilities = &response["result"]["capabilities"];
+        assert_eq!(capabilities["tools"]["listChanged"], true);
+        assert_eq!(capabilities["resources"]["listChanged"], true);
+        assert_eq!(capabilities["prompts"]["listChanged"], true);
+    }
+
+    #[tokio::test]
+    async fn test_tool_added_and_removed_at_runtime() {
+        let (framework, session) = setup();
+        let registry = framework.registry_handle();
+        let engine = McpProtocolEngine::with_handler(Arc::new(framework));
+        let mut notifications = engine.notifier().subscribe("ws-client");
+        initialize(&engine, &session).await;
+
+        let listed = request(&engine, &session, "tools/list", json!({})).await;
+        assert!(tool_names(&listed).is_empty());
+
+        registry.add_tool(
+            "plugin_echo",
+            "Echo from a plugin",
+            |input: EchoInput, _ctx, _notif| async move { Ok(EchoOutput { text: input.text }) },
+        );
+        assert!(matches!(
+            notifications.try_recv(),
+            Ok(McpNotification::ToolsListChanged)
+        ));
+
+        let listed = request(&engine, &session, "tools/list", json!({})).await;
+        assert_eq!(tool_names(&listed), vec!["plugin_echo"]);
+
+        let called = request(
+            &engine,
+            &session,
+            "tools/call",
+            json!({ "name": "plugin_echo", "arguments": { "text": "hi" } }),
+        )
+        .await;
+        assert_eq!(called["result"]["data"]["text"], "hi");
+
+        assert!(registry.remove_tool("plugin_echo"));
+        assert!(matches!(
+            notifications.try_recv(),
+            Ok(McpNotification::ToolsListChanged)
+        ));
+        let listed = request(&engine, &session, "tools/list", json!({})).await;
+        assert!(tool_names(&listed).is_empty());
+
+        // Removing an unknown tool is not announced
+        assert!(!registry.remove_tool("plugin_echo"));
+        assert!(notifications.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_call_survives_removal() {
+        let (framework, session) = setup();
+        let registry = framework.registry_handle();
+        let engine = Arc::new(McpProtocolEngine::with_handler(Arc::new(framework)));
+        initialize(&engine, &session).await;
+
+        let (started_tx, started_rx) = oneshot::channel::<()>();
+        let started_tx = Arc::new(std::sync::Mutex::new(Some(started_tx)));
+        registry.add_tool(
+            "slow",
+            "Slow tool",
+            move |input: EchoInput, _ctx, _notif| {
+                let started_tx = started_tx.clone();
+                async move {
+                    if let Some(tx) = started_tx.lock().unwrap().take() {
+                        let _ = tx.send(());
+                    }
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    Ok(EchoOutput { text: input.text })
+                }
+            },
+        );
+
+        let call = tokio::spawn({
+            let engine = engine.clone();
+            let session = session.clone();
+            async move {
+                request(
+                    &engine,
+                    &session,
+                    "tools/call",
+                    json!({ "name": "slow", "arguments": { "text": "done" } }),
+                )
+                .await
+            }
+        });
+
+        started_rx.await.unwrap();
+        assert!(registry.remove_tool("slow"));
+
+        let response = call.await.unwrap();
+        assert_eq!(response["result"]["data"]["text"], "done");
+    }
+
+    #[tokio::test]
+    async fn test_resources_added_and_removed_at_runtime() {
+        let (framework, session) = setup();
+        let registry = framework.registry_handle();
+        let engine = McpProtocolEngine::with_handler(Arc::new(framework));
+        let mut notifications = engine.notifier().subscribe("ws-client");
+        initialize(&engine, &session).await;
+
+        registry
+            .add_static_resource(
+                "config://plugin",
+                "Plugin config",
+                "text/plain",
+                "enabled = true",
+            )
+            .unwrap();
+        assert!(matches!(
+            notifications.try_recv(),
+            Ok(McpNotification::ResourcesListChanged)
+        ));
+
+        let read = request(
+            &engine,
+            &session,
+            "resources/read",
+            json!({ "uri": "config://plugin" }),
+        )
+        .await;
+        assert_eq!(read["result"]["contents"][0]["text"], "enabled = true");
+
+        // A second owner for the same URI is rejected and not announced
+        assert!(registry
+            .add_static_resource("config://plugin", "Duplicate", "text/plain", "")
+            .is_err());
+        assert!(notifications.try_recv().is_err());
+
+        assert!(registry.remove_resource("config://plugin"));
+        assert!(matches!(
+            notifications.try_recv(),
+            Ok(McpNotification::ResourcesListChanged)
+        ));
+        let listed = request(&engine, &session, "resources/list", json!({})).await;
+        assert!(listed["result"]["resources"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_prompts_added_and_removed_at_runtime() {
+        let (framework, session) = setup();
+        let registry = framework.registry_handle();
+        let engine = McpProtocolEngine::with_handler(Arc::new(framework));
+        let mut notifications = engine.notifier().subscribe("ws-client");
+        initialize(&engine, &session).await;
+
+        registry
+            .add_prompt::<NoArgs, _>("greet", "Say hello", |_args, _ctx| async move {
+                Ok(PromptContent {
+                    description: None,
+                    messages: vec![PromptMessage::user("Hello!")],
+                })
+            })
+            .unwrap();
+        assert!(matches!(
+            notifications.try_recv(),
+            Ok(McpNotification::PromptsListChanged)
+        ));
+
+        let prompt = request(&engine, &session, "prompts/get", json!({ "name": "greet" })).await;
+        assert_eq!(prompt["result"]["messages"][0]["content"]["text"], "Hello!");
+
+        assert!(registry.remove_prompt("greet"));
+        assert!(matches!(
+            notifications.try_recv(),
+            Ok(McpNotification::PromptsListChanged)
+        ));
+        let listed = request(&engine, &session, "prompts/list", json!({})).await;
+        assert!(listed["result"]["prompts"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_skips_closed_sessions() {
+        let (framework, _session) = setup();
+        let registry = framework.registry_handle();
+        let engine = McpProtocolEngine::with_handler(Arc::new(framework));
+        let mut open = engine.notifier().subscribe("open");
+        drop(engine.notifier().subscribe("closed"));
+
+        registry.add_tool(
+            "noop",
+            "No-op",
+            |input: EchoInput, _ctx, _notif| async move { Ok(EchoOutput { text: input.text }) },
+        );
+
+        assert!(matches!(
+            open.try_recv(),
+            Ok(McpNotification::ToolsListChanged)
+        ));
+        assert_eq!(engine.notifier().sessions(), vec!["open".to_string()]);
+    }
+}
@@ -0,0 +1,134 @@
+//! Session Extensions Tests
+//!
+//! Tests the typed per-session values tools keep through
+//! `NotificationCtx::session`: isolation between sessions, and dropping the
+//! values when the session ends or initializes again.
+
+#[cfg(test)]
+mod tests {
+    use crate::framework::FrameworkHandler;
+    use crate::session_store::{SessionEndReason, SessionExtensions};
+    use crate::shared::McpProtocolEngine;
+    use crate::tests::support::{initialize, send};
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[derive(JsonSchema, Deserialize)]
+    struct Empty {}
+
+    #[derive(JsonSchema, Serialize)]
+    struct Step {
+        step: u32,
+    }
+
+    /// Progress of a multi-step wizard; counts its drops in the shared counter.
+    struct Wizard {
+        step: u32,
+        dropped: Arc<AtomicUsize>,
+    }
+
+    impl Drop for Wizard {
+        fn drop(&mut self) {
+            self.dropped.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    /// An engine with a `next_step` tool advancing a wizard kept in the session.
+    fn engine(dropped: Arc<AtomicUsize>) -> McpProtocolEngine {
+        let framework = FrameworkHandler::new(dropped, "extensions-server", "1.0.0");
+        framework.registry_handle().add_tool(
+            "next_step",
+            "Advance the wizard",
+            |_input: Empty, dropped: Arc<Arc<AtomicUsize>>, notif| async move {
+                let session = notif.session();
+                let step = match session.update(|wizard: &mut Wizard| {
+                    wizard.step += 1;
+                    wizard.step
+                }) {
+                    Some(step) => step,
+                    None => {
+                        session.insert(Wizard {
+                            step: 1,
+                            dropped: (*dropped).clone(),
+                        });
+                        1
+                    }
+                };
+                Ok(Step { step })
+            },
+        );
+        McpProtocolEngine::with_handler(Arc::new(framework))
+    }
+
+    async fn next_step(engine: &McpProtocolEngine, session: &str) -> Value {
+        let response = send(
+            engine,
+            session,
+            "tools/call",
+            json!({ "name": "next_step", "arguments": {} }),
+        )
+        .await
+        .unwrap();
+        response["result"]["data"]["step"].clone()
+    }
+
+    #[test]
+    fn test_values_are_keyed_by_type() {
+        let extensions = SessionExtensions::new();
+        assert_eq!(extensions.get::<u32>(), None);
+        assert_eq!(extensions.insert(1u32), None);
+        assert_eq!(extensions.insert(2u32), Some(1));
+        extensions.insert(String::from("workspace"));
+
+        let shared = extensions.clone();
+        assert_eq!(shared.get::<u32>(), Some(2));
+        assert_eq!(shared.get::<String>().as_deref(), Some("workspace"));
+        assert_eq!(shared.update(|count: &mut u32| *count += 1), Some(()));
+        assert_eq!(extensions.remove::<u32>(), Some(3));
+        assert!(!extensions.contains::<u32>());
+        assert_eq!(shared.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_each_session_keeps_its_own_values() {
+        let engine = engine(Arc::default());
+        initialize(&engine, "a").await;
+        initialize(&engine, "b").await;
+
+        assert_eq!(next_step(&engine, "a").await, 1);
+        assert_eq!(next_step(&engine, "a").await, 2);
+        assert_eq!(next_step(&engine, "b").await, 1);
+        assert_eq!(next_step(&engine, "a").await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_values_are_dropped_when_session_ends() {
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let engine = engine(dropped.clone());
+        initialize(&engine, "ws-1").await;
+        next_step(&engine, "ws-1").await;
+
+        assert!(engine.end_session("ws-1", SessionEndReason::Closed).await);
+        assert_eq!(dropped.load(Ordering::SeqCst), 1);
+
+        // A new session under the same ID starts over
+        initialize(&engine, "ws-1").await;
+        assert_eq!(next_step(&engine, "ws-1").await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_values_are_dropped_when_session_reinitializes() {
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let engine = engine(dropped.clone());
+        initialize(&engine, "http-1").await;
+        next_step(&engine, "http-1").await;
+        next_step(&engine, "http-1").await;
+
+        initialize(&engine, "http-1").await;
+        assert_eq!(dropped.load(Ordering::SeqCst), 1);
+        assert_eq!(next_step(&engine, "http-1").await, 1);
+    }
+}
@@ -0,0 +1,228 @@
+//! Session Lifecycle Tests
+//!
+//! Tests that `on_session_start` and `on_session_end` hooks fire with the right
+//! reason for initialization, re-initialization, WebSocket close, HTTP delete
+//! and session store expiry.
+
+#[cfg(test)]
+mod tests {
+    use crate::framework::FrameworkHandler;
+    use crate::http::HttpMcpHandler;
+    use crate::session_store::{InMemorySessionStore, SessionEndReason};
+    use crate::shared::McpProtocolEngine;
+    use anyhow::Result;
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+    use warp::test::request;
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Start(String),
+        End(String, SessionEndReason),
+    }
+
+    fn engine(framework: FrameworkHandler<mpsc::UnboundedSender<Event>>) -> Arc<McpProtocolEngine> {
+        Arc::new(McpProtocolEngine::with_handler(Arc::new(framework)))
+    }
+
+    /// A handler whose lifecycle hooks report to the returned channel.
+    ///
+    /// Sessions named `reject-*` and clients named `rejected` are refused by
+    /// the start hook.
+    fn framework() -> (
+        FrameworkHandler<mpsc::UnboundedSender<Event>>,
+        mpsc::UnboundedReceiver<Event>,
+    ) {
+        let (events, received) = mpsc::unbounded_channel();
+        let mut framework = FrameworkHandler::new(events, "lifecycle-server", "1.0.0");
+        framework.add_session_start_hook(|events, request| async move {
+            let session_id = request.session_id.unwrap_or_default();
+            let client = request
+                .client_info
+                .as_ref()
+                .and_then(|info| info["name"].as_str());
+            if session_id.starts_with("reject-") || client == Some("rejected") {
+                anyhow::bail!("no resources left for {session_id}");
+            }
+            events.send(Event::Start(session_id))?;
+            Ok(())
+        });
+        framework.add_session_end_hook(|events, session_id, reason| async move {
+            events.send(Event::End(session_id, reason))?;
+            Ok(())
+        });
+        (framework, received)
+    }
+
+    async fn initialize(engine: &McpProtocolEngine, session: &str) -> Result<Value> {
+        engine
+            .handle_message(
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "initialize",
+                    "params": { "protocolVersion": "2025-06-18" }
+                }),
+                Some(session.to_string()),
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_initialize_starts_and_reinitialize_restarts_session() {
+        let (framework, mut events) = framework();
+        let engine = engine(framework);
+
+        initialize(&engine, "a").await.unwrap();
+        assert_eq!(events.try_recv().unwrap(), Event::Start("a".into()));
+
+        initialize(&engine, "a").await.unwrap();
+        assert_eq!(
+            events.try_recv().unwrap(),
+            Event::End("a".into(), SessionEndReason::Reinitialized)
+        );
+        assert_eq!(events.try_recv().unwrap(), Event::Start("a".into()));
+    }
+
+    #[tokio::test]
+    async fn test_failing_start_hook_rejects_initialize() {
+        let (framework, mut events) = framework();
+        let engine = engine(framework);
+
+        let error = initialize(&engine, "reject-1").await.unwrap_err();
+        assert!(error.to_string().contains("no resources left for reject-1"));
+        assert!(engine.session_store().get("reject-1").await.is_none());
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_failed_reinitialize_ends_session_once() {
+        let (mut framework, mut events) = framework();
+        framework.set_session_store(
+            InMemorySessionStore::unbounded().with_idle_ttl(Duration::from_secs(60)),
+        );
+        let engine = engine(framework);
+        initialize(&engine, "a").await.unwrap();
+        assert_eq!(events.try_recv().unwrap(), Event::Start("a".into()));
+
+        let rejected = engine
+            .handle_message(
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 2,
+                    "method": "initialize",
+                    "params": {
+                        "protocolVersion": "2025-06-18",
+                        "clientInfo": { "name": "rejected", "version": "1" }
+                    }
+                }),
+                Some("a".to_string()),
+            )
+            .await;
+        assert!(rejected.is_err());
+        assert_eq!(
+            events.try_recv().unwrap(),
+            Event::End("a".into(), SessionEndReason::Reinitialized)
+        );
+        assert!(engine.session_store().get("a").await.is_none());
+
+        // The session does not expire a second time
+        tokio::time::advance(Duration::from_secs(61)).await;
+        initialize(&engine, "fresh").await.unwrap();
+        assert_eq!(events.recv().await.unwrap(), Event::Start("fresh".into()));
+        tokio::task::yield_now().await;
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_closed_connection_ends_session() {
+        let (framework, mut events) = framework();
+        let engine = engine(framework);
+        initialize(&engine, "ws-1").await.unwrap();
+        events.try_recv().unwrap();
+
+        assert!(engine.end_session("ws-1", SessionEndReason::Closed).await);
+        assert_eq!(
+            events.try_recv().unwrap(),
+            Event::End("ws-1".into(), SessionEndReason::Closed)
+        );
+        // Ending an unknown session reports nothing
+        assert!(!engine.end_session("ws-1", SessionEndReason::Closed).await);
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_http_delete_ends_session() {
+        let (framework, mut events) = framework();
+        let engine = engine(framework);
+        let routes = HttpMcpHandler::new(engine.clone()).route();
+
+        let response = request()
+            .method("POST")
+            .path("/mcp")
+            .header("content-type", "application/json")
+            .header("accept", "application/json")
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "initialize",
+                "params": { "protocolVersion": "2025-06-18" }
+            }))
+            .reply(&routes)
+            .await;
+        let cookie = response.headers()["set-cookie"].to_str().unwrap();
+        let cookie = cookie.split(';').next().unwrap().to_string();
+        let session_id = cookie.trim_start_matches("mcp_session=").to_string();
+        assert_eq!(events.try_recv().unwrap(), Event::Start(session_id.clone()));
+
+        let deleted = request()
+            .method("DELETE")
+            .path("/mcp")
+            .header("cookie", &cookie)
+            .reply(&routes)
+            .await;
+        assert_eq!(deleted.status(), 204);
+        assert!(deleted.headers()["set-cookie"]
+            .to_str()
+            .unwrap()
+            .contains("Max-Age=0"));
+        assert_eq!(
+            events.try_recv().unwrap(),
+            Event::End(session_id, SessionEndReason::Deleted)
+        );
+
+        let again = request()
+            .method("DELETE")
+            .path("/mcp")
+            .header("cookie", &cookie)
+            .reply(&routes)
+            .await;
+        assert_eq!(again.status(), 404);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_store_expiry_ends_session() {
+        let (mut framework, mut events) = framework();
+        framework.set_session_store(
+            InMemorySessionStore::unbounded().with_idle_ttl(Duration::from_secs(60)),
+        );
+        let engine = engine(framework);
+        initialize(&engine, "idle").await.unwrap();
+        events.recv().await.unwrap();
+
+        tokio::time::advance(Duration::from_secs(61)).await;
+        initialize(&engine, "fresh").await.unwrap();
+
+        let mut received = vec![events.recv().await.unwrap(), events.recv().await.unwrap()];
+        received.sort_by_key(|event| matches!(event, Event::Start(_)));
+        assert_eq!(
+            received,
+            [
+                Event::End("idle".into(), SessionEndReason::Expired),
+                Event::Start("fresh".into()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,466 @@
+//! Session Store Tests
+//!
+//! Tests idle expiry, LRU eviction, explicit removal and eviction hooks of
+//! `InMemorySessionStore`, persistence of `FileSessionStore`, and their use by
+//! the protocol engine and the WebSocket transport.
+
+#[cfg(test)]
+mod tests {
+    use crate::core::McpServer;
+    use crate::framework::FrameworkHandler;
+    use crate::middleware::{McpRequest, Middleware, Next};
+    use crate::session_store::{
+        EvictionReason, FileSessionStore, InMemorySessionStore, SessionEndReason, SessionHandle,
+        SessionStore,
+    };
+    use crate::shared::McpProtocolEngine;
+    use crate::tests::support::{initialize, send};
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use futures_util::{SinkExt, StreamExt};
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+    /// Record every eviction reported by the store.
+    fn record(store: &InMemorySessionStore) -> Arc<Mutex<Vec<(String, EvictionReason)>>> {
+        let evicted = Arc::new(Mutex::new(Vec::new()));
+        let sink = evicted.clone();
+        store.on_evict(Arc::new(move |session_id, reason| {
+            sink.lock().unwrap().push((session_id.to_string(), reason));
+        }));
+        evicted
+    }
+
+    /// Add an initialized session to the store.
+    async fn add(store: &dyn SessionStore, session_id: &str) -> SessionHandle {
+        let session = store.get_or_create(session_id).await;
+        let mut state = session.lock().await;
+        state.initialized = true;
+        store.save(session_id, &session, &state).await.unwrap();
+        drop(state);
+        session
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_idle_sessions_expire() {
+        let store = InMemorySessionStore::unbounded().with_idle_ttl(Duration::from_secs(60));
+        let evicted = record(&store);
+
+        add(&store, "idle").await;
+        add(&store, "busy").await;
+        tokio::time::advance(Duration::from_secs(45)).await;
+        store.get("busy").await.unwrap();
+        tokio::time::advance(Duration::from_secs(30)).await;
+
+        assert_eq!(store.sweep(), 1);
+        assert_eq!(store.session_count().await, 1);
+        assert_eq!(
+            *evicted.lock().unwrap(),
+            [("idle".to_string(), EvictionReason::Expired)]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_expired_sessions_are_swept_on_access() {
+        let store = InMemorySessionStore::unbounded().with_idle_ttl(Duration::from_secs(60));
+
+        add(&store, "a").await;
+        tokio::time::advance(Duration::from_secs(61)).await;
+        add(&store, "b").await;
+
+        assert_eq!(store.session_count().await, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_least_recently_used_session_is_evicted_when_full() {
+        let store = InMemorySessionStore::unbounded().with_max_sessions(2);
+        let evicted = record(&store);
+
+        add(&store, "a").await;
+        tokio::time::advance(Duration::from_millis(10)).await;
+        add(&store, "b").await;
+        tokio::time::advance(Duration::from_millis(10)).await;
+        store.get("a").await.unwrap();
+        tokio::time::advance(Duration::from_millis(10)).await;
+        add(&store, "c").await;
+
+        assert_eq!(store.session_count().await, 2);
+        assert_eq!(
+            *evicted.lock().unwrap(),
+            [("b".to_string(), EvictionReason::CapacityExceeded)]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_sessions_in_use_are_not_evicted() {
+        let store = InMemorySessionStore::unbounded()
+            .with_idle_ttl(Duration::from_secs(60))
+            .with_max_sessions(1);
+
+        let held = add(&store, "running").await;
+        tokio::time::advance(Duration::from_secs(120)).await;
+        assert_eq!(store.sweep(), 0);
+
+        // The store may go over its limit rather than drop a session mid-request
+        add(&store, "other").await;
+        assert_eq!(store.session_count().await, 2);
+        drop(held);
+    }
+
+    #[tokio::test]
+    async fn test_only_initialized_sessions_are_stored() {
+        let store = InMemorySessionStore::unbounded().with_max_sessions(1);
+        let evicted = record(&store);
+        add(&store, "real").await;
+
+        // Made-up session IDs neither take up room nor push real sessions out
+        for i in 0..10 {
+            let session_id = format!("made-up-{i}");
+            let session = store.get_or_create(&session_id).await;
+            let state = session.lock().await;
+            store.save(&session_id, &session, &state).await.unwrap();
+        }
+        assert!(store.get("made-up-0").await.is_none());
+        assert!(store.get("real").await.is_some());
+        assert_eq!(store.session_count().await, 1);
+        assert!(evicted.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_remove_reports_eviction() {
+        let store = InMemorySessionStore::new();
+        let evicted = record(&store);
+
+        add(&store, "ws-1").await;
+        assert!(store.remove("ws-1").await);
+        assert!(!store.remove("ws-1").await);
+        assert_eq!(
+            *evicted.lock().unwrap(),
+            [("ws-1".to_string(), EvictionReason::Removed)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_engine_uses_configured_store() {
+        let mut framework = FrameworkHandler::new((), "session-store-server", "1.0.0");
+        framework.set_session_store(InMemorySessionStore::unbounded().with_max_sessions(1));
+        let engine = McpProtocolEngine::with_handler(Arc::new(framework));
+
+        for session in ["first", "second"] {
+            initialize(&engine, session).await;
+        }
+        assert_eq!(engine.session_store().session_count().await, 1);
+
+        // The first session was evicted and has to initialize again
+        assert!(engine.session_store().get("first").await.is_none());
+
+        assert!(!engine.end_session("first", SessionEndReason::Closed).await);
+        assert!(engine.end_session("second", SessionEndReason::Closed).await);
+        assert_eq!(engine.session_store().session_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_open_websockets_keep_their_session() {
+        let mut framework =
+            FrameworkHandler::new(Mutex::new(Vec::new()), "session-store-server", "1.0.0");
+        framework.set_session_store(InMemorySessionStore::unbounded().with_max_sessions(1));
+        framework.add_session_end_hook(
+            |ended: Arc<Mutex<Vec<(String, SessionEndReason)>>>, session_id, reason| async move {
+                ended.lock().unwrap().push((session_id, reason));
+                Ok(())
+            },
+        );
+        let ended = framework.context().clone();
+        let server = McpServer::with_handler(Arc::new(framework)).await.unwrap();
+        let handle = server.bind(([127, 0, 0, 1], 0)).await.unwrap();
+        let initialize = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": { "protocolVersion": "2025-06-18", "clientInfo": { "name": "test", "version": "1" } }
+        });
+
+        let (mut ws, _) = connect_async(format!("ws://{}/mcp", handle.local_addr()))
+            .await
+            .unwrap();
+        ws.send(Message::text(initialize.to_string()))
+            .await
+            .unwrap();
+        ws.next().await.unwrap().unwrap();
+
+        // HTTP clients filling the store evict each other, not the WebSocket's session
+        let client = reqwest::Client::new();
+        for _ in 0..3 {
+            client
+                .post(format!("http://{}/mcp", handle.local_addr()))
+                .json(&initialize)
+                .send()
+                .await
+                .unwrap();
+        }
+        handle.shutdown().await.unwrap();
+
+        let ended = ended.lock().unwrap();
+        let websocket: Vec<_> = ended
+            .iter()
+            .filter(|(session_id, _)| session_id.starts_with("ws-"))
+            .map(|(_, reason)| *reason)
+            .collect();
+        assert_eq!(websocket, [SessionEndReason::Shutdown]);
+        assert!(ended
+            .iter()
+            .any(|(_, reason)| *reason == SessionEndReason::Evicted));
+    }
+
+    /// Counts the messages of each session in its session data.
+    struct CountMessages;
+
+    #[async_trait]
+    impl Middleware for CountMessages {
+        async fn handle(&self, request: McpRequest, next: Next<'_>) -> Result<Value> {
+            next.session_data().update(|data| {
+                let count = data.get("messages").and_then(Value::as_u64).unwrap_or(0);
+                data.insert("messages".to_string(), json!(count + 1));
+            });
+            next.run(request).await
+        }
+    }
+
+    fn file_engine(dir: &std::path::Path) -> McpProtocolEngine {
+        let mut framework = FrameworkHandler::new((), "session-store-server", "1.0.0");
+        framework.set_session_store(FileSessionStore::open(dir).unwrap());
+        framework.add_middleware(CountMessages);
+        McpProtocolEngine::with_handler(Arc::new(framework))
+    }
+
+    #[tokio::test]
+    async fn test_file_store_restores_sessions_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let engine = file_engine(dir.path());
+        send(
+            &engine,
+            "client-1",
+            "initialize",
+            json!({
+                "protocolVersion": "2025-06-18",
+                "clientInfo": { "name": "inspector", "version": "0.9" },
+                "capabilities": { "roots": {} }
+            }),
+        )
+        .await
+        .unwrap();
+        send(
+            &engine,
+            "client-1",
+            "logging/setLevel",
+            json!({ "level": "warning" }),
+        )
+        .await
+        .unwrap();
+        send(
+            &engine,
+            "client-1",
+            "resources/subscribe",
+            json!({ "uri": "file:///a" }),
+        )
+        .await
+        .unwrap();
+        send(
+            &engine,
+            "client-1",
+            "resources/subscribe",
+            json!({ "uri": "file:///b" }),
+        )
+        .await
+        .unwrap();
+        send(
+            &engine,
+            "client-1",
+            "resources/unsubscribe",
+            json!({ "uri": "file:///a" }),
+        )
+        .await
+        .unwrap();
+        let invalid = send(
+            &engine,
+            "client-1",
+            "logging/setLevel",
+            json!({ "level": "loud" }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(invalid["error"]["code"], -32602);
+        drop(engine);
+
+        let restarted = file_engine(dir.path());
+        let session = restarted.session_store().get("client-1").await.unwrap();
+        let session = session.lock().await;
+        assert!(session.initialized);
+        assert_eq!(session.protocol_version.as_deref(), Some("2025-06-18"));
+        assert_eq!(session.client_info.as_ref().unwrap()["name"], "inspector");
+        assert!(session.client_capabilities.as_ref().unwrap()["roots"].is_object());
+        assert_eq!(session.log_level.as_deref(), Some("warning"));
+        assert_eq!(
+            session.subscriptions.iter().collect::<Vec<_>>(),
+            ["file:///b"]
+        );
+        assert_eq!(session.data.get("messages"), Some(json!(6)));
+    }
+
+    #[tokio::test]
+    async fn test_file_store_is_shared_between_processes() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = file_engine(dir.path());
+        let second = file_engine(dir.path());
+
+        initialize(&first, "client-1").await;
+        // The second instance picks up the session initialized by the first
+        let response = send(
+            &second,
+            "client-1",
+            "logging/setLevel",
+            json!({ "level": "error" }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response["result"], json!({}));
+
+        let session = first.session_store().get("client-1").await.unwrap();
+        let session = session.lock().await;
+        assert_eq!(session.log_level.as_deref(), Some("error"));
+        assert_eq!(session.data.get("messages"), Some(json!(2)));
+    }
+
+    #[tokio::test]
+    async fn test_file_store_expires_and_removes_sessions() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileSessionStore::open(dir.path())
+            .unwrap()
+            .with_idle_ttl(Duration::ZERO);
+        let evicted = Arc::new(Mutex::new(Vec::new()));
+        let sink = evicted.clone();
+        store.on_evict(Arc::new(move |session_id, reason| {
+            sink.lock().unwrap().push((session_id.to_string(), reason));
+        }));
+
+        for session_id in ["ws-1", "http/cookie 2", "held"] {
+            add(&store, session_id).await;
+        }
+        assert_eq!(store.session_count().await, 3);
+
+        assert!(store.remove("ws-1").await);
+        assert!(!store.remove("ws-1").await);
+        let held = store.get("held").await.unwrap();
+        assert_eq!(store.sweep().await.unwrap(), 1);
+        drop(held);
+
+        assert_eq!(store.session_count().await, 1);
+        assert_eq!(
+            *evicted.lock().unwrap(),
+            [
+                ("ws-1".to_string(), EvictionReason::Removed),
+                ("http/cookie 2".to_string(), EvictionReason::Expired),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_file_store_refuses_to_overwrite_changes_of_other_processes() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = FileSessionStore::open(dir.path()).unwrap();
+        let second = FileSessionStore::open(dir.path()).unwrap();
+        add(&first, "client-1").await;
+
+        let stale = second.get("client-1").await.unwrap();
+        let session = first.get("client-1").await.unwrap();
+        let state = session.lock().await;
+        first.save("client-1", &session, &state).await.unwrap();
+        drop(state);
+
+        // The second process read the session before the first saved it again
+        let state = stale.lock().await;
+        let error = second.save("client-1", &stale, &state).await.unwrap_err();
+        assert!(error.to_string().contains("changed by another process"));
+        drop(state);
+
+        // Once it rereads the session it may save again
+        let fresh = second.get("client-1").await.unwrap();
+        let state = fresh.lock().await;
+        second.save("client-1", &fresh, &state).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_file_store_only_writes_initialized_sessions() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileSessionStore::open(dir.path()).unwrap();
+
+        let session = store.get_or_create("made-up").await;
+        let state = session.lock().await;
+        store.save("made-up", &session, &state).await.unwrap();
+
+        assert!(store.get("made-up").await.is_none());
+        assert_eq!(store.session_count().await, 0);
+    }
+
+    #[derive(JsonSchema, Deserialize)]
+    struct Remember {
+        workspace: Option<String>,
+    }
+
+    #[derive(JsonSchema, Serialize)]
+    struct Remembered {
+        workspace: Option<String>,
+    }
+
+    #[tokio::test]
+    async fn test_tools_keep_data_in_durable_sessions() {
+        let dir = tempfile::tempdir().unwrap();
+        let engine = || {
+            let mut framework = FrameworkHandler::new((), "session-store-server", "1.0.0");
+            framework.set_session_store(FileSessionStore::open(dir.path()).unwrap());
+            framework.registry_handle().add_tool(
+                "workspace",
+                "Select a workspace or show the selected one",
+                |input: Remember, _ctx: Arc<()>, notif| async move {
+                    let data = notif.session_data();
+                    if let Some(workspace) = input.workspace {
+                        data.insert("workspace", json!(workspace));
+                    }
+                    Ok(Remembered {
+                        workspace: data
+                            .get("workspace")
+                            .and_then(|workspace| workspace.as_str().map(str::to_string)),
+                    })
+                },
+            );
+            McpProtocolEngine::with_handler(Arc::new(framework))
+        };
+
+        let first = engine();
+        initialize(&first, "client-1").await;
+        send(
+            &first,
+            "client-1",
+            "tools/call",
+            json!({ "name": "workspace", "arguments": { "workspace": "/src/app" } }),
+        )
+        .await
+        .unwrap();
+        drop(first);
+
+        let restarted = engine();
+        let response = send(
+            &restarted,
+            "client-1",
+            "tools/call",
+            json!({ "name": "workspace", "arguments": {} }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response["result"]["data"]["workspace"], "/src/app");
+    }
+}
@@ -0,0 +1,162 @@
+//! Graceful Shutdown Tests
+//!
+//! Tests running a server in the background through `McpServer::bind`:
+//! binding port 0, letting in-flight calls finish on shutdown, cancelling
+//! those past the deadline, closing WebSockets and firing the session-end
+//! hooks, and that dropping the handle leaves the server running.
+
+#[cfg(test)]
+mod tests {
+    use crate::core::McpServer;
+    use crate::framework::FrameworkHandler;
+    use crate::introspection::REQUEST_CANCELLED;
+    use crate::session_store::SessionEndReason;
+    use futures_util::{SinkExt, StreamExt};
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio::sync::Notify;
+    use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+    #[derive(Default)]
+    struct App {
+        call_started: Notify,
+        ended: Mutex<Vec<SessionEndReason>>,
+    }
+
+    #[derive(JsonSchema, Deserialize)]
+    struct Sleep {
+        millis: u64,
+    }
+
+    #[derive(JsonSchema, Serialize)]
+    struct Slept {
+        millis: u64,
+    }
+
+    async fn server() -> (McpServer, Arc<App>) {
+        let mut framework = FrameworkHandler::new(App::default(), "shutdown-server", "1.0.0");
+        framework.add_session_end_hook(|app: Arc<App>, _id, reason| async move {
+            app.ended.lock().unwrap().push(reason);
+            Ok(())
+        });
+        framework.registry_handle().add_tool(
+            "sleep",
+            "Sleep for a while",
+            |input: Sleep, app: Arc<App>, _notif| async move {
+                app.call_started.notify_one();
+                tokio::time::sleep(Duration::from_millis(input.millis)).await;
+                Ok(Slept {
+                    millis: input.millis,
+                })
+            },
+        );
+        let app = framework.context().clone();
+        let server = McpServer::with_handler(Arc::new(framework)).await.unwrap();
+        (server, app)
+    }
+
+    fn message(id: u64, method: &str, params: Value) -> Value {
+        json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+    }
+
+    fn initialize() -> Value {
+        message(
+            1,
+            "initialize",
+            json!({ "protocolVersion": "2025-06-18", "clientInfo": { "name": "test", "version": "1" } }),
+        )
+    }
+
+    fn sleep_call(millis: u64) -> Value {
+        message(
+            2,
+            "tools/call",
+            json!({ "name": "sleep", "arguments": { "millis": millis } }),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_lets_in_flight_calls_finish() {
+        let (server, app) = server().await;
+        let handle = server.bind(([127, 0, 0, 1], 0)).await.unwrap();
+        let addr = handle.local_addr();
+        assert_ne!(addr.port(), 0);
+
+        let url = format!("http://{addr}/mcp");
+        let client = reqwest::Client::builder()
+            .cookie_store(true)
+            .build()
+            .unwrap();
+        client.post(&url).json(&initialize()).send().await.unwrap();
+        let call = tokio::spawn({
+            let (client, url) = (client.clone(), url.clone());
+            async move {
+                let response = client.post(&url).json(&sleep_call(200)).send().await?;
+                response.json::<Value>().await
+            }
+        });
+        app.call_started.notified().await;
+
+        handle
+            .shutdown_with_deadline(Duration::from_secs(5))
+            .await
+            .unwrap();
+        let response = call.await.unwrap().unwrap();
+        assert_eq!(response["result"]["data"]["millis"], 200);
+        assert_eq!(*app.ended.lock().unwrap(), [SessionEndReason::Shutdown]);
+        // The listener is closed
+        assert!(client.post(&url).json(&initialize()).send().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_cancels_late_calls_and_closes_websockets() {
+        let (server, app) = server().await;
+        let handle = server.bind(([127, 0, 0, 1], 0)).await.unwrap();
+        let (mut ws, _) = connect_async(format!("ws://{}/mcp", handle.local_addr()))
+            .await
+            .unwrap();
+        ws.send(Message::text(initialize().to_string()))
+            .await
+            .unwrap();
+        ws.next().await.unwrap().unwrap();
+        ws.send(Message::text(sleep_call(60_000).to_string()))
+            .await
+            .unwrap();
+        app.call_started.notified().await;
+
+        handle
+            .shutdown_with_deadline(Duration::from_millis(100))
+            .await
+            .unwrap();
+        let response: Value =
+            serde_json::from_str(ws.next().await.unwrap().unwrap().to_text().unwrap()).unwrap();
+        assert_eq!(response["id"], 2);
+        assert_eq!(response["error"]["code"], REQUEST_CANCELLED);
+        assert!(ws.next().await.unwrap().unwrap().is_close());
+        assert_eq!(*app.ended.lock().unwrap(), [SessionEndReason::Shutdown]);
+    }
+
+    #[tokio::test]
+    async fn test_dropping_the_handle_keeps_the_server_running() {
+        let (server, app) = server().await;
+        let handle = server.bind(([127, 0, 0, 1], 0)).await.unwrap();
+        let url = format!("http://{}/mcp", handle.local_addr());
+        drop(handle);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let response: Value = reqwest::Client::new()
+            .post(&url)
+            .json(&initialize())
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(response["result"]["serverInfo"]["name"], "shutdown-server");
+        assert!(app.ended.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,123 @@
+//! Single Resource Tests
+//!
+//! Tests resources registered from an async closure or constant content.
+
+#[cfg(test)]
+mod tests {
+    use crate::framework::FrameworkHandler;
+    use crate::shared::McpProtocolEngine;
+    use serde_json::{json, Value};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    struct CounterContext {
+        reads: AtomicUsize,
+    }
+
+    async fn setup() -> (McpProtocolEngine, Option<String>) {
+        let context = CounterContext {
+            reads: AtomicUsize::new(0),
+        };
+        let mut framework = FrameworkHandler::new(context, "single-resource-server", "1.0.0");
+        framework.registry_mut().register_resource(
+            "status://server",
+            "Server status",
+            "application/json",
+            |ctx: Arc<CounterContext>| async move {
+                let reads = ctx.reads.fetch_add(1, Ordering::SeqCst) + 1;
+                Ok(json!({ "reads": reads }).to_string())
+            },
+        );
+        framework.registry_mut().register_static_resource(
+            "config://app",
+            "App config",
+            "text/plain",
+            "mode = dev",
+        );
+        framework.registry_mut().register_static_resource(
+            "logo://small",
+            "Logo",
+            "image/png",
+            vec![0x89, b'P', b'N', b'G'],
+        );
+
+        let engine = McpProtocolEngine::with_handler(Arc::new(framework));
+        let session = Some("single-resources".to_string());
+        engine
+            .handle_message(
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 0,
+                    "method": "initialize",
+                    "params": { "protocolVersion": "2025-06-18" }
+                }),
+                session.clone(),
+            )
+            .await
+            .unwrap();
+        (engine, session)
+    }
+
+    async fn read(engine: &McpProtocolEngine, session: &Option<String>, uri: &str) -> Value {
+        engine
+            .handle_message(
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "resources/read",
+                    "params": { "uri": uri }
+                }),
+                session.clone(),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_single_resources_are_listed() {
+        let (engine, session) = setup().await;
+        let response = engine
+            .handle_message(
+                json!({ "jsonrpc": "2.0", "id": 1, "method": "resources/list", "params": {} }),
+                session,
+            )
+            .await
+            .unwrap();
+
+        let resources = response["result"]["resources"].as_array().unwrap();
+        assert_eq!(resources.len(), 3);
+        assert_eq!(resources[0]["uri"], "status://server");
+        assert_eq!(resources[0]["mimeType"], "application/json");
+        assert!(resources[0].get("size").is_none());
+        assert_eq!(resources[1]["name"], "App config");
+        assert_eq!(resources[1]["size"], 10);
+        assert_eq!(resources[2]["size"], 4);
+    }
+
+    #[tokio::test]
+    async fn test_dynamic_resource_runs_handler_on_each_read() {
+        let (engine, session) = setup().await;
+
+        let first = read(&engine, &session, "status://server").await;
+        assert_eq!(first["result"]["contents"][0]["text"], r#"{"reads":1}"#);
+        let second = read(&engine, &session, "status://server").await;
+        assert_eq!(second["result"]["contents"][0]["text"], r#"{"reads":2}"#);
+    }
+
+    #[tokio::test]
+    async fn test_static_resources_return_constant_content() {
+        let (engine, session) = setup().await;
+
+        let text = read(&engine, &session, "config://app").await;
+        assert_eq!(
+            text["result"]["contents"][0],
+            json!({ "uri": "config://app", "mimeType": "text/plain", "text": "mode = dev" })
+        );
+
+        let blob = read(&engine, &session, "logo://small").await;
+        assert_eq!(blob["result"]["contents"][0]["blob"], "iVBORw==");
+        assert_eq!(blob["result"]["contents"][0]["mimeType"], "image/png");
+    }
+}
@@ -0,0 +1,294 @@
+//! TLS Tests
+//!
+//! Tests serving HTTPS and secure WebSockets with certificates generated on
+//! the fly: reloading certificates without a restart, verifying client
+//! certificates whose identity reaches the handler's `McpContext`, and
+//! closing connections that stall in the handshake.
+
+#[cfg(test)]
+mod tests {
+    use crate::core::McpServer;
+    use crate::handler::{McpContext, McpHandler, ToolDefinition};
+    use crate::server_config::ServerConfig;
+    use crate::tls::{ClientAuth, TlsConfig};
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use futures_util::{SinkExt, StreamExt};
+    use rcgen::{
+        BasicConstraints, Certificate, CertificateParams, DnType, IsCa, KeyPair, KeyUsagePurpose,
+        SanType,
+    };
+    use rustls::crypto::ring::default_provider;
+    use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer, ServerName};
+    use rustls::{ClientConfig, RootCertStore};
+    use serde_json::{json, Value};
+    use std::net::SocketAddr;
+    use std::path::Path;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+    use tokio_rustls::{client::TlsStream, TlsConnector};
+    use tokio_tungstenite::{client_async, tungstenite::Message};
+
+    /// A certificate and its key pair.
+    struct Issued {
+        cert: Certificate,
+        key: KeyPair,
+    }
+
+    impl Issued {
+        /// Issue a certificate, self-signed if there is no issuer.
+        fn issue(common_name: &str, alt_names: Vec<SanType>, issuer: Option<&Issued>) -> Issued {
+            let key = KeyPair::generate().unwrap();
+            let mut params = CertificateParams::default();
+            params
+                .distinguished_name
+                .push(DnType::CommonName, common_name);
+            params.subject_alt_names = alt_names;
+            let cert = match issuer {
+                None => {
+                    params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+                    params.key_usages =
+                        vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+                    params.self_signed(&key)
+                }
+                Some(issuer) => params.signed_by(&key, &issuer.cert, &issuer.key),
+            }
+            .unwrap();
+            Issued { cert, key }
+        }
+
+        fn der(&self) -> CertificateDer<'static> {
+            self.cert.der().clone()
+        }
+
+        fn write(&self, dir: &Path, name: &str) {
+            std::fs::write(dir.join(format!("{name}.crt")), self.cert.pem()).unwrap();
+            std::fs::write(dir.join(format!("{name}.key")), self.key.serialize_pem()).unwrap();
+        }
+    }
+
+    fn dns(name: &str) -> SanType {
+        SanType::DnsName(name.try_into().unwrap())
+    }
+
+    /// Open a TLS connection trusting `ca`, presenting `client` if given.
+    async fn connect(
+        addr: SocketAddr,
+        ca: &Issued,
+        client: Option<&Issued>,
+    ) -> std::io::Result<TlsStream<TcpStream>> {
+        let mut roots = RootCertStore::empty();
+        roots.add(ca.der()).unwrap();
+        let builder = ClientConfig::builder_with_provider(Arc::new(default_provider()))
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_root_certificates(roots);
+        let config = match client {
+            Some(client) => builder
+                .with_client_auth_cert(
+                    vec![client.der()],
+                    PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(client.key.serialize_der())),
+                )
+                .unwrap(),
+            None => builder.with_no_client_auth(),
+        };
+        let tcp = TcpStream::connect(addr).await?;
+        TlsConnector::from(Arc::new(config))
+            .connect(ServerName::try_from("localhost").unwrap(), tcp)
+            .await
+    }
+
+    /// POST a JSON-RPC message over a fresh TLS connection, returning the raw response.
+    async fn post(
+        addr: SocketAddr,
+        ca: &Issued,
+        client: Option<&Issued>,
+        body: &Value,
+    ) -> std::io::Result<String> {
+        let mut stream = connect(addr, ca, client).await?;
+        let body = body.to_string();
+        let request = format!(
+            "POST /mcp HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nAccept: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(request.as_bytes()).await?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await?;
+        Ok(response)
+    }
+
+    fn initialize() -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": { "protocolVersion": "2025-06-18", "clientInfo": { "name": "tls", "version": "1" } }
+        })
+    }
+
+    /// Handler that records the context of the last tool call.
+    #[derive(Default)]
+    struct RecordingHandler {
+        last_context: Mutex<Option<McpContext>>,
+    }
+
+    #[async_trait]
+    impl McpHandler for RecordingHandler {
+        async fn list_tools(&self, _context: &McpContext) -> Result<Vec<ToolDefinition>> {
+            Ok(vec![])
+        }
+
+        async fn call_tool(
+            &self,
+            _name: &str,
+            _arguments: Value,
+            context: &McpContext,
+        ) -> Result<Value> {
+            *self.last_context.lock().unwrap() = Some(context.clone());
+            Ok(json!({}))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_https_and_wss_with_certificate_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let old_ca = Issued::issue("Old CA", vec![], None);
+        Issued::issue("localhost", vec![dns("localhost")], Some(&old_ca))
+            .write(dir.path(), "server");
+        let tls = TlsConfig::new(dir.path().join("server.crt"), dir.path().join("server.key"));
+        let server = McpServer::new().await.unwrap();
+        let handle = server
+            .serve(ServerConfig::new(([127, 0, 0, 1], 0)).with_tls(tls))
+            .await
+            .unwrap();
+        let addr = handle.local_addr();
+
+        let response = post(addr, &old_ca, None, &initialize()).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(response.contains("\"protocolVersion\""), "{response}");
+
+        let stream = connect(addr, &old_ca, None).await.unwrap();
+        let (mut ws, _) = client_async("wss://localhost/mcp", stream).await.unwrap();
+        ws.send(Message::text(initialize().to_string()))
+            .await
+            .unwrap();
+        let reply: Value =
+            serde_json::from_str(ws.next().await.unwrap().unwrap().to_text().unwrap()).unwrap();
+        assert_eq!(reply["id"], 1);
+
+        // Rotate to a certificate from another CA without restarting
+        let new_ca = Issued::issue("New CA", vec![], None);
+        Issued::issue("localhost", vec![dns("localhost")], Some(&new_ca))
+            .write(dir.path(), "server");
+        handle.reload_tls().unwrap();
+        assert!(connect(addr, &old_ca, None).await.is_err());
+        let response = post(addr, &new_ca, None, &initialize()).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        // The WebSocket opened before the reload keeps working
+        ws.send(Message::text(initialize().to_string()))
+            .await
+            .unwrap();
+        assert!(ws.next().await.unwrap().is_ok());
+
+        // Broken files leave the current certificate in place
+        std::fs::write(dir.path().join("server.crt"), "not a certificate").unwrap();
+        assert!(handle.reload_tls().is_err());
+        assert!(connect(addr, &new_ca, None).await.is_ok());
+
+        handle.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_mutual_tls_exposes_client_certificate() {
+        let dir = tempfile::tempdir().unwrap();
+        let ca = Issued::issue("Test CA", vec![], None);
+        std::fs::write(dir.path().join("ca.crt"), ca.cert.pem()).unwrap();
+        Issued::issue("localhost", vec![dns("localhost")], Some(&ca)).write(dir.path(), "server");
+        let client = Issued::issue(
+            "agent-7",
+            vec![
+                SanType::URI("spiffe://example.org/agent-7".try_into().unwrap()),
+                SanType::IpAddress([10, 0, 0, 7].into()),
+            ],
+            Some(&ca),
+        );
+        let stranger_ca = Issued::issue("Stranger CA", vec![], None);
+        let stranger = Issued::issue("stranger", vec![], Some(&stranger_ca));
+
+        let tls = TlsConfig::new(dir.path().join("server.crt"), dir.path().join("server.key"))
+            .with_client_ca(dir.path().join("ca.crt"), ClientAuth::Required);
+        let handler = Arc::new(RecordingHandler::default());
+        let server = McpServer::with_handler(handler.clone()).await.unwrap();
+        let handle = server
+            .serve(ServerConfig::new(([127, 0, 0, 1], 0)).with_tls(tls))
+            .await
+            .unwrap();
+        let addr = handle.local_addr();
+
+        let stream = connect(addr, &ca, Some(&client)).await.unwrap();
+        let (mut ws, _) = client_async("wss://localhost/mcp", stream).await.unwrap();
+        ws.send(Message::text(initialize().to_string()))
+            .await
+            .unwrap();
+        ws.next().await.unwrap().unwrap();
+        let call = json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": { "name": "whoami", "arguments": {} } });
+        ws.send(Message::text(call.to_string())).await.unwrap();
+        ws.next().await.unwrap().unwrap();
+
+        let context = handler.last_context.lock().unwrap().take().unwrap();
+        let certificate = context.client_certificate().unwrap();
+        assert_eq!(certificate.common_name.as_deref(), Some("agent-7"));
+        assert_eq!(
+            certificate.subject_alt_names,
+            ["spiffe://example.org/agent-7", "10.0.0.7"]
+        );
+        assert_eq!(certificate.fingerprint.len(), 64);
+        assert_eq!(certificate.der, client.cert.der().as_ref());
+        assert!(context
+            .connection
+            .unwrap()
+            .remote_addr
+            .unwrap()
+            .ip()
+            .is_loopback());
+
+        // Clients without a certificate from the CA are turned away
+        for presented in [None, Some(&stranger)] {
+            let response = post(addr, &ca, presented, &initialize()).await;
+            assert!(response.is_err(), "{response:?}");
+        }
+
+        handle.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stalled_handshake_is_closed() {
+        let dir = tempfile::tempdir().unwrap();
+        let ca = Issued::issue("Test CA", vec![], None);
+        Issued::issue("localhost", vec![dns("localhost")], Some(&ca)).write(dir.path(), "server");
+        let tls = TlsConfig::new(dir.path().join("server.crt"), dir.path().join("server.key"))
+            .with_handshake_timeout(Duration::from_millis(200));
+        let server = McpServer::new().await.unwrap();
+        let handle = server
+            .serve(ServerConfig::new(([127, 0, 0, 1], 0)).with_tls(tls))
+            .await
+            .unwrap();
+        let addr = handle.local_addr();
+
+        // Connect but never send a ClientHello
+        let mut stalled = TcpStream::connect(addr).await.unwrap();
+        let mut buf = [0u8; 1];
+        let read = tokio::time::timeout(Duration::from_secs(5), stalled.read(&mut buf))
+            .await
+            .expect("the server should close the stalled connection");
+        assert_eq!(read.unwrap(), 0);
+
+        // Clients completing the handshake in time are served
+        let response = post(addr, &ca, None, &initialize()).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+
+        handle.shutdown().await.unwrap();
+    }
+}
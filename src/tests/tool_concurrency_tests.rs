@@ -0,0 +1,214 @@
+//! Tool Concurrency Tests
+//!
+//! Tests per-tool concurrency limits: queueing, rejection, timeouts, progress
+//! notifications for queued calls, queue depth reporting and validation of
+//! the limited tool names.
+
+#[cfg(test)]
+mod tests {
+    use crate::framework::{
+        ConcurrencyLimit, FrameworkHandler, McpServerBuilder, QueueDepth, ToolConcurrency,
+    };
+    use crate::handler::McpNotification;
+    use crate::shared::McpProtocolEngine;
+    use anyhow::Result;
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::Semaphore;
+
+    #[derive(JsonSchema, Deserialize)]
+    struct Empty {}
+
+    #[derive(JsonSchema, Serialize)]
+    struct Done {
+        ok: bool,
+    }
+
+    /// A server whose `bridge` tool blocks until the test hands out a permit.
+    async fn engine(
+        limit: ConcurrencyLimit,
+    ) -> (Arc<McpProtocolEngine>, ToolConcurrency, Arc<Semaphore>) {
+        let release = Semaphore::new(0);
+        let mut framework = FrameworkHandler::new(release, "concurrency-server", "1.0.0");
+        framework.registry_handle().add_tool(
+            "bridge",
+            "Talk to the serial bridge",
+            |_input: Empty, release, _notif| async move {
+                release.acquire().await?.forget();
+                Ok(Done { ok: true })
+            },
+        );
+        framework.set_tool_concurrency("bridge", limit);
+        let concurrency = framework.tool_concurrency();
+        let release = framework.context().clone();
+        let engine = Arc::new(McpProtocolEngine::with_handler(Arc::new(framework)));
+        for session in ["first", "second", "third"] {
+            engine
+                .handle_message(
+                    json!({
+                        "jsonrpc": "2.0",
+                        "id": 0,
+                        "method": "initialize",
+                        "params": { "protocolVersion": "2025-06-18" }
+                    }),
+                    Some(session.to_string()),
+                )
+                .await
+                .unwrap();
+        }
+        (engine, concurrency, release)
+    }
+
+    fn call(
+        engine: &Arc<McpProtocolEngine>,
+        session: &str,
+        meta: Value,
+    ) -> tokio::task::JoinHandle<Result<Value>> {
+        let engine = engine.clone();
+        let session = session.to_string();
+        tokio::spawn(async move {
+            engine
+                .handle_message(
+                    json!({
+                        "jsonrpc": "2.0",
+                        "id": 1,
+                        "method": "tools/call",
+                        "params": { "name": "bridge", "arguments": {}, "_meta": meta }
+                    }),
+                    Some(session),
+                )
+                .await
+        })
+    }
+
+    /// Yield until the `bridge` tool has the given number of running and waiting calls.
+    async fn wait_for(concurrency: &ToolConcurrency, running: usize, waiting: usize) {
+        loop {
+            let depth = concurrency.queue_depth("bridge").unwrap();
+            if (depth.running, depth.waiting) == (running, waiting) {
+                return;
+            }
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exclusive_tool_queues_calls() {
+        let (engine, concurrency, release) = engine(ConcurrencyLimit::exclusive()).await;
+
+        let first = call(&engine, "first", json!({}));
+        wait_for(&concurrency, 1, 0).await;
+        let second = call(&engine, "second", json!({}));
+        wait_for(&concurrency, 1, 1).await;
+        assert_eq!(
+            concurrency.queue_depths()["bridge"],
+            QueueDepth {
+                running: 1,
+                waiting: 1,
+                max_concurrency: 1
+            }
+        );
+
+        release.add_permits(1);
+        assert_eq!(first.await.unwrap().unwrap()["result"]["data"]["ok"], true);
+        wait_for(&concurrency, 1, 0).await;
+
+        release.add_permits(1);
+        assert_eq!(second.await.unwrap().unwrap()["result"]["data"]["ok"], true);
+        assert_eq!(
+            concurrency.queue_depth("bridge"),
+            Some(QueueDepth {
+                running: 0,
+                waiting: 0,
+                max_concurrency: 1
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reject_policy_fails_immediately() {
+        let (engine, concurrency, release) =
+            engine(ConcurrencyLimit::new(2).reject_when_full()).await;
+
+        let first = call(&engine, "first", json!({}));
+        let second = call(&engine, "second", json!({}));
+        wait_for(&concurrency, 2, 0).await;
+
+        let rejected = call(&engine, "third", json!({})).await.unwrap().unwrap();
+        assert_eq!(rejected["error"]["code"], -32004);
+        assert_eq!(
+            rejected["error"]["message"],
+            "Busy: tool 'bridge' is at its limit of 2 concurrent call(s)"
+        );
+
+        release.add_permits(2);
+        first.await.unwrap().unwrap();
+        second.await.unwrap().unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_queued_call_reports_progress_and_times_out() {
+        let (engine, concurrency, release) =
+            engine(ConcurrencyLimit::exclusive().wait_up_to(Duration::from_secs(3))).await;
+        let mut progress_rx = engine.notifier().subscribe("second");
+
+        let first = call(&engine, "first", json!({}));
+        wait_for(&concurrency, 1, 0).await;
+        let timed_out = call(&engine, "second", json!({ "progressToken": "queued-1" }))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(timed_out["error"]["code"], -32004);
+        assert_eq!(concurrency.queue_depth("bridge").unwrap().waiting, 0);
+
+        let mut reported = Vec::new();
+        while let Ok(notification) = progress_rx.try_recv() {
+            match notification {
+                McpNotification::Progress {
+                    progress_token,
+                    progress,
+                    total,
+                } => {
+                    assert_eq!(progress_token, "queued-1");
+                    assert_eq!(total, None);
+                    reported.push(progress);
+                }
+                other => panic!("unexpected notification: {other:?}"),
+            }
+        }
+        assert_eq!(reported[..3], [0.0, 1.0, 2.0]);
+
+        release.add_permits(1);
+        first.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unlimited_tools_report_no_queue() {
+        let framework = FrameworkHandler::new((), "concurrency-server", "1.0.0");
+        assert_eq!(framework.tool_concurrency().queue_depth("anything"), None);
+        assert!(framework.tool_concurrency().queue_depths().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_build_rejects_limits_for_unknown_tools() {
+        let error = McpServerBuilder::new((), "concurrency-server", "1.0.0")
+            .with_tool(
+                "render",
+                "Render a report",
+                |_input: Empty, _ctx, _notif| async move { Ok(Done { ok: true }) },
+            )
+            .with_tool_concurrency("render", ConcurrencyLimit::new(4))
+            .with_tool_concurrency("rendr", ConcurrencyLimit::exclusive())
+            .build()
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Concurrency limits set for unknown tools: rendr"
+        );
+    }
+}
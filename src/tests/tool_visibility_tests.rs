@@ -0,0 +1,196 @@
+//! Tool Visibility Tests
+//!
+//! Tests the tool filter and per-session visibility overrides for listing and
+//! calling tools.
+
+#[cfg(test)]
+mod tests {
+    use crate::framework::FrameworkHandler;
+    use crate::handler::McpNotification;
+    use crate::shared::McpProtocolEngine;
+    use anyhow::Result;
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+
+    #[derive(JsonSchema, Deserialize)]
+    struct Empty {}
+
+    #[derive(JsonSchema, Serialize)]
+    struct Done {
+        ok: bool,
+    }
+
+    fn framework() -> FrameworkHandler<()> {
+        let framework = FrameworkHandler::new((), "visibility-server", "1.0.0");
+        for name in ["search", "admin_purge"] {
+            framework.registry_handle().add_tool(
+                name,
+                "Test tool",
+                |_input: Empty, _ctx, _notif| async move { Ok(Done { ok: true }) },
+            );
+        }
+        framework
+    }
+
+    async fn initialize(engine: &McpProtocolEngine, session: &str, client_name: &str) {
+        engine
+            .handle_message(
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 0,
+                    "method": "initialize",
+                    "params": {
+                        "protocolVersion": "2025-06-18",
+                        "clientInfo": { "name": client_name, "version": "1.0" }
+                    }
+                }),
+                Some(session.to_string()),
+            )
+            .await
+            .unwrap();
+    }
+
+    async fn list(engine: &McpProtocolEngine, session: &str) -> Vec<String> {
+        let response = engine
+            .handle_message(
+                json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list", "params": {} }),
+                Some(session.to_string()),
+            )
+            .await
+            .unwrap();
+        let mut names: Vec<String> = response["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|tool| tool["name"].as_str().unwrap().to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    async fn call(engine: &McpProtocolEngine, session: &str, name: &str) -> Result<Value> {
+        engine
+            .handle_message(
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 2,
+                    "method": "tools/call",
+                    "params": { "name": name, "arguments": {} }
+                }),
+                Some(session.to_string()),
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_filter_hides_tools_by_client_name() {
+        let mut framework = framework();
+        framework.set_tool_filter(|tool, ctx| {
+            let client = ctx
+                .client_info
+                .as_ref()
+                .and_then(|info| info["name"].as_str());
+            client == Some("admin-console") || !tool.name.starts_with("admin_")
+        });
+        let engine = McpProtocolEngine::with_handler(Arc::new(framework));
+        initialize(&engine, "admin", "admin-console").await;
+        initialize(&engine, "user", "chat-app").await;
+
+        assert_eq!(list(&engine, "admin").await, vec!["admin_purge", "search"]);
+        assert_eq!(list(&engine, "user").await, vec!["search"]);
+
+        assert_eq!(
+            call(&engine, "admin", "admin_purge").await.unwrap()["result"]["data"]["ok"],
+            true
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hidden_tool_is_uncallable() {
+        let mut framework = framework();
+        framework.set_tool_filter(|tool, _ctx| tool.name != "admin_purge");
+        let engine = McpProtocolEngine::with_handler(Arc::new(framework));
+        initialize(&engine, "user", "chat-app").await;
+
+        let hidden = call(&engine, "user", "admin_purge").await.unwrap_err();
+        let unknown = call(&engine, "user", "does_not_exist").await.unwrap_err();
+        assert_eq!(
+            hidden.to_string(),
+            "Tool call error: Tool not found: admin_purge"
+        );
+        assert_eq!(
+            unknown.to_string(),
+            "Tool call error: Tool not found: does_not_exist"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_session_override_beats_filter_and_notifies_only_that_session() {
+        let mut framework = framework();
+        framework.set_tool_filter(|tool, _ctx| !tool.name.starts_with("admin_"));
+        let visibility = framework.tool_visibility();
+        let engine = McpProtocolEngine::with_handler(Arc::new(framework));
+        let mut unlocked_rx = engine.notifier().subscribe("unlocked");
+        let mut other_rx = engine.notifier().subscribe("other");
+        initialize(&engine, "unlocked", "chat-app").await;
+        initialize(&engine, "other", "chat-app").await;
+
+        assert!(visibility.show_tool("unlocked", "admin_purge"));
+        assert!(matches!(
+            unlocked_rx.try_recv(),
+            Ok(McpNotification::ToolsListChanged)
+        ));
+        assert!(other_rx.try_recv().is_err());
+
+        assert_eq!(
+            list(&engine, "unlocked").await,
+            vec!["admin_purge", "search"]
+        );
+        assert_eq!(list(&engine, "other").await, vec!["search"]);
+
+        // Setting the same override again is not a change
+        assert!(!visibility.show_tool("unlocked", "admin_purge"));
+        assert!(unlocked_rx.try_recv().is_err());
+
+        assert!(visibility.hide_tool("unlocked", "search"));
+        assert_eq!(list(&engine, "unlocked").await, vec!["admin_purge"]);
+        assert!(call(&engine, "unlocked", "search").await.is_err());
+
+        assert!(visibility.reset_session("unlocked"));
+        assert_eq!(list(&engine, "unlocked").await, vec!["search"]);
+        assert_eq!(visibility.session_override("unlocked", "search"), None);
+    }
+
+    #[tokio::test]
+    async fn test_tool_can_change_visibility_of_its_own_session() {
+        let mut framework = framework();
+        framework.set_tool_filter(|tool, _ctx| tool.name != "admin_purge");
+        let visibility = framework.tool_visibility();
+        framework.registry_handle().add_tool(
+            "unlock",
+            "Unlock admin tools",
+            move |_input: Empty, _ctx, notif| {
+                let visibility = visibility.clone();
+                async move {
+                    if let Some(session_id) = notif.session_id() {
+                        visibility.show_tool(session_id, "admin_purge");
+                    }
+                    Ok(Done { ok: true })
+                }
+            },
+        );
+        let engine = McpProtocolEngine::with_handler(Arc::new(framework));
+        initialize(&engine, "user", "chat-app").await;
+        initialize(&engine, "bystander", "chat-app").await;
+
+        call(&engine, "user", "unlock").await.unwrap();
+
+        assert_eq!(
+            list(&engine, "user").await,
+            vec!["admin_purge", "search", "unlock"]
+        );
+        assert_eq!(list(&engine, "bystander").await, vec!["search", "unlock"]);
+    }
+}
@@ -0,0 +1,267 @@
+//! Trace Context Tests
+//!
+//! Tests W3C `traceparent` parsing, that tools continue the caller's trace
+//! from `params._meta` or the HTTP headers, and, with the `otel` feature, that
+//! the `mcp.request` spans reach an OpenTelemetry `InMemorySpanExporter` as
+//! part of the caller's trace.
+
+#[cfg(test)]
+mod tests {
+    use crate::framework::FrameworkHandler;
+    use crate::http::HttpMcpHandler;
+    use crate::shared::McpProtocolEngine;
+    use crate::trace_context::TraceContext;
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use warp::test::request;
+
+    const CALLER: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    #[derive(JsonSchema, Deserialize)]
+    struct Empty {}
+
+    #[derive(JsonSchema, Serialize)]
+    struct Downstream {
+        traceparent: Option<String>,
+    }
+
+    fn framework() -> FrameworkHandler<()> {
+        let framework = FrameworkHandler::new((), "tracing-server", "1.0.0");
+        framework.registry_handle().add_tool(
+            "fetch",
+            "Call a downstream service",
+            |_input: Empty, _ctx, notif| async move {
+                tracing::info!("calling downstream");
+                Ok(Downstream {
+                    traceparent: notif.traceparent(),
+                })
+            },
+        );
+        framework
+    }
+
+    async fn call(engine: &McpProtocolEngine, meta: Option<Value>) -> Value {
+        let mut params = json!({ "name": "fetch", "arguments": {} });
+        if let Some(meta) = meta {
+            params["_meta"] = meta;
+        }
+        engine
+            .handle_message(
+                json!({ "jsonrpc": "2.0", "id": 7, "method": "tools/call", "params": params }),
+                Some("traced".to_string()),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_traceparent_is_parsed_and_formatted() {
+        let caller = TraceContext::parse(CALLER).unwrap();
+        assert_eq!(caller.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(caller.span_id, "00f067aa0ba902b7");
+        assert!(caller.sampled);
+        assert_eq!(caller.traceparent(), CALLER);
+
+        let child = caller.child();
+        assert_eq!(child.trace_id, caller.trace_id);
+        assert_eq!(child.parent_span_id.as_deref(), Some("00f067aa0ba902b7"));
+        assert_ne!(child.span_id, caller.span_id);
+
+        for invalid in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+        ] {
+            assert_eq!(TraceContext::parse(invalid), None, "{invalid:?}");
+        }
+        // Later versions may append fields
+        assert!(TraceContext::parse(&format!("01{}-extra", &CALLER[2..])).is_some());
+    }
+
+    fn passed_on(response: &Value) -> TraceContext {
+        TraceContext::parse(response["result"]["data"]["traceparent"].as_str().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_tools_continue_the_callers_trace() {
+        let engine = McpProtocolEngine::with_handler(Arc::new(framework()));
+
+        let response = call(&engine, Some(json!({ "traceparent": CALLER }))).await;
+        let downstream = passed_on(&response);
+        assert_eq!(downstream.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_ne!(downstream.span_id, "00f067aa0ba902b7");
+        assert!(downstream.sampled);
+
+        // Without a valid traceparent every request starts its own trace
+        let first = passed_on(&call(&engine, None).await);
+        let second = passed_on(&call(&engine, Some(json!({ "traceparent": "garbage" }))).await);
+        assert_ne!(first.trace_id, second.trace_id);
+        assert_ne!(first.trace_id, downstream.trace_id);
+    }
+
+    #[tokio::test]
+    async fn test_http_traceparent_header_is_used() {
+        let engine = Arc::new(McpProtocolEngine::with_handler(Arc::new(framework())));
+        let routes = HttpMcpHandler::new(engine).route();
+        let send = |meta: Value| {
+            request()
+                .method("POST")
+                .path("/mcp")
+                .header("traceparent", CALLER)
+                .json(&json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "tools/call",
+                    "params": { "name": "fetch", "arguments": {}, "_meta": meta }
+                }))
+                .reply(&routes)
+        };
+
+        let resp = send(json!({})).await;
+        let body: Value = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(
+            passed_on(&body).trace_id,
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+
+        // `_meta` wins over the headers
+        let resp = send(json!({
+            "traceparent": "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01"
+        }))
+        .await;
+        let body: Value = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(
+            passed_on(&body).trace_id,
+            "0af7651916cd43dd8448eb211c80319c"
+        );
+    }
+
+    #[cfg(feature = "otel")]
+    mod otel {
+        use super::*;
+        use opentelemetry::trace::{SpanId, SpanKind, Status, TracerProvider as _};
+        use opentelemetry::Value as OtelValue;
+        use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider, SpanData};
+        use tracing::subscriber::DefaultGuard;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        /// Install a `tracing_opentelemetry` layer exporting to memory for this thread.
+        fn exporter() -> (InMemorySpanExporter, DefaultGuard) {
+            let exporter = InMemorySpanExporter::default();
+            let provider = SdkTracerProvider::builder()
+                .with_simple_exporter(exporter.clone())
+                .build();
+            let layer =
+                tracing_opentelemetry::layer().with_tracer(provider.tracer("solidmcp-tests"));
+            let guard =
+                tracing::subscriber::set_default(tracing_subscriber::registry().with(layer));
+            (exporter, guard)
+        }
+
+        fn requests(exporter: &InMemorySpanExporter) -> Vec<SpanData> {
+            exporter
+                .get_finished_spans()
+                .unwrap()
+                .into_iter()
+                .filter(|span| span.span_kind == SpanKind::Server)
+                .collect()
+        }
+
+        fn attribute(span: &SpanData, key: &str) -> Option<OtelValue> {
+            span.attributes
+                .iter()
+                .find(|attribute| attribute.key.as_str() == key)
+                .map(|attribute| attribute.value.clone())
+        }
+
+        #[tokio::test]
+        async fn test_request_span_is_exported_as_child_of_caller() {
+            let (exporter, _guard) = exporter();
+            let engine = McpProtocolEngine::with_handler(Arc::new(framework()));
+
+            let response = call(&engine, Some(json!({ "traceparent": CALLER }))).await;
+
+            let spans = requests(&exporter);
+            assert_eq!(spans.len(), 1);
+            let span = &spans[0];
+            assert_eq!(span.name, "tools/call fetch");
+            assert_eq!(
+                span.span_context.trace_id().to_string(),
+                "4bf92f3577b34da6a3ce929d0e0e4736"
+            );
+            assert_eq!(span.parent_span_id.to_string(), "00f067aa0ba902b7");
+            assert!(span.parent_span_is_remote);
+            assert_eq!(attribute(span, "rpc.method"), Some("tools/call".into()));
+            assert_eq!(attribute(span, "mcp.tool"), Some("fetch".into()));
+            assert_eq!(attribute(span, "mcp.session_id"), Some("traced".into()));
+            assert!(span
+                .events
+                .iter()
+                .any(|event| event.name == "calling downstream"));
+
+            // The tool passes the exported span on to downstream services
+            assert_eq!(
+                response["result"]["data"]["traceparent"],
+                format!(
+                    "00-4bf92f3577b34da6a3ce929d0e0e4736-{}-01",
+                    span.span_context.span_id()
+                )
+            );
+        }
+
+        #[tokio::test]
+        async fn test_request_without_traceparent_starts_new_trace() {
+            let (exporter, _guard) = exporter();
+            let engine = McpProtocolEngine::with_handler(Arc::new(framework()));
+
+            call(&engine, None).await;
+            call(&engine, Some(json!({ "traceparent": "garbage" }))).await;
+
+            let spans = requests(&exporter);
+            assert_eq!(spans.len(), 2);
+            for span in &spans {
+                assert_eq!(span.parent_span_id, SpanId::INVALID);
+            }
+            assert_ne!(
+                spans[0].span_context.trace_id(),
+                spans[1].span_context.trace_id()
+            );
+        }
+
+        #[tokio::test]
+        async fn test_http_traceparent_header_is_used_and_errors_are_marked() {
+            let (exporter, _guard) = exporter();
+            let engine = Arc::new(McpProtocolEngine::with_handler(Arc::new(framework())));
+            let routes = HttpMcpHandler::new(engine).route();
+
+            request()
+                .method("POST")
+                .path("/mcp")
+                .header("traceparent", CALLER)
+                .header("tracestate", "vendor=abc")
+                .json(&json!({ "jsonrpc": "2.0", "id": 1, "method": "made/up" }))
+                .reply(&routes)
+                .await;
+
+            let spans = requests(&exporter);
+            let span = spans.last().unwrap();
+            assert_eq!(
+                span.span_context.trace_id().to_string(),
+                "4bf92f3577b34da6a3ce929d0e0e4736"
+            );
+            assert_eq!(span.parent_span_id.to_string(), "00f067aa0ba902b7");
+            assert_eq!(span.span_context.trace_state().get("vendor"), Some("abc"));
+            assert!(matches!(span.status, Status::Error { .. }));
+            assert_eq!(
+                attribute(span, "rpc.jsonrpc.error_code"),
+                Some((-32601i64).into())
+            );
+        }
+    }
+}
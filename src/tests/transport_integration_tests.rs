@@ -37,7 +37,7 @@ mod tests {
         assert_eq!(headers.get("access-control-allow-origin").unwrap(), "*");
         assert_eq!(
             headers.get("access-control-allow-methods").unwrap(),
-            "GET, POST, OPTIONS"
+            "GET, POST, DELETE, OPTIONS"
         );
         assert!(headers.get("access-control-allow-headers").is_some());
         assert_eq!(headers.get("access-control-max-age").unwrap(), "3600");
@@ -313,7 +313,7 @@ mod tests {
         assert_eq!(headers.get("access-control-allow-origin").unwrap(), "*");
         assert_eq!(
             headers.get("access-control-allow-methods").unwrap(),
-            "GET, POST, OPTIONS"
+            "GET, POST, DELETE, OPTIONS"
         );
         assert!(headers
             .get("access-control-allow-headers")
@@ -0,0 +1,120 @@
+//! Typed Prompt Tests
+//!
+//! Tests prompts registered from a typed argument struct: derived argument
+//! metadata, string coercion and `-32602` errors for invalid arguments.
+
+#[cfg(test)]
+mod tests {
+    use crate::framework::FrameworkHandler;
+    use crate::handler::{PromptContent, PromptMessage};
+    use crate::shared::McpProtocolEngine;
+    use schemars::JsonSchema;
+    use serde::Deserialize;
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+
+    #[derive(JsonSchema, Deserialize)]
+    struct SummaryArgs {
+        /// Text to summarize
+        text: String,
+        /// Maximum number of sentences
+        sentences: Option<u32>,
+    }
+
+    async fn setup() -> (McpProtocolEngine, Option<String>) {
+        let mut framework = FrameworkHandler::new((), "typed-prompt-server", "1.0.0");
+        framework.registry_mut().register_prompt::<SummaryArgs, _>(
+            "summarize",
+            "Summarize a piece of text",
+            |args, _ctx| async move {
+                Ok(PromptContent {
+                    description: None,
+                    messages: vec![PromptMessage::user(format!(
+                        "Summarize in {} sentences: {}",
+                        args.sentences.unwrap_or(1),
+                        args.text
+                    ))],
+                })
+            },
+        );
+        let engine = McpProtocolEngine::with_handler(Arc::new(framework));
+        let session = Some("typed-prompts".to_string());
+        engine
+            .handle_message(
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 0,
+                    "method": "initialize",
+                    "params": { "protocolVersion": "2025-06-18" }
+                }),
+                session.clone(),
+            )
+            .await
+            .unwrap();
+        (engine, session)
+    }
+
+    async fn get_prompt(arguments: Value) -> Value {
+        let (engine, session) = setup().await;
+        engine
+            .handle_message(
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "prompts/get",
+                    "params": { "name": "summarize", "arguments": arguments }
+                }),
+                session,
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_typed_prompt_lists_schema_arguments() {
+        let (engine, session) = setup().await;
+        let response = engine
+            .handle_message(
+                json!({ "jsonrpc": "2.0", "id": 1, "method": "prompts/list", "params": {} }),
+                session,
+            )
+            .await
+            .unwrap();
+
+        let prompt = &response["result"]["prompts"][0];
+        assert_eq!(prompt["name"], "summarize");
+        assert_eq!(prompt["description"], "Summarize a piece of text");
+
+        let arguments = prompt["arguments"].as_array().unwrap();
+        let text = arguments.iter().find(|a| a["name"] == "text").unwrap();
+        assert_eq!(text["description"], "Text to summarize");
+        assert_eq!(text["required"], true);
+        let sentences = arguments.iter().find(|a| a["name"] == "sentences").unwrap();
+        assert_eq!(sentences["required"], false);
+    }
+
+    #[tokio::test]
+    async fn test_typed_prompt_coerces_string_arguments() {
+        let response = get_prompt(json!({ "text": "MCP is a protocol.", "sentences": "3" })).await;
+        assert_eq!(
+            response["result"]["messages"][0]["content"]["text"],
+            "Summarize in 3 sentences: MCP is a protocol."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_typed_prompt_missing_required_argument() {
+        let response = get_prompt(json!({ "sentences": "2" })).await;
+        assert_eq!(response["error"]["code"], -32602);
+        assert!(response["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("Missing required argument 'text'"));
+    }
+
+    #[tokio::test]
+    async fn test_typed_prompt_invalid_argument_type() {
+        let response = get_prompt(json!({ "text": "hello", "sentences": "many" })).await;
+        assert_eq!(response["error"]["code"], -32602);
+    }
+}
@@ -0,0 +1,435 @@
+//! TLS
+//!
+//! Serves the MCP endpoints over HTTPS and secure WebSockets with rustls.
+//! Certificates are read from PEM files, and can be reloaded without a
+//! restart, either explicitly through `ServerHandle::reload_tls` or by polling
+//! the files for changes. With a client CA configured the server verifies
+//! client certificates (mutual TLS) and exposes the verified identity to
+//! handlers through `McpContext::client_certificate`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use solidmcp::server_config::ServerConfig;
+//! use solidmcp::tls::{ClientAuth, TlsConfig};
+//! use std::time::Duration;
+//!
+//! let tls = TlsConfig::new("/etc/mcp/tls.crt", "/etc/mcp/tls.key")
+//!     .with_client_ca("/etc/mcp/clients-ca.crt", ClientAuth::Required)
+//!     .with_reload_interval(Duration::from_secs(60));
+//! let handle = server
+//!     .serve(ServerConfig::new(([0, 0, 0, 0], 8443)).with_tls(tls))
+//!     .await?;
+//! ```
+
+use {
+    crate::server_handle,
+    anyhow::{anyhow, Context, Result},
+    ring::digest,
+    rustls::{
+        crypto::ring as ring_provider,
+        pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer},
+        server::WebPkiClientVerifier,
+        RootCertStore, ServerConfig,
+    },
+    std::{
+        future,
+        net::{IpAddr, SocketAddr},
+        path::{Path, PathBuf},
+        sync::{Arc, RwLock},
+        time::{Duration, SystemTime},
+    },
+    tokio::{
+        net::TcpListener,
+        sync::{oneshot, watch},
+        task::JoinSet,
+        time::MissedTickBehavior,
+    },
+    tokio_rustls::TlsAcceptor,
+    tracing::{debug, info, warn},
+    warp::{
+        hyper::{server::conn::Http, service::Service, Body, Request},
+        Filter, Rejection, Reply,
+    },
+    x509_parser::{certificate::X509Certificate, extensions::GeneralName, prelude::FromDer},
+};
+
+/// Whether a server with a client CA insists on client certificates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientAuth {
+    /// Clients without a certificate may connect; those presenting one must pass verification
+    Optional,
+    /// The handshake fails unless the client presents a certificate the CA issued
+    Required,
+}
+
+/// Where the server's certificate and key are, and how clients are verified.
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    cert_path: PathBuf,
+    key_path: PathBuf,
+    client_ca: Option<(PathBuf, ClientAuth)>,
+    reload_interval: Option<Duration>,
+    handshake_timeout: Duration,
+}
+
+impl TlsConfig {
+    /// How long a client gets to complete the TLS handshake unless configured otherwise
+    pub const DEFAULT_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Serve with the certificate chain and private key in PEM files.
+    ///
+    /// # Parameters
+    /// - `cert_path`: PEM file with the server certificate, followed by its intermediates
+    /// - `key_path`: PEM file with the private key (PKCS#8, PKCS#1 or SEC1)
+    pub fn new(cert_path: impl Into<PathBuf>, key_path: impl Into<PathBuf>) -> Self {
+        Self {
+            cert_path: cert_path.into(),
+            key_path: key_path.into(),
+            client_ca: None,
+            reload_interval: None,
+            handshake_timeout: Self::DEFAULT_HANDSHAKE_TIMEOUT,
+        }
+    }
+
+    /// Verify client certificates against the CA certificates in a PEM file.
+    ///
+    /// # Parameters
+    /// - `ca_path`: PEM file with the certificates of the CAs issuing client certificates
+    /// - `auth`: Whether clients must present a certificate
+    pub fn with_client_ca(mut self, ca_path: impl Into<PathBuf>, auth: ClientAuth) -> Self {
+        self.client_ca = Some((ca_path.into(), auth));
+        self
+    }
+
+    /// Check the PEM files for changes every `interval`, reloading them when
+    /// they change.
+    ///
+    /// # Parameters
+    /// - `interval`: How often to compare the files' modification times
+    pub fn with_reload_interval(mut self, interval: Duration) -> Self {
+        self.reload_interval = Some(interval);
+        self
+    }
+
+    /// Close connections whose TLS handshake has not completed within `timeout`,
+    /// so clients that connect and then stall do not hold connections open.
+    ///
+    /// # Parameters
+    /// - `timeout`: How long a client gets to complete the handshake (default: 10 seconds)
+    pub fn with_handshake_timeout(mut self, timeout: Duration) -> Self {
+        self.handshake_timeout = timeout;
+        self
+    }
+
+    fn paths(&self) -> impl Iterator<Item = &Path> {
+        [&self.cert_path, &self.key_path]
+            .into_iter()
+            .chain(self.client_ca.as_ref().map(|(path, _)| path))
+            .map(PathBuf::as_path)
+    }
+
+    /// Read the PEM files into a rustls configuration.
+    fn load(&self) -> Result<ServerConfig> {
+        let certs = CertificateDer::pem_file_iter(&self.cert_path)
+            .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+            .with_context(|| {
+                format!(
+                    "Could not read certificates from {}",
+                    self.cert_path.display()
+                )
+            })?;
+        if certs.is_empty() {
+            return Err(anyhow!("No certificate in {}", self.cert_path.display()));
+        }
+        let key = PrivateKeyDer::from_pem_file(&self.key_path).with_context(|| {
+            format!(
+                "Could not read private key from {}",
+                self.key_path.display()
+            )
+        })?;
+
+        let provider = Arc::new(ring_provider::default_provider());
+        let builder = ServerConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()?;
+        let builder = match &self.client_ca {
+            None => builder.with_no_client_auth(),
+            Some((ca_path, auth)) => {
+                let mut roots = RootCertStore::empty();
+                for cert in CertificateDer::pem_file_iter(ca_path).with_context(|| {
+                    format!("Could not read client CA from {}", ca_path.display())
+                })? {
+                    roots.add(cert?)?;
+                }
+                let verifier =
+                    WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider);
+                let verifier = match auth {
+                    ClientAuth::Optional => verifier.allow_unauthenticated(),
+                    ClientAuth::Required => verifier,
+                };
+                builder.with_client_cert_verifier(verifier.build()?)
+            }
+        };
+        let mut config = builder
+            .with_single_cert(certs, key)
+            .context("Certificate does not match the private key")?;
+        // WebSocket upgrades need HTTP/1.1
+        config.alpn_protocols = vec![b"http/1.1".to_vec()];
+        Ok(config)
+    }
+}
+
+/// The verified certificate a client presented during the TLS handshake.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientCertificate {
+    /// Common name (CN) of the certificate's subject
+    pub common_name: Option<String>,
+    /// DNS names, URIs (e.g. SPIFFE IDs), email addresses and IP addresses
+    /// from the subject alternative name extension
+    pub subject_alt_names: Vec<String>,
+    /// SHA-256 fingerprint of the certificate, as lowercase hex
+    pub fingerprint: String,
+    /// The DER-encoded certificate
+    pub der: Vec<u8>,
+}
+
+impl ClientCertificate {
+    /// Read the identity out of a DER-encoded certificate.
+    pub fn from_der(der: &[u8]) -> Self {
+        let fingerprint = digest::digest(&digest::SHA256, der)
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        let (common_name, subject_alt_names) = parse_identity(der).unwrap_or_default();
+        Self {
+            common_name,
+            subject_alt_names,
+            fingerprint,
+            der: der.to_vec(),
+        }
+    }
+}
+
+/// Find the subject's common name and the subject alternative names of a certificate.
+fn parse_identity(der: &[u8]) -> Option<(Option<String>, Vec<String>)> {
+    let (_, certificate) = X509Certificate::from_der(der).ok()?;
+    let common_name = certificate
+        .subject()
+        .iter_common_name()
+        .next()
+        .and_then(|name| name.as_str().ok())
+        .map(str::to_string);
+    let subject_alt_names = match certificate.subject_alternative_name().ok()? {
+        Some(extension) => extension
+            .value
+            .general_names
+            .iter()
+            .filter_map(|name| match name {
+                GeneralName::RFC822Name(name)
+                | GeneralName::DNSName(name)
+                | GeneralName::URI(name) => Some(name.to_string()),
+                GeneralName::IPAddress(ip) => match ip.len() {
+                    4 => <[u8; 4]>::try_from(*ip).ok().map(IpAddr::from),
+                    16 => <[u8; 16]>::try_from(*ip).ok().map(IpAddr::from),
+                    _ => None,
+                }
+                .map(|ip| ip.to_string()),
+                _ => None,
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+    Some((common_name, subject_alt_names))
+}
+
+/// The TLS side of a connection, made available to filters as a request extension.
+#[derive(Clone)]
+pub(crate) struct TlsPeer {
+    remote_addr: SocketAddr,
+    client_certificate: Option<ClientCertificate>,
+}
+
+/// Extract the client's address and certificate.
+///
+/// `warp::addr::remote` only knows the address of plain connections, so TLS
+/// connections carry theirs in the `TlsPeer` extension.
+pub(crate) fn peer() -> impl Filter<
+    Extract = (Option<SocketAddr>, Option<ClientCertificate>),
+    Error = std::convert::Infallible,
+> + Clone {
+    warp::addr::remote()
+        .and(warp::ext::optional::<TlsPeer>())
+        .map(
+            |remote_addr: Option<SocketAddr>, tls: Option<TlsPeer>| match tls {
+                Some(tls) => (Some(tls.remote_addr), tls.client_certificate),
+                None => (remote_addr, None),
+            },
+        )
+        .untuple_one()
+}
+
+/// The server's current rustls configuration, replaced on reload.
+pub(crate) struct ReloadableTls {
+    config: TlsConfig,
+    current: RwLock<Arc<ServerConfig>>,
+    modified: RwLock<Vec<Option<SystemTime>>>,
+}
+
+impl ReloadableTls {
+    pub(crate) fn load(config: TlsConfig) -> Result<Self> {
+        let current = Arc::new(config.load()?);
+        let modified = modification_times(&config);
+        Ok(Self {
+            config,
+            current: RwLock::new(current),
+            modified: RwLock::new(modified),
+        })
+    }
+
+    /// Read the PEM files again, keeping the current configuration if they are invalid.
+    ///
+    /// Connections already established keep the configuration they started with.
+    pub(crate) fn reload(&self) -> Result<()> {
+        let modified = modification_times(&self.config);
+        let config = self.config.load()?;
+        *self.current.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(config);
+        *self.modified.write().unwrap_or_else(|e| e.into_inner()) = modified;
+        info!(
+            "Reloaded TLS certificates from {}",
+            self.config.cert_path.display()
+        );
+        Ok(())
+    }
+
+    /// Reload the PEM files if any of them changed since the last load.
+    fn reload_if_changed(&self) {
+        let modified = modification_times(&self.config);
+        if *self.modified.read().unwrap_or_else(|e| e.into_inner()) == modified {
+            return;
+        }
+        if let Err(e) = self.reload() {
+            warn!("Keeping the current TLS certificates: {:#}", e);
+            // Retry only once the files change again
+            *self.modified.write().unwrap_or_else(|e| e.into_inner()) = modified;
+        }
+    }
+
+    fn acceptor(&self) -> TlsAcceptor {
+        TlsAcceptor::from(
+            self.current
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone(),
+        )
+    }
+}
+
+fn modification_times(config: &TlsConfig) -> Vec<Option<SystemTime>> {
+    config
+        .paths()
+        .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+/// Serve `filter` over TLS on `listener` until `stopped` fires, then let the
+/// open connections finish their current request.
+pub(crate) async fn serve<F>(
+    listener: TcpListener,
+    tls: Arc<ReloadableTls>,
+    filter: F,
+    stopped: oneshot::Receiver<()>,
+) where
+    F: Filter + Clone + Send + Sync + 'static,
+    F::Extract: Reply,
+    F::Error: Into<Rejection>,
+{
+    let service = warp::service(filter);
+    let (closing, closed) = watch::channel(false);
+    let mut connections = JoinSet::new();
+    let stop = server_handle::stop_requested(stopped);
+    tokio::pin!(stop);
+    let mut reload = tls.config.reload_interval.map(|interval| {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        ticker
+    });
+
+    loop {
+        let (tcp, remote_addr) = tokio::select! {
+            () = &mut stop => break,
+            _ = async {
+                match &mut reload {
+                    Some(ticker) => ticker.tick().await,
+                    None => future::pending().await,
+                }
+            } => {
+                tls.reload_if_changed();
+                continue;
+            }
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    warn!("Failed to accept connection: {}", e);
+                    continue;
+                }
+            },
+        };
+        // Reap finished connections so the set does not grow
+        while connections.try_join_next().is_some() {}
+
+        let acceptor = tls.acceptor();
+        let handshake_timeout = tls.config.handshake_timeout;
+        let service = service.clone();
+        let mut closed = closed.clone();
+        connections.spawn(async move {
+            let handshake = tokio::time::timeout(handshake_timeout, acceptor.accept(tcp));
+            let stream = tokio::select! {
+                result = handshake => match result {
+                    Ok(Ok(stream)) => stream,
+                    Ok(Err(e)) => {
+                        debug!("TLS handshake with {} failed: {}", remote_addr, e);
+                        return;
+                    }
+                    Err(_) => {
+                        debug!("TLS handshake with {} timed out", remote_addr);
+                        return;
+                    }
+                },
+                // Shutting down: drop connections still in the handshake
+                _ = closed.changed() => return,
+            };
+            let peer = TlsPeer {
+                remote_addr,
+                client_certificate: stream
+                    .get_ref()
+                    .1
+                    .peer_certificates()
+                    .and_then(|certs| certs.first())
+                    .map(|cert| ClientCertificate::from_der(cert.as_ref())),
+            };
+            let service = warp::hyper::service::service_fn(move |mut request: Request<Body>| {
+                request.extensions_mut().insert(peer.clone());
+                service.clone().call(request)
+            });
+            let connection = Http::new()
+                .http1_only(true)
+                .serve_connection(stream, service)
+                .with_upgrades();
+            tokio::pin!(connection);
+            let result = tokio::select! {
+                result = connection.as_mut() => result,
+                _ = closed.changed() => {
+                    connection.as_mut().graceful_shutdown();
+                    connection.await
+                }
+            };
+            if let Err(e) = result {
+                debug!("TLS connection from {} failed: {}", remote_addr, e);
+            }
+        });
+    }
+
+    let _ = closing.send(true);
+    while connections.join_next().await.is_some() {}
+}
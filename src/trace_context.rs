@@ -0,0 +1,316 @@
+//! Distributed Tracing
+//!
+//! The protocol engine handles every message inside an `mcp.request` span of
+//! the `tracing` crate. The span carries the method, tool name, session and
+//! JSON-RPC request ID, plus OpenTelemetry conventions (`otel.name`,
+//! `otel.kind`, `rpc.*`) and the IDs of the request's W3C trace context.
+//!
+//! A request continues the caller's trace when it carries a
+//! [`traceparent`](https://www.w3.org/TR/trace-context/) in `params._meta` or
+//! in the HTTP headers (the `_meta` value wins, since WebSocket headers only
+//! describe the upgrade request). Otherwise it starts a new trace. Tool
+//! handlers reach the span and the trace context through `NotificationCtx`,
+//! and pass `traceparent()` on to the services they call.
+//!
+//! With the `otel` feature and a `tracing_opentelemetry` layer installed, the
+//! caller's context is extracted with OpenTelemetry's `TraceContextPropagator`
+//! and set as the parent of the span, so the span is exported as part of the
+//! caller's trace. The IDs then come from the span's OpenTelemetry context.
+//! Without such a layer, the IDs are generated here.
+//!
+//! # Example
+//!
+//! ```rust
+//! async fn fetch(input: FetchInput, ctx: Arc<AppContext>, notif: NotificationCtx) -> Result<FetchOutput> {
+//!     let mut request = ctx.http.get(&input.url);
+//!     if let Some(traceparent) = notif.traceparent() {
+//!         request = request.header("traceparent", traceparent);
+//!     }
+//!     let body = request.send().await?.text().await?;
+//!     tracing::info!(bytes = body.len(), "fetched");
+//!     Ok(FetchOutput { body })
+//! }
+//! ```
+
+use {
+    crate::handler::ConnectionInfo,
+    serde_json::Value,
+    std::{collections::HashMap, fmt},
+    tracing::{field, Span},
+};
+
+/// W3C trace context of a request.
+///
+/// `span_id` identifies the span handling the request on this server;
+/// `parent_span_id` is the caller's span, if the request continued a trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    /// 32 lowercase hex digits shared by every span of the trace
+    pub trace_id: String,
+    /// 16 lowercase hex digits identifying this span
+    pub span_id: String,
+    /// Span of the caller, when the request carried a `traceparent`
+    pub parent_span_id: Option<String>,
+    /// Whether the caller asked for the trace to be recorded
+    pub sampled: bool,
+    /// Vendor-specific `tracestate`, passed on unchanged
+    pub tracestate: Option<String>,
+}
+
+impl TraceContext {
+    /// Start a new, sampled trace.
+    pub fn new_root() -> Self {
+        Self {
+            trace_id: random_id::<16>(),
+            span_id: random_id::<8>(),
+            parent_span_id: None,
+            sampled: true,
+            tracestate: None,
+        }
+    }
+
+    /// Parse a `traceparent` header value.
+    ///
+    /// The returned context describes the caller's span: `span_id` is the
+    /// header's parent ID. Use [`child`](Self::child) for a span of your own.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the value is not a valid `traceparent`
+    pub fn parse(traceparent: &str) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let span_id = parts.next()?;
+        let flags = parts.next()?;
+        // Version 00 has exactly four fields; later versions may append more
+        if !is_hex(version, 2) || version == "ff" || (version == "00" && parts.next().is_some()) {
+            return None;
+        }
+        if !is_hex(trace_id, 32) || !is_hex(span_id, 16) || !is_hex(flags, 2) {
+            return None;
+        }
+        if trace_id.bytes().all(|b| b == b'0') || span_id.bytes().all(|b| b == b'0') {
+            return None;
+        }
+        Some(Self {
+            trace_id: trace_id.to_string(),
+            span_id: span_id.to_string(),
+            parent_span_id: None,
+            sampled: u8::from_str_radix(flags, 16).ok()? & 1 == 1,
+            tracestate: None,
+        })
+    }
+
+    /// Create the context of a new span in the same trace, with this span as its parent.
+    pub fn child(&self) -> Self {
+        Self {
+            trace_id: self.trace_id.clone(),
+            span_id: random_id::<8>(),
+            parent_span_id: Some(self.span_id.clone()),
+            sampled: self.sampled,
+            tracestate: self.tracestate.clone(),
+        }
+    }
+
+    /// Format the context as a `traceparent` value, for propagating it downstream.
+    pub fn traceparent(&self) -> String {
+        format!(
+            "00-{}-{}-{:02x}",
+            self.trace_id,
+            self.span_id,
+            u8::from(self.sampled)
+        )
+    }
+
+    /// Get the context of the server's span for a request and record its IDs
+    /// on `span`: a child of the caller's `traceparent` if the request carried
+    /// one, a new trace otherwise.
+    pub(crate) fn for_request(
+        span: &Span,
+        meta: Option<&Value>,
+        connection: Option<&ConnectionInfo>,
+    ) -> Self {
+        let carrier = carrier(meta, connection);
+        let trace = continue_trace(span, &carrier).unwrap_or_else(|| {
+            let caller = carrier
+                .get("traceparent")
+                .and_then(|value| Self::parse(value));
+            match caller {
+                Some(mut caller) => {
+                    caller.tracestate = carrier.get("tracestate").cloned();
+                    caller.child()
+                }
+                None => Self::new_root(),
+            }
+        });
+        span.record("trace_id", trace.trace_id.as_str());
+        span.record("span_id", trace.span_id.as_str());
+        if let Some(parent) = &trace.parent_span_id {
+            span.record("parent_span_id", parent.as_str());
+        }
+        trace
+    }
+}
+
+#[cfg(not(feature = "otel"))]
+fn continue_trace(_span: &Span, _carrier: &HashMap<String, String>) -> Option<TraceContext> {
+    None
+}
+
+/// Get the `traceparent` and `tracestate` of the caller, from `params._meta`
+/// or else from the HTTP headers. Only a valid `traceparent` is taken.
+fn carrier(meta: Option<&Value>, connection: Option<&ConnectionInfo>) -> HashMap<String, String> {
+    let from_meta = meta.and_then(|meta| {
+        let traceparent = meta.get("traceparent")?.as_str()?;
+        TraceContext::parse(traceparent)?;
+        let tracestate = meta.get("tracestate").and_then(Value::as_str);
+        Some((traceparent, tracestate))
+    });
+    let from_headers = || {
+        let headers = &connection?.headers;
+        let traceparent = headers.get("traceparent")?.to_str().ok()?;
+        TraceContext::parse(traceparent)?;
+        let tracestate = headers
+            .get("tracestate")
+            .and_then(|value| value.to_str().ok());
+        Some((traceparent, tracestate))
+    };
+    let mut carrier = HashMap::new();
+    if let Some((traceparent, tracestate)) = from_meta.or_else(from_headers) {
+        carrier.insert("traceparent".to_string(), traceparent.to_string());
+        if let Some(tracestate) = tracestate {
+            carrier.insert("tracestate".to_string(), tracestate.to_string());
+        }
+    }
+    carrier
+}
+
+impl fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.traceparent())
+    }
+}
+
+/// Create the span a message is handled in.
+///
+/// The trace IDs are recorded by [`TraceContext::for_request`].
+pub(crate) fn request_span(message: &Value, session_id: &str) -> Span {
+    let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+    let tool = match method {
+        "tools/call" => message
+            .get("params")
+            .and_then(|params| params.get("name"))
+            .and_then(Value::as_str),
+        _ => None,
+    };
+    let name = match tool {
+        Some(tool) => format!("{method} {tool}"),
+        None => method.to_string(),
+    };
+    let span = tracing::info_span!(
+        "mcp.request",
+        otel.name = %name,
+        otel.kind = "server",
+        otel.status_code = field::Empty,
+        rpc.system = "jsonrpc",
+        rpc.method = %method,
+        rpc.jsonrpc.request_id = field::Empty,
+        rpc.jsonrpc.error_code = field::Empty,
+        mcp.tool = field::Empty,
+        mcp.session_id = %session_id,
+        trace_id = field::Empty,
+        span_id = field::Empty,
+        parent_span_id = field::Empty,
+    );
+    if let Some(tool) = tool {
+        span.record("mcp.tool", tool);
+    }
+    if let Some(id) = message.get("id") {
+        span.record("rpc.jsonrpc.request_id", field::display(id));
+    }
+    span
+}
+
+/// Record the outcome of a message on its span.
+pub(crate) fn record_outcome(span: &Span, response: Option<&Value>) {
+    let code = match response {
+        Some(response) => match response.get("error") {
+            Some(error) => error.get("code").and_then(Value::as_i64),
+            None => return,
+        },
+        None => None,
+    };
+    span.record("otel.status_code", "ERROR");
+    if let Some(code) = code {
+        span.record("rpc.jsonrpc.error_code", code);
+    }
+}
+
+fn is_hex(value: &str, len: usize) -> bool {
+    value.len() == len
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+/// Random non-zero ID of `N` bytes, as lowercase hex.
+fn random_id<const N: usize>() -> String {
+    loop {
+        let bytes: [u8; N] = rand::random();
+        if bytes.iter().any(|&b| b != 0) {
+            return bytes.iter().map(|b| format!("{b:02x}")).collect();
+        }
+    }
+}
+
+#[cfg(feature = "otel")]
+mod otel {
+    use {
+        super::TraceContext,
+        opentelemetry::{propagation::TextMapPropagator, trace::TraceContextExt},
+        opentelemetry_sdk::propagation::TraceContextPropagator,
+        std::collections::HashMap,
+        tracing::Span,
+        tracing_opentelemetry::OpenTelemetrySpanExt,
+    };
+
+    /// Make `span` a child of the caller's span and read its context back.
+    ///
+    /// # Returns
+    ///
+    /// `None` if no OpenTelemetry layer records the span
+    pub(super) fn continue_trace(
+        span: &Span,
+        carrier: &HashMap<String, String>,
+    ) -> Option<TraceContext> {
+        let parent = TraceContextPropagator::new().extract(carrier);
+        span.set_parent(parent.clone()).ok()?;
+        let context = span.context();
+        let own = context.span().span_context().clone();
+        if !own.is_valid() {
+            return None;
+        }
+        let caller = parent.span().span_context().clone();
+        let tracestate = own.trace_state().header();
+        Some(TraceContext {
+            trace_id: own.trace_id().to_string(),
+            span_id: own.span_id().to_string(),
+            parent_span_id: caller.is_valid().then(|| caller.span_id().to_string()),
+            sampled: own.is_sampled(),
+            tracestate: (!tracestate.is_empty()).then_some(tracestate),
+        })
+    }
+
+    /// Format the OpenTelemetry context of `span` as a `traceparent`.
+    pub(crate) fn traceparent(span: &Span) -> Option<String> {
+        let mut carrier = HashMap::new();
+        TraceContextPropagator::new().inject_context(&span.context(), &mut carrier);
+        carrier.remove("traceparent")
+    }
+}
+
+#[cfg(feature = "otel")]
+use otel::continue_trace;
+#[cfg(feature = "otel")]
+pub(crate) use otel::traceparent as span_traceparent;
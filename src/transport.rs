@@ -311,15 +311,17 @@ pub fn cors_headers() -> HeaderMap<HeaderValue> {
     headers.insert("access-control-allow-origin", HeaderValue::from_static("*"));
     headers.insert(
         "access-control-allow-methods",
-        HeaderValue::from_static("GET, POST, OPTIONS"),
+        HeaderValue::from_static("GET, POST, DELETE, OPTIONS"),
     );
     headers.insert(
         "access-control-allow-headers",
-        HeaderValue::from_static("content-type, upgrade, connection, x-mcp-protocol-version"),
+        HeaderValue::from_static(
+            "content-type, upgrade, connection, x-mcp-protocol-version, mcp-session-id, authorization, x-api-key",
+        ),
     );
     headers.insert(
         "access-control-expose-headers",
-        HeaderValue::from_static("x-mcp-protocol-version"),
+        HeaderValue::from_static("x-mcp-protocol-version, mcp-session-id, www-authenticate"),
     );
     headers.insert(
         "access-control-max-age",
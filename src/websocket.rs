@@ -3,8 +3,13 @@
 //! Handles WebSocket connections and message processing for the MCP server.
 
 use {
+    super::auth::{self, AccessDenied},
     super::logging::McpConnectionId,
+    super::handler::ConnectionInfo,
+    super::http::with_connection_info,
     super::logging::McpDebugLogger,
+    super::health::ServerPhase,
+    super::session_store::SessionEndReason,
     super::shared::McpProtocolEngine,
     anyhow::Result,
     futures_util::{SinkExt, StreamExt},
@@ -29,7 +34,7 @@ pub async fn handle_mcp_ws_main(ws: Ws) -> Result<impl Reply, Rejection> {
             connection_id
         );
 
-        handle_mcp_ws(websocket, logger, protocol_engine.clone()).await;
+        handle_mcp_ws(websocket, logger, protocol_engine.clone(), None).await;
 
         info!("🔌 MCP WebSocket connection closed: {:?}", connection_id);
     }))
@@ -41,22 +46,27 @@ pub fn create_ws_handler(
 ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     warp::path!("mcp")
         .and(warp::ws())
+        .and(with_connection_info(protocol_engine.clone()))
         .and(warp::any().map(move || protocol_engine.clone()))
-        .and_then(|ws: Ws, engine: Arc<McpProtocolEngine>| async move {
-            Ok::<_, Rejection>(ws.on_upgrade(move |websocket| async move {
-                let connection_id = McpConnectionId::new();
-                let logger = McpDebugLogger::new(connection_id.clone());
-
-                info!(
-                    "🔌 MCP WebSocket connection established: {:?}",
-                    connection_id
-                );
-
-                handle_mcp_ws(websocket, logger, engine.clone()).await;
-
-                info!("🔌 MCP WebSocket connection closed: {:?}", connection_id);
-            }))
-        })
+        .and_then(
+            |ws: Ws, connection_info: ConnectionInfo, engine: Arc<McpProtocolEngine>| async move {
+                Ok::<_, Rejection>(ws.on_upgrade(move |websocket| async move {
+                    let connection_id = McpConnectionId::new();
+                    let logger = McpDebugLogger::new(connection_id.clone());
+
+                    info!(
+                        "🔌 MCP WebSocket connection established: {:?}",
+                        connection_id
+                    );
+
+                    handle_mcp_ws(websocket, logger, engine.clone(), Some(connection_info)).await;
+
+                    info!("🔌 MCP WebSocket connection closed: {:?}", connection_id);
+                }))
+            },
+        )
+        // Upgrades that fail authentication get a 401 instead of a WebSocket
+        .recover(auth::handle_rejection)
 }
 
 /// Handle MCP WebSocket connection
@@ -64,13 +74,50 @@ async fn handle_mcp_ws(
     websocket: WebSocket,
     logger: McpDebugLogger,
     protocol_engine: Arc<McpProtocolEngine>,
+    connection_info: Option<ConnectionInfo>,
 ) {
     let (mut ws_sender, mut ws_receiver) = websocket.split();
     let session_id = format!("ws-{}", logger.connection_id().0);
 
     info!("{}", logger.fmt_connection_start());
 
-    while let Some(msg_result) = ws_receiver.next().await {
+    // Server-initiated notifications (e.g. list_changed) for this connection
+    let mut notifications = protocol_engine.notifier().subscribe(&session_id);
+    protocol_engine.metrics().websocket_opened(&session_id);
+    // Signalled when an operator terminates the session
+    let close = protocol_engine.introspection().websocket_opened(&session_id);
+    // Holding the session's handle keeps the store from expiring or evicting
+    // it while the connection is open
+    let mut lease = None;
+
+    loop {
+        if lease.is_none() {
+            lease = protocol_engine.session_store().get(&session_id).await;
+        }
+        protocol_engine
+            .metrics()
+            .notification_queue(&session_id, notifications.len());
+        let msg_result = tokio::select! {
+            incoming = ws_receiver.next() => match incoming {
+                Some(msg_result) => msg_result,
+                None => break,
+            },
+            Some(notification) = notifications.recv() => {
+                let notification_text = notification.to_json_rpc().to_string();
+                debug!("📤 Raw MCP Notification: {}", notification_text);
+                if let Err(e) = ws_sender.send(Message::text(notification_text)).await {
+                    error!("{}", logger.fmt_response_error(&e.to_string()));
+                    break;
+                }
+                continue;
+            }
+            () = close.notified() => {
+                info!("Closing terminated session {}", session_id);
+                let _ = ws_sender.send(Message::close()).await;
+                break;
+            }
+        };
+
         match msg_result {
             Ok(msg) => {
                 if msg.is_text() {
@@ -81,7 +128,11 @@ async fn handle_mcp_ws(
                     match serde_json::from_str::<Value>(text) {
                         Ok(message) => {
                             match protocol_engine
-                                .handle_message(message.clone(), Some(session_id.clone()))
+                                .handle_message_with_connection(
+                                    message.clone(),
+                                    Some(session_id.clone()),
+                                    connection_info.clone(),
+                                )
                                 .await
                             {
                                 Ok(response) => {
@@ -118,13 +169,19 @@ async fn handle_mcp_ws(
                                         message.get("id").unwrap_or(&json!(null)).clone();
 
                                     // Create error response
+                                    let error = match e.downcast_ref::<AccessDenied>() {
+                                        Some(denied) => {
+                                            json!({ "code": denied.code(), "message": denied.to_string() })
+                                        }
+                                        None => json!({
+                                            "code": -32603,
+                                            "message": format!("Internal error: {}", e)
+                                        }),
+                                    };
                                     let error_response = json!({
                                         "jsonrpc": "2.0",
                                         "id": message_id,
-                                        "error": {
-                                            "code": -32603,
-                                            "message": format!("Internal error: {}", e)
-                                        }
+                                        "error": error
                                     });
 
                                     let error_text = match serde_json::to_string(&error_response) {
@@ -178,5 +235,14 @@ async fn handle_mcp_ws(
         }
     }
 
+    protocol_engine.notifier().unsubscribe(&session_id);
+    protocol_engine.metrics().websocket_closed(&session_id);
+    protocol_engine.introspection().websocket_closed(&session_id);
+    let reason = match protocol_engine.readiness().phase() {
+        ServerPhase::ShuttingDown => SessionEndReason::Shutdown,
+        _ => SessionEndReason::Closed,
+    };
+    drop(lease);
+    protocol_engine.end_session(&session_id, reason).await;
     info!("{}", logger.fmt_connection_closed());
 }
@@ -11,6 +11,7 @@ use anyhow::Result;
 use std::sync::Arc;
 use async_trait::async_trait;
 use solidmcp::framework::{McpServerBuilder, PromptProvider};
+use solidmcp::content_types::Role;
 use solidmcp::handler::{PromptInfo, PromptContent, PromptMessage, PromptArgument};
 
 mod mcp_test_helpers;
@@ -87,14 +88,15 @@ impl PromptProvider<CachingTestContext> for CachingPromptProvider {
                 Ok(PromptContent {
                     messages: vec![
                         PromptMessage {
-                            role: "system".to_string(),
-                            content: "You are processing static content that should be cacheable.".to_string(),
+                            role: Role::User,
+                            content: "You are processing static content that should be cacheable.".into(),
                         },
                         PromptMessage {
-                            role: "user".to_string(),
-                            content: format!("Process this static input: {}", input),
+                            role: Role::User,
+                            content: format!("Process this static input: {}", input).into(),
                         },
                     ],
+                    ..Default::default()
                 })
             }
             "dynamic_prompt" => {
@@ -120,14 +122,15 @@ impl PromptProvider<CachingTestContext> for CachingPromptProvider {
                 Ok(PromptContent {
                     messages: vec![
                         PromptMessage {
-                            role: "system".to_string(),
-                            content: "You are processing dynamic content that changes over time.".to_string(),
+                            role: Role::User,
+                            content: "You are processing dynamic content that changes over time.".into(),
                         },
                         PromptMessage {
-                            role: "user".to_string(),
-                            content: format!("Dynamic content: {}", content),
+                            role: Role::User,
+                            content: format!("Dynamic content: {}", content).into(),
                         },
                     ],
+                    ..Default::default()
                 })
             }
             "timestamp_prompt" => {
@@ -147,14 +150,15 @@ impl PromptProvider<CachingTestContext> for CachingPromptProvider {
                 Ok(PromptContent {
                     messages: vec![
                         PromptMessage {
-                            role: "system".to_string(),
-                            content: "You are processing content that may include timestamps.".to_string(),
+                            role: Role::User,
+                            content: "You are processing content that may include timestamps.".into(),
                         },
                         PromptMessage {
-                            role: "user".to_string(),
-                            content,
+                            role: Role::User,
+                            content: content.into(),
                         },
                     ],
+                    ..Default::default()
                 })
             }
             _ => Err(anyhow::anyhow!("Prompt not found: {}", name))
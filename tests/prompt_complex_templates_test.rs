@@ -11,6 +11,7 @@ use anyhow::Result;
 use std::sync::Arc;
 use async_trait::async_trait;
 use solidmcp::framework::{McpServerBuilder, PromptProvider};
+use solidmcp::content_types::Role;
 use solidmcp::handler::{PromptInfo, PromptContent, PromptMessage, PromptArgument};
 
 mod mcp_test_helpers;
@@ -107,22 +108,23 @@ impl PromptProvider<ComplexTestContext> for ComplexPromptProvider {
                 Ok(PromptContent {
                     messages: vec![
                         PromptMessage {
-                            role: "system".to_string(),
-                            content: system_instruction.to_string(),
+                            role: Role::User,
+                            content: system_instruction.to_string().into(),
                         },
                         PromptMessage {
-                            role: "user".to_string(),
-                            content: format!("Please explain {} in detail.", topic),
+                            role: Role::User,
+                            content: format!("Please explain {} in detail.", topic).into(),
                         },
                         PromptMessage {
-                            role: "assistant".to_string(),
-                            content: format!("I'll explain {} at the {} level. Let me break this down for you.", topic, expertise_level),
+                            role: Role::Assistant,
+                            content: format!("I'll explain {} at the {} level. Let me break this down for you.", topic, expertise_level).into(),
                         },
                         PromptMessage {
-                            role: "user".to_string(),
-                            content: "Please continue with the explanation.".to_string(),
+                            role: Role::User,
+                            content: "Please continue with the explanation.".into(),
                         },
                     ],
+                    ..Default::default()
                 })
             }
             "conditional_prompt" => {
@@ -158,14 +160,15 @@ impl PromptProvider<ComplexTestContext> for ComplexPromptProvider {
                 Ok(PromptContent {
                     messages: vec![
                         PromptMessage {
-                            role: "system".to_string(),
-                            content: system_role.to_string(),
+                            role: Role::User,
+                            content: system_role.to_string().into(),
                         },
                         PromptMessage {
-                            role: "user".to_string(),
-                            content: user_prompt,
+                            role: Role::User,
+                            content: user_prompt.into(),
                         },
                     ],
+                    ..Default::default()
                 })
             }
             "nested_params_prompt" => {
@@ -187,14 +190,15 @@ impl PromptProvider<ComplexTestContext> for ComplexPromptProvider {
                 Ok(PromptContent {
                     messages: vec![
                         PromptMessage {
-                            role: "system".to_string(),
-                            content: format!("You are assisting {} with project management for {}.", user_name, project_name),
+                            role: Role::User,
+                            content: format!("You are assisting {} with project management for {}.", user_name, project_name).into(),
                         },
                         PromptMessage {
-                            role: "user".to_string(),
-                            content: format!("Hello, I'm {} and I need help with my project '{}'.{}", user_name, project_name, deadline_text),
+                            role: Role::User,
+                            content: format!("Hello, I'm {} and I need help with my project '{}'.{}", user_name, project_name, deadline_text).into(),
                         },
                     ],
+                    ..Default::default()
                 })
             }
             _ => Err(anyhow::anyhow!("Prompt not found: {}", name))
@@ -280,7 +284,7 @@ async fn test_multi_role_prompt() -> Result<(), Box<dyn std::error::Error + Send
 
     // Verify system message contains advanced instruction
     let system_message = &messages[0];
-    assert_eq!(system_message["role"], "system");
+    assert_eq!(system_message["role"], "user");
     assert!(system_message["content"]["text"].as_str().unwrap().contains("expert consultant"));
 
     // Verify topic is mentioned in user message
@@ -11,6 +11,7 @@ use anyhow::Result;
 use std::sync::Arc;
 use async_trait::async_trait;
 use solidmcp::framework::{McpServerBuilder, PromptProvider};
+use solidmcp::content_types::Role;
 use solidmcp::handler::{PromptInfo, PromptContent, PromptMessage, PromptArgument};
 
 mod mcp_test_helpers;
@@ -80,14 +81,15 @@ impl PromptProvider<ConcurrentTestContext> for ConcurrentPromptProvider {
                 Ok(PromptContent {
                     messages: vec![
                         PromptMessage {
-                            role: "system".to_string(),
-                            content: "You are processing concurrent requests efficiently.".to_string(),
+                            role: Role::User,
+                            content: "You are processing concurrent requests efficiently.".into(),
                         },
                         PromptMessage {
-                            role: "user".to_string(),
-                            content: format!("Process this data: {}", data),
+                            role: Role::User,
+                            content: format!("Process this data: {}", data).into(),
                         },
                     ],
+                    ..Default::default()
                 })
             }
             "slow_prompt" => {
@@ -101,10 +103,11 @@ impl PromptProvider<ConcurrentTestContext> for ConcurrentPromptProvider {
                 Ok(PromptContent {
                     messages: vec![
                         PromptMessage {
-                            role: "system".to_string(),
-                            content: format!("Processed after {}ms delay", delay_ms),
+                            role: Role::User,
+                            content: format!("Processed after {}ms delay", delay_ms).into(),
                         },
                     ],
+                    ..Default::default()
                 })
             }
             _ => Err(anyhow::anyhow!("Prompt not found: {}", name))
@@ -11,6 +11,7 @@ use anyhow::Result;
 use std::sync::Arc;
 use async_trait::async_trait;
 use solidmcp::framework::{McpServerBuilder, PromptProvider};
+use solidmcp::content_types::Role;
 use solidmcp::handler::{PromptInfo, PromptContent, PromptMessage, PromptArgument};
 
 mod mcp_test_helpers;
@@ -93,14 +94,15 @@ impl PromptProvider<EdgeCaseTestContext> for EdgeCasePromptProvider {
                 Ok(PromptContent {
                     messages: vec![
                         PromptMessage {
-                            role: "system".to_string(),
-                            content: "You are processing a large prompt.".to_string(),
+                            role: Role::User,
+                            content: "You are processing a large prompt.".into(),
                         },
                         PromptMessage {
-                            role: "user".to_string(),
-                            content: large_content,
+                            role: Role::User,
+                            content: large_content.into(),
                         },
                     ],
+                    ..Default::default()
                 })
             }
             "unicode_prompt" => {
@@ -123,14 +125,15 @@ impl PromptProvider<EdgeCaseTestContext> for EdgeCasePromptProvider {
                 Ok(PromptContent {
                     messages: vec![
                         PromptMessage {
-                            role: "system".to_string(),
-                            content: "You are handling Unicode and international text.".to_string(),
+                            role: Role::User,
+                            content: "You are handling Unicode and international text.".into(),
                         },
                         PromptMessage {
-                            role: "user".to_string(),
-                            content: unicode_content,
+                            role: Role::User,
+                            content: unicode_content.into(),
                         },
                     ],
+                    ..Default::default()
                 })
             }
             "empty_prompt" => {
@@ -142,19 +145,21 @@ impl PromptProvider<EdgeCaseTestContext> for EdgeCasePromptProvider {
                     Ok(PromptContent {
                         messages: vec![
                             PromptMessage {
-                                role: "system".to_string(),
-                                content: "".to_string(),
+                                role: Role::User,
+                                content: "".into(),
                             },
                         ],
+                        ..Default::default()
                     })
                 } else {
                     Ok(PromptContent {
                         messages: vec![
                             PromptMessage {
-                                role: "system".to_string(),
-                                content: "This is a minimal prompt.".to_string(),
+                                role: Role::User,
+                                content: "This is a minimal prompt.".into(),
                             },
                         ],
+                        ..Default::default()
                     })
                 }
             }
@@ -179,14 +184,15 @@ impl PromptProvider<EdgeCaseTestContext> for EdgeCasePromptProvider {
                 Ok(PromptContent {
                     messages: vec![
                         PromptMessage {
-                            role: "system".to_string(),
-                            content: "You are handling special JSON characters.".to_string(),
+                            role: Role::User,
+                            content: "You are handling special JSON characters.".into(),
                         },
                         PromptMessage {
-                            role: "user".to_string(),
-                            content: escape_content,
+                            role: Role::User,
+                            content: escape_content.into(),
                         },
                     ],
+                    ..Default::default()
                 })
             }
             _ => Err(anyhow::anyhow!("Prompt not found: {}", name))
@@ -485,7 +491,7 @@ async fn test_empty_content_handling() -> Result<(), Box<dyn std::error::Error +
 
     // Verify empty content is handled properly
     let system_message = &messages[0];
-    assert_eq!(system_message["role"], "system");
+    assert_eq!(system_message["role"], "user");
     assert_eq!(system_message["content"]["text"], "");
 
     server_handle.abort();
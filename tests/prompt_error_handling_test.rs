@@ -15,6 +15,7 @@ use mcp_test_helpers::*;
 async fn create_error_test_server() -> Result<u16> {
     use std::sync::Arc;
     use solidmcp::{
+        content_types::Role,
         framework::{McpServerBuilder, PromptProvider},
         handler::{PromptInfo, PromptContent, PromptMessage, PromptArgument},
     };
@@ -87,10 +88,11 @@ async fn create_error_test_server() -> Result<u16> {
                     Ok(PromptContent {
                         messages: vec![
                             PromptMessage {
-                                role: "user".to_string(),
-                                content,
+                                role: Role::User,
+                                content: content.into(),
                             }
                         ],
+                        ..Default::default()
                     })
                 }
                 "special_chars_prompt" => {
@@ -101,10 +103,11 @@ async fn create_error_test_server() -> Result<u16> {
                     Ok(PromptContent {
                         messages: vec![
                             PromptMessage {
-                                role: "user".to_string(),
-                                content: format!("Special text: {}", text),
+                                role: Role::User,
+                                content: format!("Special text: {}", text).into(),
                             }
                         ],
+                        ..Default::default()
                     })
                 }
                 _ => Err(anyhow::anyhow!("Prompt not found: {}", name))
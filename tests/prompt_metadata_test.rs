@@ -163,7 +163,7 @@ async fn test_prompt_arguments_validation() -> Result<(), Box<dyn std::error::Er
 
     // Check that default language "unknown" was used
     let system_message = &messages[0];
-    assert_eq!(system_message["role"], "system");
+    assert_eq!(system_message["role"], "user");
     assert!(system_message["content"]["text"].as_str().unwrap().contains("unknown"));
 
     server_handle.abort();
@@ -239,7 +239,7 @@ async fn test_prompt_mixed_parameters() -> Result<(), Box<dyn std::error::Error
 
     // Check that specified language "rust" was used
     let system_message = &messages[0];
-    assert_eq!(system_message["role"], "system");
+    assert_eq!(system_message["role"], "user");
     assert!(system_message["content"]["text"].as_str().unwrap().contains("rust"));
 
     // Check user message contains the code
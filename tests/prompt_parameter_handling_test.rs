@@ -15,6 +15,7 @@ use mcp_test_helpers::*;
 async fn create_test_prompt_server() -> Result<u16> {
     use std::sync::Arc;
     use solidmcp::{
+        content_types::Role,
         framework::{McpServerBuilder, PromptProvider},
         handler::{PromptInfo, PromptContent, PromptMessage, PromptArgument},
     };
@@ -96,10 +97,11 @@ async fn create_test_prompt_server() -> Result<u16> {
                     Ok(PromptContent {
                         messages: vec![
                             PromptMessage {
-                                role: "user".to_string(),
-                                content: format!("Hello, {}! How are you today?", name),
+                                role: Role::User,
+                                content: format!("Hello, {}! How are you today?", name).into(),
                             }
                         ],
+                        ..Default::default()
                     })
                 }
                 "complex_template" => {
@@ -118,17 +120,18 @@ async fn create_test_prompt_server() -> Result<u16> {
                     Ok(PromptContent {
                         messages: vec![
                             PromptMessage {
-                                role: "system".to_string(),
-                                content: "You are a document reviewer.".to_string(),
+                                role: Role::User,
+                                content: "You are a document reviewer.".into(),
                             },
                             PromptMessage {
-                                role: "user".to_string(),
+                                role: Role::User,
                                 content: format!(
                                     "Please review this document:\n\nTitle: {}\nAuthor: {}\nTags: {}",
                                     title, author, tags
-                                ),
+                                ).into(),
                             }
                         ],
+                        ..Default::default()
                     })
                 }
                 "numeric_template" => {
@@ -143,13 +146,14 @@ async fn create_test_prompt_server() -> Result<u16> {
                     Ok(PromptContent {
                         messages: vec![
                             PromptMessage {
-                                role: "user".to_string(),
+                                role: Role::User,
                                 content: format!(
                                     "Process {} items with {}% completion rate.",
                                     count, percentage
-                                ),
+                                ).into(),
                             }
                         ],
+                        ..Default::default()
                     })
                 }
                 _ => Err(anyhow::anyhow!("Prompt not found: {}", name))
@@ -7,6 +7,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::Value;
 use solidmcp::framework::{McpServerBuilder, PromptProvider};
+use solidmcp::content_types::Role;
 use solidmcp::handler::{PromptInfo, PromptContent, PromptMessage, PromptArgument};
 
 /// Test context for prompt tests
@@ -64,10 +65,11 @@ impl PromptProvider<TestContext> for TestPromptProvider {
                 Ok(PromptContent {
                     messages: vec![
                         PromptMessage {
-                            role: "user".to_string(),
-                            content: format!("Hello, {}!", name),
+                            role: Role::User,
+                            content: format!("Hello, {}!", name).into(),
                         }
                     ],
+                    ..Default::default()
                 })
             }
             "code_review" => {
@@ -83,14 +85,15 @@ impl PromptProvider<TestContext> for TestPromptProvider {
                 Ok(PromptContent {
                     messages: vec![
                         PromptMessage {
-                            role: "system".to_string(),
-                            content: format!("You are a code reviewer specializing in {}.", language),
+                            role: Role::User,
+                            content: format!("You are a code reviewer specializing in {}.", language).into(),
                         },
                         PromptMessage {
-                            role: "user".to_string(),
-                            content: format!("Please review this code:\n\n{}", code),
+                            role: Role::User,
+                            content: format!("Please review this code:\n\n{}", code).into(),
                         }
                     ],
+                    ..Default::default()
                 })
             }
             _ => Err(anyhow::anyhow!("Prompt not found: {}", name))
@@ -82,6 +82,7 @@ async fn test_capabilities_match_features() -> Result<()> {
     });
 
     let response = client.post(&url).json(&init_request).send().await?;
+    let session_id = response.headers()["mcp-session-id"].clone();
     let init_response: Value = response.json().await?;
 
     let has_tools_capability = init_response["result"]["capabilities"]["tools"].is_object();
@@ -96,7 +97,7 @@ async fn test_capabilities_match_features() -> Result<()> {
 
     let response = client
         .post(&url)
-        .header("Cookie", "mcp_session=http_default_session")
+        .header("Mcp-Session-Id", session_id)
         .json(&tools_request)
         .send()
         .await?;
@@ -32,30 +32,35 @@ impl ResourceProvider<()> for ErrorTestResourceProvider {
                 name: "not_found".to_string(),
                 description: Some("Resource that returns not found".to_string()),
                 mime_type: Some("text/plain".to_string()),
+                ..Default::default()
             },
             ResourceInfo {
                 uri: "error://access-denied".to_string(),
                 name: "access_denied".to_string(),
                 description: Some("Resource that returns access denied".to_string()),
                 mime_type: Some("text/plain".to_string()),
+                ..Default::default()
             },
             ResourceInfo {
                 uri: "error://timeout".to_string(),
                 name: "timeout".to_string(),
                 description: Some("Resource that simulates timeout".to_string()),
                 mime_type: Some("text/plain".to_string()),
+                ..Default::default()
             },
             ResourceInfo {
                 uri: "error://server-error".to_string(),
                 name: "server_error".to_string(),
                 description: Some("Resource that causes server error".to_string()),
                 mime_type: Some("text/plain".to_string()),
+                ..Default::default()
             },
             ResourceInfo {
                 uri: "valid://resource".to_string(),
                 name: "valid_resource".to_string(),
                 description: Some("Valid resource for comparison".to_string()),
                 mime_type: Some("text/plain".to_string()),
+                ..Default::default()
             },
         ])
     }
@@ -79,7 +84,7 @@ impl ResourceProvider<()> for ErrorTestResourceProvider {
             "valid://resource" => Ok(ResourceContent {
                 uri: uri.to_string(),
                 mime_type: Some("text/plain".to_string()),
-                content: "This is a valid resource for testing.".to_string(),
+                content: "This is a valid resource for testing.".into(),
             }),
             _ => Err(anyhow::anyhow!("Unknown resource: {}", uri)),
         }
@@ -33,18 +33,21 @@ impl ResourceProvider<()> for MetadataTestResourceProvider {
                 name: "plain_text".to_string(),
                 description: Some("Plain text content".to_string()),
                 mime_type: Some("text/plain".to_string()),
+                ..Default::default()
             },
             ResourceInfo {
                 uri: "data://text/markdown".to_string(),
                 name: "markdown_content".to_string(),
                 description: Some("Markdown formatted content".to_string()),
                 mime_type: Some("text/markdown".to_string()),
+                ..Default::default()
             },
             ResourceInfo {
                 uri: "data://text/html".to_string(),
                 name: "html_content".to_string(),
                 description: Some("HTML formatted content".to_string()),
                 mime_type: Some("text/html".to_string()),
+                ..Default::default()
             },
             // Structured data formats
             ResourceInfo {
@@ -52,18 +55,21 @@ impl ResourceProvider<()> for MetadataTestResourceProvider {
                 name: "json_data".to_string(),
                 description: Some("JSON structured data".to_string()),
                 mime_type: Some("application/json".to_string()),
+                ..Default::default()
             },
             ResourceInfo {
                 uri: "data://application/xml".to_string(),
                 name: "xml_data".to_string(),
                 description: Some("XML structured data".to_string()),
                 mime_type: Some("application/xml".to_string()),
+                ..Default::default()
             },
             ResourceInfo {
                 uri: "data://text/csv".to_string(),
                 name: "csv_data".to_string(),
                 description: Some("CSV tabular data".to_string()),
                 mime_type: Some("text/csv".to_string()),
+                ..Default::default()
             },
             // Binary-like content represented as text
             ResourceInfo {
@@ -71,6 +77,7 @@ impl ResourceProvider<()> for MetadataTestResourceProvider {
                 name: "base64_data".to_string(),
                 description: Some("Base64 encoded data".to_string()),
                 mime_type: Some("application/octet-stream".to_string()),
+                ..Default::default()
             },
             // Resource without MIME type
             ResourceInfo {
@@ -78,6 +85,7 @@ impl ResourceProvider<()> for MetadataTestResourceProvider {
                 name: "unknown_format".to_string(),
                 description: Some("Content with no specified MIME type".to_string()),
                 mime_type: None,
+                ..Default::default()
             },
             // Resource without description
             ResourceInfo {
@@ -85,6 +93,7 @@ impl ResourceProvider<()> for MetadataTestResourceProvider {
                 name: "minimal_info".to_string(),
                 description: None,
                 mime_type: Some("text/plain".to_string()),
+                ..Default::default()
             },
             // Large content resource
             ResourceInfo {
@@ -92,6 +101,7 @@ impl ResourceProvider<()> for MetadataTestResourceProvider {
                 name: "large_content".to_string(),
                 description: Some("Large content for size testing".to_string()),
                 mime_type: Some("text/plain".to_string()),
+                ..Default::default()
             },
         ])
     }
@@ -101,17 +111,17 @@ impl ResourceProvider<()> for MetadataTestResourceProvider {
             "data://text/plain" => Ok(ResourceContent {
                 uri: uri.to_string(),
                 mime_type: Some("text/plain".to_string()),
-                content: "This is plain text content with special characters: äöü, 日本語, 🚀".to_string(),
+                content: "This is plain text content with special characters: äöü, 日本語, 🚀".into(),
             }),
             "data://text/markdown" => Ok(ResourceContent {
                 uri: uri.to_string(),
                 mime_type: Some("text/markdown".to_string()),
-                content: "# Markdown Content\n\n## Features\n\n- **Bold text**\n- *Italic text*\n- [Link](https://example.com)\n- `Code snippet`\n\n```rust\nfn hello() {\n    println!(\"Hello, world!\");\n}\n```".to_string(),
+                content: "# Markdown Content\n\n## Features\n\n- **Bold text**\n- *Italic text*\n- [Link](https://example.com)\n- `Code snippet`\n\n```rust\nfn hello() {\n    println!(\"Hello, world!\");\n}\n```".into(),
             }),
             "data://text/html" => Ok(ResourceContent {
                 uri: uri.to_string(),
                 mime_type: Some("text/html".to_string()),
-                content: "<!DOCTYPE html>\n<html>\n<head><title>Test</title></head>\n<body>\n<h1>HTML Content</h1>\n<p>This is <strong>HTML</strong> with <em>formatting</em>.</p>\n<a href=\"https://example.com\">Link</a>\n</body>\n</html>".to_string(),
+                content: "<!DOCTYPE html>\n<html>\n<head><title>Test</title></head>\n<body>\n<h1>HTML Content</h1>\n<p>This is <strong>HTML</strong> with <em>formatting</em>.</p>\n<a href=\"https://example.com\">Link</a>\n</body>\n</html>".into(),
             }),
             "data://application/json" => Ok(ResourceContent {
                 uri: uri.to_string(),
@@ -127,32 +137,32 @@ impl ResourceProvider<()> for MetadataTestResourceProvider {
                     "numbers": [1, 2, 3.14, -42],
                     "boolean": true,
                     "null_value": null
-                }).to_string(),
+                }).to_string().into(),
             }),
             "data://application/xml" => Ok(ResourceContent {
                 uri: uri.to_string(),
                 mime_type: Some("application/xml".to_string()),
-                content: "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<root>\n  <item id=\"1\">\n    <name>Test Item</name>\n    <value>42</value>\n    <active>true</active>\n  </item>\n  <item id=\"2\">\n    <name>Another Item</name>\n    <value>3.14</value>\n    <active>false</active>\n  </item>\n</root>".to_string(),
+                content: "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<root>\n  <item id=\"1\">\n    <name>Test Item</name>\n    <value>42</value>\n    <active>true</active>\n  </item>\n  <item id=\"2\">\n    <name>Another Item</name>\n    <value>3.14</value>\n    <active>false</active>\n  </item>\n</root>".into(),
             }),
             "data://text/csv" => Ok(ResourceContent {
                 uri: uri.to_string(),
                 mime_type: Some("text/csv".to_string()),
-                content: "name,age,city,score\nAlice,30,New York,95.5\nBob,25,London,87.2\nCharlie,35,Tokyo,92.1\nDiana,28,\"San Francisco\",89.7".to_string(),
+                content: "name,age,city,score\nAlice,30,New York,95.5\nBob,25,London,87.2\nCharlie,35,Tokyo,92.1\nDiana,28,\"San Francisco\",89.7".into(),
             }),
             "data://application/base64" => Ok(ResourceContent {
                 uri: uri.to_string(),
                 mime_type: Some("application/octet-stream".to_string()),
-                content: "SGVsbG8sIFdvcmxkISBUaGlzIGlzIGEgYmFzZTY0IGVuY29kZWQgbWVzc2FnZS4gSXQgY29udGFpbnMgc3BlY2lhbCBjaGFyYWN0ZXJzOiDDpMO2w7wsIOaXpeacrOiqniwg8J+agA==".to_string(),
+                content: "SGVsbG8sIFdvcmxkISBUaGlzIGlzIGEgYmFzZTY0IGVuY29kZWQgbWVzc2FnZS4gSXQgY29udGFpbnMgc3BlY2lhbCBjaGFyYWN0ZXJzOiDDpMO2w7wsIOaXpeacrOiqniwg8J+agA==".into(),
             }),
             "data://unknown/format" => Ok(ResourceContent {
                 uri: uri.to_string(),
                 mime_type: None,
-                content: "This content has no specified MIME type and could be anything.".to_string(),
+                content: "This content has no specified MIME type and could be anything.".into(),
             }),
             "data://minimal/info" => Ok(ResourceContent {
                 uri: uri.to_string(),
                 mime_type: Some("text/plain".to_string()),
-                content: "Minimal resource info.".to_string(),
+                content: "Minimal resource info.".into(),
             }),
             "data://large/content" => {
                 // Generate large content (about 50KB)
@@ -163,7 +173,7 @@ impl ResourceProvider<()> for MetadataTestResourceProvider {
                 Ok(ResourceContent {
                     uri: uri.to_string(),
                     mime_type: Some("text/plain".to_string()),
-                    content: large_content,
+                    content: large_content.into(),
                 })
             },
             _ => Err(anyhow::anyhow!("Resource not found: {}", uri)),
@@ -34,18 +34,21 @@ impl TestResourceProvider {
                     name: "simple".to_string(),
                     description: Some("Simple test resource".to_string()),
                     mime_type: Some("text/plain".to_string()),
+                    ..Default::default()
                 },
                 ResourceInfo {
                     uri: "test://markdown".to_string(),
                     name: "markdown".to_string(),
                     description: Some("Markdown test resource".to_string()),
                     mime_type: Some("text/markdown".to_string()),
+                    ..Default::default()
                 },
                 ResourceInfo {
                     uri: "file:///test/file.txt".to_string(),
                     name: "file.txt".to_string(),
                     description: None,
                     mime_type: Some("text/plain".to_string()),
+                    ..Default::default()
                 },
             ],
         }
@@ -63,17 +66,17 @@ impl ResourceProvider<()> for TestResourceProvider {
             "test://simple" => Ok(ResourceContent {
                 uri: uri.to_string(),
                 mime_type: Some("text/plain".to_string()),
-                content: "Hello, simple resource!".to_string(),
+                content: "Hello, simple resource!".into(),
             }),
             "test://markdown" => Ok(ResourceContent {
                 uri: uri.to_string(),
                 mime_type: Some("text/markdown".to_string()),
-                content: "# Markdown Resource\n\nThis is a **markdown** resource.".to_string(),
+                content: "# Markdown Resource\n\nThis is a **markdown** resource.".into(),
             }),
             "file:///test/file.txt" => Ok(ResourceContent {
                 uri: uri.to_string(),
                 mime_type: Some("text/plain".to_string()),
-                content: "File content from disk".to_string(),
+                content: "File content from disk".into(),
             }),
             _ => Err(anyhow::anyhow!("Resource not found: {}", uri)),
         }
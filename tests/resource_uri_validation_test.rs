@@ -32,18 +32,21 @@ impl ResourceProvider<()> for UriValidationResourceProvider {
                 name: "file_resource".to_string(),
                 description: Some("File scheme resource".to_string()),
                 mime_type: Some("text/plain".to_string()),
+                ..Default::default()
             },
             ResourceInfo {
                 uri: "http://example.com/resource".to_string(),
                 name: "http_resource".to_string(),
                 description: Some("HTTP scheme resource".to_string()),
                 mime_type: Some("application/json".to_string()),
+                ..Default::default()
             },
             ResourceInfo {
                 uri: "https://secure.example.com/data".to_string(),
                 name: "https_resource".to_string(),
                 description: Some("HTTPS scheme resource".to_string()),
                 mime_type: Some("application/json".to_string()),
+                ..Default::default()
             },
             // Custom schemes
             ResourceInfo {
@@ -51,12 +54,14 @@ impl ResourceProvider<()> for UriValidationResourceProvider {
                 name: "custom_resource".to_string(),
                 description: Some("Custom scheme resource".to_string()),
                 mime_type: Some("application/x-custom".to_string()),
+                ..Default::default()
             },
             ResourceInfo {
                 uri: "mcp://internal/config".to_string(),
                 name: "mcp_resource".to_string(),
                 description: Some("MCP internal resource".to_string()),
                 mime_type: Some("application/json".to_string()),
+                ..Default::default()
             },
             // Edge case URIs
             ResourceInfo {
@@ -64,12 +69,14 @@ impl ResourceProvider<()> for UriValidationResourceProvider {
                 name: "complex_uri".to_string(),
                 description: Some("Complex URI with all components".to_string()),
                 mime_type: Some("text/html".to_string()),
+                ..Default::default()
             },
             ResourceInfo {
                 uri: "unicode://café/naïve-résumé.txt".to_string(),
                 name: "unicode_resource".to_string(),
                 description: Some("Unicode characters in URI".to_string()),
                 mime_type: Some("text/plain".to_string()),
+                ..Default::default()
             },
             // Special characters
             ResourceInfo {
@@ -77,6 +84,7 @@ impl ResourceProvider<()> for UriValidationResourceProvider {
                 name: "encoded_resource".to_string(),
                 description: Some("URL-encoded characters".to_string()),
                 mime_type: Some("text/plain".to_string()),
+                ..Default::default()
             },
         ])
     }
@@ -122,7 +130,7 @@ impl ResourceProvider<()> for UriValidationResourceProvider {
         Ok(ResourceContent {
             uri: uri.to_string(),
             mime_type,
-            content,
+            content: content.into(),
         })
     }
 }
@@ -188,8 +188,10 @@ async fn test_uninitialized_session_rejection() {
             "method": "tools/list"
         });
 
+        // in a session that was never initialized
         let response = client
             .post(&server.http_url())
+            .header("Mcp-Session-Id", "never-initialized")
             .json(&tools_request)
             .send()
             .await?;
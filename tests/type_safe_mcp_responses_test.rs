@@ -145,7 +145,7 @@ fn test_mcp_content_types() {
     let image_json = serde_json::to_value(&image_content).unwrap();
     assert_eq!(image_json["type"], "image");
     assert_eq!(image_json["data"], "base64data");
-    assert_eq!(image_json["mime_type"], "image/png");
+    assert_eq!(image_json["mimeType"], "image/png");
 
     let resource_content = McpContent::resource(
         "file://results.json", 
@@ -154,9 +154,9 @@ fn test_mcp_content_types() {
     );
     let resource_json = serde_json::to_value(&resource_content).unwrap();
     assert_eq!(resource_json["type"], "resource");
-    assert_eq!(resource_json["uri"], "file://results.json");
-    assert_eq!(resource_json["mime_type"], "application/json");
-    assert_eq!(resource_json["text"], "Search results");
+    assert_eq!(resource_json["resource"]["uri"], "file://results.json");
+    assert_eq!(resource_json["resource"]["mimeType"], "application/json");
+    assert_eq!(resource_json["resource"]["text"], "Search results");
 
     println!("✅ MCP content types serialize correctly");
 }